@@ -0,0 +1,240 @@
+use crate::years::YEARS;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Shell, String> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!(
+                "Unsupported shell {}, expected bash, zsh or fish",
+                s
+            )),
+        }
+    }
+}
+
+const SHELLS: &str = "bash zsh fish";
+
+// Subcommands and their descriptions
+const COMMANDS: &[(&str, &str)] = &[
+    ("run", "Run a day"),
+    ("watch", "Re-run a day whenever its source or input changes"),
+    ("history", "Show how a day's run times have changed"),
+    ("completions", "Print a shell completion script"),
+];
+
+// Subcommands which take the flags below
+const DAY_COMMANDS: &[&str] = &["run", "watch", "history"];
+
+// Flags without values are switches
+struct Flag {
+    name: &'static str,
+    help: &'static str,
+    values: Vec<String>,
+}
+
+fn flags() -> Vec<Flag> {
+    let mut days = YEARS
+        .iter()
+        .flat_map(|year| year.days.iter().map(|day| day.day))
+        .collect::<Vec<_>>();
+    days.sort_unstable();
+    days.dedup();
+
+    vec![
+        Flag {
+            name: "year",
+            help: "Year the day belongs to",
+            values: YEARS.iter().map(|year| year.year.to_string()).collect(),
+        },
+        Flag {
+            name: "day",
+            help: "Day to run",
+            values: days.iter().map(u8::to_string).collect(),
+        },
+        Flag {
+            name: "part",
+            help: "Only run one part",
+            values: vec!["1".to_owned(), "2".to_owned()],
+        },
+        Flag {
+            name: "both-parts",
+            help: "Parse the input once for both parts",
+            values: vec![],
+        },
+        Flag {
+            name: "explain",
+            help: "Print the steps taken to reach the answers",
+            values: vec![],
+        },
+    ]
+}
+
+/// Generate a completion script for the `aoc` CLI.
+pub fn generate(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    }
+}
+
+fn bash() -> String {
+    let flags = flags();
+    let commands = COMMANDS.iter().map(|(c, _)| *c).collect::<Vec<_>>();
+    let mut script = String::from(
+        "_aoc() {\n    \
+             local cur prev\n    \
+             cur=\"${COMP_WORDS[COMP_CWORD]}\"\n    \
+             prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n    \
+             case \"$prev\" in\n",
+    );
+    for flag in flags.iter().filter(|f| !f.values.is_empty()) {
+        script += &format!(
+            "        --{})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return\n            ;;\n",
+            flag.name,
+            flag.values.join(" ")
+        );
+    }
+    script += &format!(
+        "        completions)\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return\n            ;;\n    esac\n\n",
+        SHELLS
+    );
+    script += &format!(
+        "    if [ \"$COMP_CWORD\" -eq 1 ]; then\n        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n        return\n    fi\n\n",
+        commands.join(" ")
+    );
+    script += &format!(
+        "    case \"${{COMP_WORDS[1]}}\" in\n        {})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            ;;\n    esac\n}}\n\ncomplete -F _aoc aoc\n",
+        DAY_COMMANDS.join("|"),
+        flags
+            .iter()
+            .map(|f| format!("--{}", f.name))
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    script
+}
+
+fn zsh() -> String {
+    let mut script = String::from(
+        "#compdef aoc\n\n\
+         _aoc() {\n    \
+             local line state\n    \
+             _arguments -C \\\n        \
+                 '1: :->command' \\\n        \
+                 '*:: :->args'\n\n    \
+             case $state in\n        \
+                 command)\n            \
+                     _values 'command'",
+    );
+    for (command, help) in COMMANDS {
+        script += &format!(" \\\n                '{}[{}]'", command, help);
+    }
+    script += &format!(
+        "\n            ;;\n        args)\n            case $line[1] in\n                {})\n                    _arguments",
+        DAY_COMMANDS.join("|")
+    );
+    for flag in flags() {
+        script += &format!(
+            " \\\n                        '--{}[{}]",
+            flag.name, flag.help
+        );
+        if !flag.values.is_empty() {
+            script += &format!(":{}:({})", flag.name, flag.values.join(" "));
+        }
+        script.push('\'');
+    }
+    script += &format!(
+        "\n                    ;;\n                completions)\n                    _arguments '1:shell:({})'\n                    ;;\n            esac\n            ;;\n    esac\n}}\n\n_aoc \"$@\"\n",
+        SHELLS
+    );
+    script
+}
+
+fn fish() -> String {
+    let mut script = String::from("complete -c aoc -f\n");
+    for (command, help) in COMMANDS {
+        script += &format!(
+            "complete -c aoc -n '__fish_use_subcommand' -a {} -d '{}'\n",
+            command, help
+        );
+    }
+    for flag in flags() {
+        let values = if flag.values.is_empty() {
+            String::new()
+        } else {
+            format!(" -x -a '{}'", flag.values.join(" "))
+        };
+        script += &format!(
+            "complete -c aoc -n '__fish_seen_subcommand_from {}' -l {}{} -d '{}'\n",
+            DAY_COMMANDS.join(" "),
+            flag.name,
+            values,
+            flag.help
+        );
+    }
+    script += &format!(
+        "complete -c aoc -n '__fish_seen_subcommand_from completions' -x -a '{}'\n",
+        SHELLS
+    );
+    script
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_shells() {
+        assert_eq!("bash".parse(), Ok(Shell::Bash));
+        assert_eq!("zsh".parse(), Ok(Shell::Zsh));
+        assert_eq!("fish".parse(), Ok(Shell::Fish));
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn scripts_cover_commands_days_and_flags() {
+        for shell in &[Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = generate(*shell);
+            for (command, _) in COMMANDS {
+                assert!(
+                    script.contains(command),
+                    "{:?} is missing {}",
+                    shell,
+                    command
+                );
+            }
+            assert!(script.contains("year"), "{:?} is missing --year", shell);
+            assert!(script.contains("2019"), "{:?} is missing years", shell);
+            assert!(script.contains("day"), "{:?} is missing --day", shell);
+            assert!(script.contains("part"), "{:?} is missing --part", shell);
+            assert!(
+                script.contains("both-parts"),
+                "{:?} is missing --both-parts",
+                shell
+            );
+            assert!(
+                script.contains("explain"),
+                "{:?} is missing --explain",
+                shell
+            );
+            assert!(
+                script.contains("1 2 3 4 5 6 7 8 9 10 11"),
+                "{:?} is missing days",
+                shell
+            );
+        }
+    }
+}
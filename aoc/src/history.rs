@@ -0,0 +1,336 @@
+use rusqlite::{params, Connection, OpenFlags};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Where run history is kept: `history.sqlite` in the workspace root, so
+/// every run lands in the same place whichever directory it starts from,
+/// unless `AOC_HISTORY` names another file.
+pub fn path() -> PathBuf {
+    match env::var_os("AOC_HISTORY") {
+        Some(path) => PathBuf::from(path),
+        // This crate sits right under the workspace root
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("history.sqlite"),
+    }
+}
+
+/// A single timed run of one part of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub duration: Duration,
+    /// Commit the solution was built from, if it could be determined
+    pub commit: Option<String>,
+    /// Whether this was an optimized build. Debug timings aren't
+    /// comparable with release ones.
+    pub release: bool,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+impl Run {
+    pub fn new(year: u16, day: u8, part: u8, answer: String, duration: Duration) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            answer,
+            duration,
+            commit: current_commit(),
+            release: !cfg!(debug_assertions),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+}
+
+// Ask git which commit we're on, marking uncommitted changes
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_owned())
+}
+
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let conn = Connection::open(path).map_err(|e| format!("Failed to open history: {}", e))?;
+        Self::init(conn)
+    }
+
+    /// Open history only to read it, without creating it or its table if
+    /// they're missing. Returns `None` when nothing has been recorded yet.
+    pub fn open_existing(path: impl AsRef<Path>) -> Result<Option<Self>, String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("Failed to open history: {}", e))?;
+        Ok(Some(Self { conn }))
+    }
+
+    #[cfg(test)]
+    fn open_in_memory() -> Result<Self, String> {
+        let conn =
+            Connection::open_in_memory().map_err(|e| format!("Failed to open history: {}", e))?;
+        Self::init(conn)
+    }
+
+    fn init(conn: Connection) -> Result<Self, String> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS runs (
+                id INTEGER PRIMARY KEY,
+                year INTEGER NOT NULL,
+                day INTEGER NOT NULL,
+                part INTEGER NOT NULL,
+                answer TEXT NOT NULL,
+                duration_ns INTEGER NOT NULL,
+                git_commit TEXT,
+                release INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| format!("Failed to create history table: {}", e))?;
+        Ok(Self { conn })
+    }
+
+    pub fn record(&self, run: &Run) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT INTO runs (year, day, part, answer, duration_ns, git_commit, release, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    run.year,
+                    run.day,
+                    run.part,
+                    run.answer,
+                    run.duration.as_nanos() as i64,
+                    run.commit,
+                    run.release,
+                    run.timestamp as i64,
+                ],
+            )
+            .map_err(|e| format!("Failed to record run: {}", e))?;
+        Ok(())
+    }
+
+    /// All recorded runs of a day, oldest first.
+    pub fn runs(&self, year: u16, day: u8, part: Option<u8>) -> Result<Vec<Run>, String> {
+        let mut statement = self
+            .conn
+            .prepare(
+                "SELECT year, day, part, answer, duration_ns, git_commit, release, timestamp
+                 FROM runs
+                 WHERE year = ?1 AND day = ?2 AND (?3 IS NULL OR part = ?3)
+                 ORDER BY timestamp, id",
+            )
+            .map_err(|e| format!("Failed to query history: {}", e))?;
+        let runs = statement
+            .query_map(params![year, day, part], |row| {
+                Ok(Run {
+                    year: row.get(0)?,
+                    day: row.get(1)?,
+                    part: row.get(2)?,
+                    answer: row.get(3)?,
+                    duration: Duration::from_nanos(row.get::<_, i64>(4)? as u64),
+                    commit: row.get(5)?,
+                    release: row.get(6)?,
+                    timestamp: row.get::<_, i64>(7)? as u64,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to read history: {}", e))?;
+        Ok(runs)
+    }
+}
+
+/// Print the recorded runs of a day, one table per part and build
+/// profile, along with how each run compares to the one before it.
+pub fn report(runs: &[Run]) -> String {
+    let mut report = String::new();
+    for part in 1..=2 {
+        for &release in &[true, false] {
+            let runs = runs
+                .iter()
+                .filter(|r| r.part == part && r.release == release)
+                .collect::<Vec<_>>();
+            let (first, last) = match (runs.first(), runs.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => continue,
+            };
+
+            report += &format!(
+                "Part {} ({} builds)\n",
+                part,
+                if release { "release" } else { "debug" }
+            );
+            let mut previous: Option<Duration> = None;
+            for run in &runs {
+                let change = previous
+                    .map(|p| format!("{:+.1}%", percent_change(p, run.duration)))
+                    .unwrap_or_default();
+                report += &format!(
+                    "  {}  {:<16}  {:>14}  {:>8}  {}\n",
+                    date(run.timestamp),
+                    run.commit.as_deref().unwrap_or("unknown"),
+                    format!("{:?}", run.duration),
+                    change,
+                    run.answer.lines().next().unwrap_or(""),
+                );
+                previous = Some(run.duration);
+            }
+
+            let best = runs.iter().map(|r| r.duration).min().unwrap_or_default();
+            report += &format!(
+                "  {} runs, best {:?}, latest {:?} ({:+.1}% since the first run)\n",
+                runs.len(),
+                best,
+                last.duration,
+                percent_change(first.duration, last.duration)
+            );
+        }
+    }
+
+    if report.is_empty() {
+        report.push_str("No runs recorded yet\n");
+    }
+    report
+}
+
+// Format a unix timestamp as a UTC date and time, using Howard Hinnant's
+// days-to-civil algorithm
+fn date(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let seconds = timestamp % 86_400;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds % 3_600 / 60
+    )
+}
+
+fn percent_change(from: Duration, to: Duration) -> f64 {
+    if from.as_nanos() == 0 {
+        return 0.0;
+    }
+
+    (to.as_secs_f64() - from.as_secs_f64()) / from.as_secs_f64() * 100.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run(day: u8, part: u8, millis: u64, timestamp: u64) -> Run {
+        Run {
+            year: 2019,
+            day,
+            part,
+            answer: "42".to_owned(),
+            duration: Duration::from_millis(millis),
+            commit: Some("abc1234".to_owned()),
+            release: true,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn records_and_reads_runs() {
+        let history = History::open_in_memory().unwrap();
+        let runs = vec![run(3, 1, 20, 2), run(3, 2, 30, 1), run(4, 1, 5, 3)];
+        for run in &runs {
+            history.record(run).unwrap();
+        }
+
+        assert_eq!(
+            history.runs(2019, 3, None).unwrap(),
+            vec![runs[1].clone(), runs[0].clone()]
+        );
+        assert_eq!(
+            history.runs(2019, 3, Some(1)).unwrap(),
+            vec![runs[0].clone()]
+        );
+        assert_eq!(history.runs(2019, 5, None).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn reads_without_creating() {
+        let path = env::temp_dir().join(format!("aoc-history-{}.sqlite", std::process::id()));
+        assert!(History::open_existing(&path).unwrap().is_none());
+        assert!(!path.exists());
+
+        History::open(&path)
+            .unwrap()
+            .record(&run(3, 1, 20, 1))
+            .unwrap();
+        let history = History::open_existing(&path).unwrap().unwrap();
+        assert_eq!(history.runs(2019, 3, None).unwrap().len(), 1);
+        assert!(history.record(&run(3, 1, 20, 2)).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn finds_the_workspace_root() {
+        if env::var_os("AOC_HISTORY").is_none() {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+            assert_eq!(path(), root.join("history.sqlite"));
+            assert!(root.join("Cargo.lock").exists());
+        }
+    }
+
+    #[test]
+    fn reports_trends() {
+        let report = report(&[run(3, 1, 20, 1), run(3, 1, 10, 2)]);
+        assert!(report.contains("Part 1 (release builds)"));
+        assert!(report.contains("-50.0%"));
+        assert!(report.contains("2 runs, best 10ms, latest 10ms"));
+        assert!(!report.contains("Part 2"));
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(date(0), "1970-01-01 00:00");
+        assert_eq!(date(951_782_400), "2000-02-29 00:00");
+        assert_eq!(date(1_575_176_400 + 3_661), "2019-12-01 06:01");
+    }
+
+    #[test]
+    fn reports_empty_history() {
+        assert_eq!(report(&[]), "No runs recorded yet\n");
+    }
+}
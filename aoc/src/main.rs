@@ -0,0 +1,397 @@
+mod completions;
+mod history;
+mod watch;
+mod years;
+
+use aoc_runner::{Day, Reporter};
+use completions::Shell;
+use history::{History, Run};
+use std::{env, fmt::Display, fs, process, str::FromStr, time::Instant};
+
+const USAGE: &str = "\
+Usage:
+    aoc run [--year <year>] --day <day> [--part <part> | --both-parts] [--explain]
+    aoc watch [--year <year>] --day <day> [--part <part> | --both-parts] [--explain]
+    aoc history [--year <year>] --day <day> [--part <part>]
+    aoc completions <bash|zsh|fish>
+
+The year defaults to the most recent one with solutions. Every run is
+recorded in history.sqlite, which `aoc history` reports on. --explain
+prints the steps a day took to reach its answers, for days that support it.
+--both-parts parses the input once and shares it between both parts.";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    year: Option<u16>,
+    day: u8,
+    part: Option<u8>,
+    both_parts: bool,
+    explain: bool,
+}
+
+impl Options {
+    // Find the registered solution for the requested day
+    fn resolve(&self) -> Result<&'static Day, String> {
+        let year = match self.year {
+            Some(year) => years::year(year).ok_or_else(|| format!("No solutions for {}", year))?,
+            None => years::latest(),
+        };
+        year.day(self.day)
+            .ok_or_else(|| format!("Day {} of {} is not solved", self.day, year.year))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(Options),
+    Watch(Options),
+    History(Options),
+    Completions(Shell),
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let command = args.next().ok_or("Missing command")?;
+    match command.as_str() {
+        "run" => Ok(Command::Run(parse_options(args)?)),
+        "watch" => Ok(Command::Watch(parse_options(args)?)),
+        "history" => Ok(Command::History(parse_options(args)?)),
+        "completions" => {
+            let shell = args.next().ok_or("Missing shell")?.parse()?;
+            match args.next() {
+                Some(arg) => Err(format!("Unexpected argument {}", arg)),
+                None => Ok(Command::Completions(shell)),
+            }
+        }
+        _ => Err(format!("Unknown command {}", command)),
+    }
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut both_parts = false;
+    let mut explain = false;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--both-parts" => {
+                both_parts = true;
+                continue;
+            }
+            "--explain" => {
+                explain = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", flag))?;
+        match flag.as_str() {
+            "--year" => year = Some(parse_value(&flag, &value)?),
+            "--day" => day = Some(parse_value(&flag, &value)?),
+            "--part" => match parse_value(&flag, &value)? {
+                p @ 1..=2 => part = Some(p),
+                p => return Err(format!("There is no part {}", p)),
+            },
+            _ => return Err(format!("Unknown flag {}", flag)),
+        }
+    }
+
+    if both_parts && part.is_some() {
+        return Err("--both-parts can't be combined with --part".to_owned());
+    }
+
+    Ok(Options {
+        year,
+        day: day.ok_or("Missing --day")?,
+        part,
+        both_parts,
+        explain,
+    })
+}
+
+fn parse_value<T>(flag: &str, value: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| format!("Invalid value for {}: {}", flag, e))
+}
+
+// Some answers and explanations are drawings, so give them a line of their own
+fn separator(text: &str) -> &'static str {
+    if text.contains('\n') {
+        "\n"
+    } else {
+        " "
+    }
+}
+
+struct StdoutReporter;
+
+impl Reporter for StdoutReporter {
+    fn report(&mut self, label: &str, detail: &str) {
+        println!("  {}:{}{}", label, separator(detail), detail.trim_end());
+    }
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let day = options.resolve()?;
+    let explain = match (options.explain, day.explain) {
+        (false, _) => None,
+        (true, Some(explain)) => Some(explain),
+        (true, None) => {
+            return Err(format!(
+                "Day {} of {} can't explain its answers",
+                day.day, day.year
+            ))
+        }
+    };
+    let input_path = day.input_path();
+    let input = fs::read_to_string(&input_path)
+        .map_err(|e| format!("Failed to open {}: {}", input_path.display(), e))?;
+    // Losing history shouldn't stop anyone from getting their answer
+    let history = History::open(history::path())
+        .map_err(|e| eprintln!("Not recording this run: {}", e))
+        .ok();
+
+    let mut answers = vec![];
+    let mut total = None;
+    if options.both_parts {
+        let timings = (day.both_parts)(&input)?;
+        println!("{} day {} parsed in {:?}", day.year, day.day, timings.parse);
+        total = Some(timings.total());
+        for (part, (answer, elapsed)) in (1..).zip(timings.answers) {
+            answers.push((part, answer, elapsed, elapsed + timings.parse));
+        }
+    } else {
+        for (part, solve) in (1..).zip(&day.parts) {
+            if options.part.is_some_and(|p| p != part) {
+                continue;
+            }
+
+            let start = Instant::now();
+            let answer = solve(&input)?;
+            let elapsed = start.elapsed();
+            answers.push((part, answer, elapsed, elapsed));
+        }
+    }
+
+    // Parts normally parse their own input, so history includes parsing in
+    // each part's time to keep both kinds of run comparable
+    for (part_number, answer, elapsed, recorded) in answers {
+        println!(
+            "{} day {} part {} ({:?}):{}{}",
+            day.year,
+            day.day,
+            part_number,
+            elapsed,
+            separator(&answer),
+            answer.trim_end()
+        );
+
+        if let Some(history) = &history {
+            let run = Run::new(day.year, day.day, part_number, answer, recorded);
+            if let Err(e) = history.record(&run) {
+                eprintln!("Not recording this run: {}", e);
+            }
+        }
+    }
+
+    if let Some(total) = total {
+        println!("{} day {} total: {:?}", day.year, day.day, total);
+    }
+
+    if let Some(explain) = explain {
+        println!("Explaining {} day {}:", day.year, day.day);
+        explain(&input, &mut StdoutReporter)?;
+    }
+
+    Ok(())
+}
+
+fn history(options: &Options) -> Result<(), String> {
+    let day = options.resolve()?;
+    // Looking at history shouldn't leave an empty one behind
+    let runs = match History::open_existing(history::path())? {
+        Some(history) => history.runs(day.year, day.day, options.part)?,
+        None => vec![],
+    };
+    println!("{} day {}", day.year, day.day);
+    print!("{}", history::report(&runs));
+    Ok(())
+}
+
+fn main() {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+
+    let result = match command {
+        Command::Run(options) => run(&options),
+        Command::Watch(options) => watch::watch(&options),
+        Command::History(options) => history(&options),
+        Command::Completions(shell) => {
+            print!("{}", completions::generate(shell));
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|&s| s.to_owned())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parses_run() {
+        assert_eq!(
+            parse_args(args(&["run", "--day", "3"])),
+            Ok(Command::Run(Options {
+                year: None,
+                day: 3,
+                part: None,
+                both_parts: false,
+                explain: false
+            }))
+        );
+        assert_eq!(
+            parse_args(args(&[
+                "run", "--part", "2", "--day", "10", "--year", "2019"
+            ])),
+            Ok(Command::Run(Options {
+                year: Some(2019),
+                day: 10,
+                part: Some(2),
+                both_parts: false,
+                explain: false
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_watch() {
+        assert_eq!(
+            parse_args(args(&["watch", "--day", "10"])),
+            Ok(Command::Watch(Options {
+                year: None,
+                day: 10,
+                part: None,
+                both_parts: false,
+                explain: false
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_explain() {
+        assert_eq!(
+            parse_args(args(&["run", "--explain", "--day", "6"])),
+            Ok(Command::Run(Options {
+                year: None,
+                day: 6,
+                part: None,
+                both_parts: false,
+                explain: true
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_both_parts() {
+        assert_eq!(
+            parse_args(args(&["run", "--day", "11", "--both-parts"])),
+            Ok(Command::Run(Options {
+                year: None,
+                day: 11,
+                part: None,
+                both_parts: true,
+                explain: false
+            }))
+        );
+    }
+
+    #[test]
+    fn resolves_explanations() {
+        let options = |day| Options {
+            year: None,
+            day,
+            part: None,
+            both_parts: false,
+            explain: true,
+        };
+        for &day in &[3, 6, 10] {
+            assert!(options(day).resolve().unwrap().explain.is_some());
+        }
+        assert!(options(1).resolve().unwrap().explain.is_none());
+    }
+
+    #[test]
+    fn parses_history() {
+        assert_eq!(
+            parse_args(args(&["history", "--day", "3", "--part", "1"])),
+            Ok(Command::History(Options {
+                year: None,
+                day: 3,
+                part: Some(1),
+                both_parts: false,
+                explain: false
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_completions() {
+        assert_eq!(
+            parse_args(args(&["completions", "zsh"])),
+            Ok(Command::Completions(Shell::Zsh))
+        );
+    }
+
+    #[test]
+    fn resolves_registered_days() {
+        let options = |year, day| Options {
+            year,
+            day,
+            part: None,
+            both_parts: false,
+            explain: false,
+        };
+        assert_eq!(options(None, 9).resolve().map(|d| d.year), Ok(2019));
+        assert_eq!(options(Some(2019), 9).resolve().map(|d| d.day), Ok(9));
+        assert!(options(Some(2019), 26).resolve().is_err());
+        assert!(options(Some(2015), 1).resolve().is_err());
+    }
+
+    #[test]
+    fn rejects_bad_args() {
+        assert!(parse_args(args(&[])).is_err());
+        assert!(parse_args(args(&["run"])).is_err());
+        assert!(parse_args(args(&["run", "--day"])).is_err());
+        assert!(parse_args(args(&["run", "--day", "x"])).is_err());
+        assert!(parse_args(args(&["run", "--day", "1", "--part", "3"])).is_err());
+        assert!(parse_args(args(&["run", "--day", "1", "--year", "-1"])).is_err());
+        assert!(parse_args(args(&["run", "--day", "1", "--part", "1", "--both-parts"])).is_err());
+        assert!(parse_args(args(&["fly", "--day", "1"])).is_err());
+        assert!(parse_args(args(&["completions"])).is_err());
+        assert!(parse_args(args(&["completions", "bash", "zsh"])).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::Options;
+use aoc_runner::Day;
+use std::{
+    env, fs,
+    path::Path,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Re-run a day every time its source or input changes. Source changes
+/// need a rebuild, so every run goes through cargo instead of calling the
+/// solver directly.
+pub fn watch(options: &Options) -> Result<(), String> {
+    let day = options.resolve()?;
+    let watched = [Path::new(day.crate_dir).join("src"), day.input_path()];
+    println!(
+        "Watching {} for changes...",
+        watched
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" and ")
+    );
+
+    let mut last_change = None;
+    loop {
+        let change = watched.iter().filter_map(|p| last_modified(p)).max();
+        if change != last_change {
+            last_change = change;
+            rerun(day, options);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+// Find the most recent modification time of a file, or of any file
+// nested under a directory.
+fn last_modified(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return metadata.modified().ok();
+    }
+
+    fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| last_modified(&entry.path()))
+        .max()
+}
+
+fn rerun(day: &Day, options: &Options) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let mut command = Command::new(cargo);
+    command.args(["run", "--release", "-q", "-p", "aoc", "--", "run"]);
+    command.arg("--year").arg(day.year.to_string());
+    command.arg("--day").arg(day.day.to_string());
+    if let Some(part) = options.part {
+        command.arg("--part").arg(part.to_string());
+    }
+    if options.both_parts {
+        command.arg("--both-parts");
+    }
+    if options.explain {
+        command.arg("--explain");
+    }
+
+    println!("\n[watch] re-running {} day {}", day.year, day.day);
+    match command.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("[watch] run failed ({})", status),
+        Err(e) => println!("[watch] could not start cargo: {}", e),
+    }
+}
@@ -0,0 +1,39 @@
+use aoc_runner::Year;
+
+/// Every event with registered solutions, oldest first.
+pub const YEARS: &[Year] = &[Year {
+    year: 2019,
+    days: &[
+        &day1::DAY,
+        &day2::DAY,
+        &day3::DAY,
+        &day4::DAY,
+        &day5::DAY,
+        &day6::DAY,
+        &day7::DAY,
+        &day8::DAY,
+        &day9::DAY,
+        &day10::DAY,
+        &day11::DAY,
+        &day12::DAY,
+        &day13::DAY,
+        &day14::DAY,
+        &day17::DAY,
+        &day18::DAY,
+        &day21::DAY,
+        &day22::DAY,
+        &day23::DAY,
+        &day24::DAY,
+        &day25::DAY,
+    ],
+}];
+
+pub fn year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year)
+}
+
+pub fn latest() -> &'static Year {
+    YEARS
+        .last()
+        .expect("At least one year should be registered")
+}
@@ -0,0 +1,211 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// A puzzle whose input is parsed once into a typed structure, which both
+/// parts then share.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, String>;
+    fn part1(input: &Self::Input) -> Result<String, String>;
+    fn part2(input: &Self::Input) -> Result<String, String>;
+}
+
+/// Solves one part of a puzzle, given the raw puzzle input.
+pub type Part = fn(&str) -> Result<String, String>;
+
+pub fn part1<S: Solution>(input: &str) -> Result<String, String> {
+    S::part1(&S::parse(input)?)
+}
+
+pub fn part2<S: Solution>(input: &str) -> Result<String, String> {
+    S::part2(&S::parse(input)?)
+}
+
+/// Answers to both parts of a puzzle, along with how long parsing and each
+/// part took.
+#[derive(Debug)]
+pub struct Timings {
+    pub parse: Duration,
+    pub answers: [(String, Duration); 2],
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.answers[0].1 + self.answers[1].1
+    }
+}
+
+/// Solves both parts of a puzzle from a single parse of its input.
+pub type BothParts = fn(&str) -> Result<Timings, String>;
+
+pub fn both_parts<S: Solution>(input: &str) -> Result<Timings, String> {
+    fn timed<T>(f: impl FnOnce() -> Result<T, String>) -> Result<(T, Duration), String> {
+        let start = Instant::now();
+        let result = f()?;
+        Ok((result, start.elapsed()))
+    }
+
+    let (parsed, parse) = timed(|| S::parse(input))?;
+    Ok(Timings {
+        parse,
+        answers: [timed(|| S::part1(&parsed))?, timed(|| S::part2(&parsed))?],
+    })
+}
+
+/// Receives the intermediate steps of a solution, so a wrong answer can be
+/// checked against the puzzle description.
+pub trait Reporter {
+    /// Report one step. The detail may span several lines, e.g. a drawing.
+    fn report(&mut self, label: &str, detail: &str);
+}
+
+// Collecting steps is handy for checking explanations in tests
+impl Reporter for Vec<(String, String)> {
+    fn report(&mut self, label: &str, detail: &str) {
+        self.push((label.to_owned(), detail.to_owned()));
+    }
+}
+
+/// Walks a reporter through how a day arrives at its answers.
+pub type Explain = fn(&str, &mut dyn Reporter) -> Result<(), String>;
+
+/// A solved puzzle, registered by its crate through [`register!`].
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub parts: [Part; 2],
+    pub both_parts: BothParts,
+    /// Only some days can explain their answers
+    pub explain: Option<Explain>,
+    /// Cargo package the solution lives in
+    pub package: &'static str,
+    /// Directory of that package, used to find its sources
+    pub crate_dir: &'static str,
+}
+
+impl Day {
+    /// Where the puzzle input for this day lives. Inputs are kept in one
+    /// directory per year, relative to the workspace root.
+    pub fn input_path(&self) -> PathBuf {
+        Path::new("input")
+            .join(self.year.to_string())
+            .join(format!("day{}.txt", self.day))
+    }
+}
+
+/// All of the days solved for a given event.
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [&'static Day],
+}
+
+impl Year {
+    pub fn day(&self, day: u8) -> Option<&'static Day> {
+        self.days.iter().find(|d| d.day == day).copied()
+    }
+}
+
+/// Register a day with the `aoc` dispatcher. This creates a `DAY` static
+/// in the calling crate which the dispatcher lists under its year.
+///
+/// ```ignore
+/// aoc_runner::register!(year: 2019, day: 1, solution: Day1);
+/// aoc_runner::register!(year: 2019, day: 3, solution: Day3, explain: explain);
+/// ```
+#[macro_export]
+macro_rules! register {
+    (year: $year:expr, day: $day:expr, solution: $solution:ty $(,)?) => {
+        $crate::register!(@day $year, $day, $solution, None);
+    };
+    (year: $year:expr, day: $day:expr, solution: $solution:ty, explain: $explain:path $(,)?) => {
+        $crate::register!(@day $year, $day, $solution, Some($explain));
+    };
+    (@day $year:expr, $day:expr, $solution:ty, $explain:expr) => {
+        pub static DAY: $crate::Day = $crate::Day {
+            year: $year,
+            day: $day,
+            parts: [$crate::part1::<$solution>, $crate::part2::<$solution>],
+            both_parts: $crate::both_parts::<$solution>,
+            explain: $explain,
+            package: env!("CARGO_PKG_NAME"),
+            crate_dir: env!("CARGO_MANIFEST_DIR"),
+        };
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Vec<u32>, String> {
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(|e| format!("Bad number {}: {}", n, e)))
+                .collect()
+        }
+
+        fn part1(input: &Vec<u32>) -> Result<String, String> {
+            Ok(input.iter().sum::<u32>().to_string())
+        }
+
+        fn part2(input: &Vec<u32>) -> Result<String, String> {
+            Ok(input.iter().product::<u32>().to_string())
+        }
+    }
+
+    register!(year: 2019, day: 3, solution: Sum);
+
+    #[test]
+    fn input_path_is_per_year() {
+        assert_eq!(DAY.input_path(), Path::new("input/2019/day3.txt"));
+    }
+
+    static YEAR: Year = Year {
+        year: 2019,
+        days: &[&DAY],
+    };
+
+    #[test]
+    fn finds_registered_days() {
+        let year = &YEAR;
+        assert!(year.day(3).is_some());
+        assert!(year.day(4).is_none());
+        assert_eq!(year.day(3).unwrap().package, "aoc_runner");
+        assert!(year.day(3).unwrap().explain.is_none());
+    }
+
+    #[test]
+    fn solves_parts_from_raw_input() {
+        assert_eq!((DAY.parts[0])("2,3,4"), Ok("9".to_owned()));
+        assert_eq!((DAY.parts[1])("2,3,4"), Ok("24".to_owned()));
+        assert!((DAY.parts[0])("2,x").is_err());
+    }
+
+    #[test]
+    fn solves_both_parts_from_one_parse() {
+        let timings = (DAY.both_parts)("2,3,4").unwrap();
+        assert_eq!(timings.answers[0].0, "9");
+        assert_eq!(timings.answers[1].0, "24");
+        assert!(timings.total() >= timings.parse);
+        assert!((DAY.both_parts)("2,x").is_err());
+    }
+
+    #[test]
+    fn collects_reported_steps() {
+        let mut steps = vec![];
+        let reporter: &mut dyn Reporter = &mut steps;
+        reporter.report("intersection", "(3, 3)");
+        assert_eq!(
+            steps,
+            vec![("intersection".to_owned(), "(3, 3)".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Colors and scales for the days that draw their answers as pictures,
+//! read the way they're given on the command line.
+
+use std::str::FromStr;
+
+/// A color written as `rrggbb`, with or without a leading `#`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Rgb(pub [u8; 3]);
+
+impl Rgb {
+    /// Whether the color is closer to white than to black, going by how
+    /// bright it looks rather than by its channels alone.
+    pub fn is_light(self) -> bool {
+        let [r, g, b] = self.0;
+        299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b) >= 128 * 1000
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim_start_matches('#');
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(format!("Expected a color like #1f77b4, found {}", s));
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        Ok(Rgb([channel(0), channel(2), channel(4)]))
+    }
+}
+
+/// Check how many pixels across something is drawn, which has to be at
+/// least one.
+pub fn check_scale(scale: u64) -> Result<(), String> {
+    if scale == 0 {
+        return Err("The scale must be at least 1".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_colors() {
+        assert_eq!("#1f77b4".parse(), Ok(Rgb([0x1f, 0x77, 0xb4])));
+        assert_eq!("FF0000".parse(), Ok(Rgb([255, 0, 0])));
+        assert!("#12345".parse::<Rgb>().is_err());
+        assert!("#12345g".parse::<Rgb>().is_err());
+        assert!("#1234567".parse::<Rgb>().is_err());
+    }
+
+    #[test]
+    fn tells_light_from_dark() {
+        assert!(Rgb([255, 255, 255]).is_light());
+        assert!(Rgb([0xff, 0x7f, 0x0e]).is_light());
+        assert!(!Rgb([0x1f, 0x77, 0xb4]).is_light());
+        assert!(!Rgb([0, 0, 0]).is_light());
+    }
+
+    #[test]
+    fn checks_scales() {
+        assert!(check_scale(1).is_ok());
+        assert_eq!(
+            check_scale(0),
+            Err("The scale must be at least 1".to_string())
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use aoc_runner::Solution;
+use rayon::prelude::*;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io::BufRead,
+    num::ParseIntError,
+    ops::Add,
+};
+
+/// A mass that isn't a number, and where it was found.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// Counting from 1, blank lines included
+    pub line: usize,
+    pub value: String,
+    pub source: ParseIntError,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Error parsing {:?} on line {}: {}",
+            self.value, self.line, self.source
+        )
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+// Parse one line of input, which may be blank
+fn parse_line(line: usize, value: &str) -> Result<Option<u64>, ParseError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    value.parse().map(Some).map_err(|source| ParseError {
+        line,
+        value: value.to_owned(),
+        source,
+    })
+}
+
+/// Parse the module masses, one per line. Blank lines are skipped, and
+/// lines may end in either LF or CRLF.
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| parse_line(i + 1, line).transpose())
+        .collect()
+}
+
+/// Work out the fuel for masses read from anywhere, such as stdin, without
+/// holding them all in memory.
+pub fn run(reader: impl BufRead) -> Result<Fuel, String> {
+    let mut masses = vec![];
+    let mut total = Fuel::default();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read line {}: {}", i + 1, e))?;
+        masses.extend(parse_line(i + 1, &line).map_err(|e| e.to_string())?);
+        // Sum in batches, so the masses never pile up
+        if masses.len() == 1024 {
+            total = total + total_fuel(masses.drain(..));
+        }
+    }
+    Ok(total + total_fuel(masses))
+}
+
+/// The fuel needed for a single module.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ModuleFuel {
+    pub mass: u64,
+    pub direct: u64,
+    pub recursive: u64,
+}
+
+/// The `n` heaviest modules, heaviest first.
+pub fn heaviest(masses: &[u64], n: usize) -> Vec<ModuleFuel> {
+    let mut masses = masses.to_vec();
+    masses.sort_unstable_by(|a, b| b.cmp(a));
+    masses
+        .into_iter()
+        .take(n)
+        .map(|mass| ModuleFuel {
+            mass,
+            direct: fuel_for(mass),
+            recursive: all_fuel_for(mass),
+        })
+        .collect()
+}
+
+/// A table of the `n` heaviest modules and how much of the total fuel each
+/// one needs, for spotting masses that don't look right.
+pub fn report(masses: &[u64], n: usize) -> String {
+    let total = total_fuel(masses.iter().copied());
+    let mut report = format!(
+        "{:>12}  {:>12}  {:>12}  {:>7}\n",
+        "mass", "fuel", "all fuel", "share"
+    );
+    for module in heaviest(masses, n) {
+        let share = if total.recursive == 0 {
+            0.0
+        } else {
+            module.recursive as f64 / total.recursive as f64 * 100.0
+        };
+        report += &format!(
+            "{:>12}  {:>12}  {:>12}  {:>6.2}%\n",
+            module.mass, module.direct, module.recursive, share
+        );
+    }
+    report += &format!(
+        "{:>12}  {:>12}  {:>12}  for all {} modules\n",
+        "total",
+        total.direct,
+        total.recursive,
+        masses.len()
+    );
+    report
+}
+
+/// Inputs at least this large are summed in parallel.
+const PARALLEL_THRESHOLD: usize = 1 << 20;
+/// Roughly how much of the input each thread takes at a time.
+const CHUNK_SIZE: usize = 1 << 16;
+
+/// Work out the fuel for a whole input without parsing it into a list
+/// first. Large inputs are split into chunks of lines and summed in
+/// parallel.
+pub fn fuel_for_input(input: &str) -> Result<Fuel, ParseError> {
+    if input.len() < PARALLEL_THRESHOLD {
+        return fuel_for_lines(input).map(|(fuel, _)| fuel);
+    }
+
+    // Chunks always end on a line break, so no line is split in two. The
+    // break is searched for byte by byte, since the chunk size may land in
+    // the middle of a character.
+    let mut chunks = vec![];
+    let mut rest = input;
+    while !rest.is_empty() {
+        let end = rest
+            .as_bytes()
+            .get(CHUNK_SIZE..)
+            .and_then(|tail| tail.iter().position(|&b| b == b'\n'))
+            .map_or(rest.len(), |i| CHUNK_SIZE + i + 1);
+        let (chunk, next) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = next;
+    }
+
+    // Line numbers in errors are only known once the earlier chunks have
+    // been counted
+    let sums = chunks
+        .par_iter()
+        .map(|chunk| fuel_for_lines(chunk))
+        .collect::<Vec<_>>();
+    let mut total = Fuel::default();
+    let mut lines = 0;
+    for sum in sums {
+        let (fuel, count) = sum.map_err(|e| ParseError {
+            line: e.line + lines,
+            ..e
+        })?;
+        total = total + fuel;
+        lines += count;
+    }
+    Ok(total)
+}
+
+// Sum the fuel for some lines, also returning how many lines there were
+fn fuel_for_lines(input: &str) -> Result<(Fuel, usize), ParseError> {
+    let mut total = Fuel::default();
+    let mut count = 0;
+    for (i, line) in input.lines().enumerate() {
+        if let Some(mass) = parse_line(i + 1, line)? {
+            total = total + total_fuel(Some(mass));
+        }
+        count += 1;
+    }
+    Ok((total, count))
+}
+
+pub struct Day1;
+
+// Both parts come out of the same sum, so it's done while parsing, where
+// large inputs can be summed in parallel without building a list first
+impl Solution for Day1 {
+    type Input = Fuel;
+
+    fn parse(input: &str) -> Result<Fuel, String> {
+        fuel_for_input(input).map_err(|e| e.to_string())
+    }
+
+    fn part1(fuel: &Fuel) -> Result<String, String> {
+        Ok(fuel.direct.to_string())
+    }
+
+    fn part2(fuel: &Fuel) -> Result<String, String> {
+        Ok(fuel.recursive.to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 1, solution: Day1);
+
+/// Calculate the fuel required for a particular mass.
+pub fn fuel_for(mass: u64) -> u64 {
+    (mass / 3).saturating_sub(2)
+}
+
+/// Each successive amount of fuel needed: first for the mass itself, then
+/// for the fuel just added, until no more is needed.
+pub fn iter_fuel(mass: u64) -> FuelSeries {
+    FuelSeries { mass }
+}
+
+pub struct FuelSeries {
+    mass: u64,
+}
+
+impl Iterator for FuelSeries {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.mass = fuel_for(self.mass);
+        Some(self.mass).filter(|&fuel| fuel > 0)
+    }
+}
+
+/// Calculate the actual fuel required for a particular mass, including
+/// the fuel needed to carry that fuel.
+pub fn all_fuel_for(mass: u64) -> u64 {
+    iter_fuel(mass).sum()
+}
+
+/// Fuel needed for a whole set of modules. A single module's fuel always
+/// fits in a u64, but enough heavy modules together won't.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Fuel {
+    /// Only counting the modules themselves
+    pub direct: u128,
+    /// Also counting the fuel's own mass
+    pub recursive: u128,
+}
+
+impl Add for Fuel {
+    type Output = Fuel;
+
+    fn add(self, other: Fuel) -> Fuel {
+        Fuel {
+            direct: self.direct + other.direct,
+            recursive: self.recursive + other.recursive,
+        }
+    }
+}
+
+pub fn total_fuel(masses: impl IntoIterator<Item = u64>) -> Fuel {
+    masses
+        .into_iter()
+        .fold(Fuel::default(), |total, mass| Fuel {
+            direct: total.direct + u128::from(fuel_for(mass)),
+            recursive: total.recursive + u128::from(all_fuel_for(mass)),
+        })
+}
+
+#[test]
+fn test_fuel_for() {
+    assert_eq!(fuel_for(12), 2);
+    assert_eq!(fuel_for(14), 2);
+    assert_eq!(fuel_for(1969), 654);
+    assert_eq!(fuel_for(100756), 33583);
+}
+
+#[test]
+fn test_all_fuel_for() {
+    assert_eq!(all_fuel_for(14), 2);
+    assert_eq!(all_fuel_for(1969), 966);
+    assert_eq!(all_fuel_for(100756), 50346);
+}
+
+#[test]
+fn test_iter_fuel() {
+    assert_eq!(
+        iter_fuel(1969).collect::<Vec<_>>(),
+        vec![654, 216, 70, 21, 5]
+    );
+    assert_eq!(iter_fuel(8).next(), None);
+}
+
+#[test]
+fn test_total_fuel() {
+    assert_eq!(
+        total_fuel(vec![14, 1969]),
+        Fuel {
+            direct: 656,
+            recursive: 968,
+        }
+    );
+    assert_eq!(total_fuel(vec![]), Fuel::default());
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(parse("12\r\n\r\n14\n\n1969\r\n"), Ok(vec![12, 14, 1969]));
+    assert_eq!(parse(""), Ok(vec![]));
+
+    let error = parse("12\n\n1x4\n").unwrap_err();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.value, "1x4");
+    assert_eq!(
+        error.to_string(),
+        "Error parsing \"1x4\" on line 3: invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_run() {
+    let input = "14\r\n\n1969\n".repeat(1000);
+    assert_eq!(
+        run(input.as_bytes()),
+        Ok(Fuel {
+            direct: 656_000,
+            recursive: 968_000,
+        })
+    );
+    assert_eq!(
+        run("14\nfuel\n".as_bytes()),
+        Err("Error parsing \"fuel\" on line 2: invalid digit found in string".to_owned())
+    );
+}
+
+#[test]
+fn test_fuel_for_input() {
+    let input = "14\r\n\n1969\n".repeat(200_000);
+    assert!(input.len() > PARALLEL_THRESHOLD);
+    let expected = Fuel {
+        direct: 131_200_000,
+        recursive: 193_600_000,
+    };
+    assert_eq!(fuel_for_input(&input), Ok(expected));
+    assert_eq!(fuel_for_input(&input[..10]), Ok(total_fuel(vec![14, 1969])));
+
+    // Errors far into a parallel input still name the right line
+    let input = input + "heavy\n";
+    let error = fuel_for_input(&input).unwrap_err();
+    assert_eq!(error.line, 600_001);
+
+    // A character straddling a chunk boundary is reported, not split
+    let mut input = "14\n".repeat(CHUNK_SIZE / 3);
+    assert_eq!(input.len(), CHUNK_SIZE - 1);
+    input += "é\n";
+    input += &"14\n".repeat(PARALLEL_THRESHOLD / 3);
+    let error = fuel_for_input(&input).unwrap_err();
+    assert_eq!(
+        (error.line, error.value.as_str()),
+        (CHUNK_SIZE / 3 + 1, "é")
+    );
+}
+
+#[test]
+fn test_solution() {
+    let input = "14\r\n\n1969\n".repeat(200_000);
+    assert!(input.len() > PARALLEL_THRESHOLD);
+    assert_eq!(
+        aoc_runner::part1::<Day1>(&input),
+        Ok("131200000".to_owned())
+    );
+    assert_eq!(
+        aoc_runner::part2::<Day1>(&input),
+        Ok("193600000".to_owned())
+    );
+    assert_eq!(
+        aoc_runner::part2::<Day1>("14\n1969\n"),
+        Ok("968".to_owned())
+    );
+
+    let error = aoc_runner::part1::<Day1>(&(input + "heavy\n")).unwrap_err();
+    assert!(error.contains("on line 600001"), "{}", error);
+}
+
+#[test]
+fn test_report() {
+    let masses = [14, 100756, 1969, 12];
+    assert_eq!(
+        heaviest(&masses, 2),
+        vec![
+            ModuleFuel {
+                mass: 100756,
+                direct: 33583,
+                recursive: 50346,
+            },
+            ModuleFuel {
+                mass: 1969,
+                direct: 654,
+                recursive: 966,
+            },
+        ]
+    );
+    assert_eq!(heaviest(&masses, 10).len(), 4);
+
+    let report = report(&masses, 1);
+    assert_eq!(report.lines().count(), 3);
+    assert!(report.contains("      100756         33583         50346   98.11%"));
+    assert!(report.contains("for all 4 modules"));
+}
+
+#[test]
+fn test_boundaries() {
+    assert_eq!(fuel_for(0), 0);
+    assert_eq!(fuel_for(8), 0);
+    assert_eq!(fuel_for(9), 1);
+    assert_eq!(fuel_for(u64::MAX), u64::MAX / 3 - 2);
+    // Each term is a third of the last, so the series can't overflow
+    assert!(all_fuel_for(u64::MAX) < u64::MAX / 2);
+
+    let total = total_fuel(vec![u64::MAX; 4]);
+    assert_eq!(total.direct, 4 * u128::from(u64::MAX / 3 - 2));
+    assert_eq!(total.recursive, 4 * u128::from(all_fuel_for(u64::MAX)));
+    assert!(total.direct > u128::from(u64::MAX));
+    assert_eq!(
+        fuel_for_input(&format!("{0}\n{0}\n{0}\n{0}\n", u64::MAX)),
+        Ok(total)
+    );
+}
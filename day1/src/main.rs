@@ -1,58 +1,64 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use day1::{fuel_for_input, parse, report, run};
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
 
+const USAGE: &str = "Usage: day1 [--top N] [PATH | -]";
+
+// Masses are read from the puzzle input by default, from another file if
+// one is given, or from stdin when that file is `-`. `--top N` prints the N
+// heaviest modules instead.
 fn main() -> Result<(), String> {
-    let file = File::open("day1/input.txt").map_err(|e| format!("{}", e))?;
-    let mut reader = BufReader::new(file);
-    let mut buffer = String::new();
-    let mut sum: u64 = 0;
-    while let Ok(_) = reader.read_line(&mut buffer) {
-        if buffer.len() < 2 {
-            break;
+    let mut top = None;
+    let mut path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => {
+                let n = args
+                    .next()
+                    .ok_or_else(|| format!("--top needs a count\n{}", USAGE))?;
+                top = Some(
+                    n.parse::<usize>()
+                        .map_err(|e| format!("Bad count {}: {}", n, e))?,
+                );
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("Unexpected argument {}\n{}", arg, USAGE)),
         }
-
-        let mass = buffer[0..buffer.len() - 1]
-            .parse::<u64>()
-            .map_err(|e| format!("Error parsing {}: {}", buffer, e))?;
-        sum += all_fuel_for(mass);
-        buffer.clear();
     }
 
-    println!("{}", sum);
-
-    Ok(())
-}
-
-// Calculate the fuel required for a particular mass
-fn fuel_for(mass: u64) -> u64 {
-    (mass / 3).saturating_sub(2)
-}
+    let read_stdin = path.as_deref() == Some("-");
+    let path = path.map_or_else(|| day1::DAY.input_path(), PathBuf::from);
+    let input = if read_stdin {
+        // Without a report, stdin can be summed as it arrives
+        if top.is_none() {
+            return print_fuel(run(io::stdin().lock())?);
+        }
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        input
+    } else {
+        fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?
+    };
 
-// Calculate the actual fuel required for a particular
-// mass. Since each subsequent calculation requires
-// less fuel, we're able to iterate to a fixed point.
-fn all_fuel_for(mass: u64) -> u64 {
-    let mut sum = fuel_for(mass);
-    let mut last_sum = sum;
-    while last_sum > 0 {
-        last_sum = fuel_for(last_sum);
-        sum += last_sum;
+    match top {
+        Some(n) => {
+            let masses = parse(&input).map_err(|e| e.to_string())?;
+            print!("{}", report(&masses, n));
+            Ok(())
+        }
+        None => print_fuel(fuel_for_input(&input).map_err(|e| e.to_string())?),
     }
-
-    sum
 }
 
-#[test]
-fn test_fuel_for() {
-    assert_eq!(fuel_for(12), 2);
-    assert_eq!(fuel_for(14), 2);
-    assert_eq!(fuel_for(1969), 654);
-    assert_eq!(fuel_for(100756), 33583);
-}
-
-#[test]
-fn test_all_fuel_for() {
-    assert_eq!(all_fuel_for(14), 2);
-    assert_eq!(all_fuel_for(1969), 966);
-    assert_eq!(all_fuel_for(100756), 50346);
+fn print_fuel(fuel: day1::Fuel) -> Result<(), String> {
+    println!("Fuel for the modules: {}", fuel.direct);
+    println!("Fuel including fuel: {}", fuel.recursive);
+    Ok(())
 }
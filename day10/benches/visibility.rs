@@ -0,0 +1,69 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day10::AsteroidField;
+
+// A square field with about one asteroid in every `spacing` cells, laid out
+// by a small xorshift generator so every run sees the same field
+fn field(side: usize, spacing: u64) -> AsteroidField {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut rows = vec![];
+    for _ in 0..side {
+        let row = (0..side)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                if state.is_multiple_of(spacing) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        rows.push(row);
+    }
+    AsteroidField::parse(&rows.join("\n")).unwrap()
+}
+
+// Group every other asteroid by direction from each station in turn
+fn by_direction(field: &AsteroidField) -> usize {
+    field
+        .asteroids()
+        .iter()
+        .map(|&station| field.visible_from(station).len())
+        .max()
+        .unwrap_or(0)
+}
+
+fn visibility(c: &mut Criterion) {
+    let mut group = c.benchmark_group("visibility");
+    group.sample_size(10);
+    // The direction table spreads its stations across threads, so give it
+    // just the one that grouping by direction gets, to compare the
+    // strategies rather than the core count
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    // About the size of a puzzle input, a field ten times as full, and one
+    // with over twenty thousand asteroids. Grouping by direction takes too
+    // long on the last to be worth waiting for.
+    let fields = [
+        ("puzzle", field(33, 3)),
+        ("large", field(100, 3)),
+        ("huge", field(300, 4)),
+    ];
+    for (name, field) in &fields {
+        if field.asteroids().len() < 10_000 {
+            group.bench_with_input(BenchmarkId::new("by direction", name), field, |b, f| {
+                b.iter(|| pool.install(|| by_direction(f)))
+            });
+        }
+        group.bench_with_input(BenchmarkId::new("direction table", name), field, |b, f| {
+            b.iter(|| pool.install(|| f.visibility().into_iter().map(|(_, count)| count).max()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, visibility);
+criterion_main!(benches);
@@ -0,0 +1,159 @@
+//! The original way of grouping asteroids, by the angle `atan2` gives for
+//! them. It's kept to check the exact directions against.
+
+use crate::Point;
+use ordered_float::NotNan;
+use std::collections::BTreeMap;
+
+type AngleIndex = BTreeMap<NotNan<f64>, Vec<(Point, usize)>>;
+
+pub fn build_index(station: Point, asteroids: &[Point]) -> AngleIndex {
+    let mut aligned_map = AngleIndex::new();
+    for &asteroid in asteroids {
+        if asteroid == station {
+            continue;
+        }
+
+        let xdiff = asteroid.x as isize - station.x as isize;
+        let ydiff = asteroid.y as isize - station.y as isize;
+        let dist_squared = (xdiff * xdiff + ydiff * ydiff) as usize;
+
+        let angle = NotNan::new((ydiff as f64).atan2(xdiff as f64)).unwrap();
+        aligned_map
+            .entry(angle)
+            .or_default()
+            .push((asteroid, dist_squared));
+    }
+    aligned_map
+}
+
+/// The asteroids at each angle, starting from straight up and going
+/// clockwise.
+pub fn sweep(map: AngleIndex) -> Vec<Vec<(Point, usize)>> {
+    let mut ordered_asteroids: Vec<_> = map
+        .into_iter()
+        .map(|(k, v)| (NotNan::new(change_angle(k.to_degrees())).unwrap(), v))
+        .collect();
+    ordered_asteroids.sort_by_key(|t| t.0);
+    ordered_asteroids.into_iter().map(|(_, v)| v).collect()
+}
+
+/// The asteroid that can see the most others, and how many, with ties
+/// going to the first.
+pub fn best_station(asteroids: &[Point]) -> Option<(Point, usize)> {
+    let mut best: Option<(Point, usize)> = None;
+    for &station in asteroids {
+        let count = build_index(station, asteroids).len();
+        if best.is_none_or(|(_, most)| count > most) {
+            best = Some((station, count));
+        }
+    }
+    best
+}
+
+/// Every other asteroid, in the order the laser at `station` vaporizes
+/// them, going round the angles one closest asteroid at a time.
+pub fn vaporization_order(station: Point, asteroids: &[Point]) -> Vec<Point> {
+    let mut angles = sweep(build_index(station, asteroids));
+    for asteroids in &mut angles {
+        asteroids.sort_by_key(|tup| tup.1);
+    }
+    let mut order = vec![];
+    for round in 0.. {
+        let before = order.len();
+        order.extend(angles.iter().filter_map(|a| a.get(round)).map(|tup| tup.0));
+        if order.len() == before {
+            break;
+        }
+    }
+    order
+}
+
+fn change_angle(angle: f64) -> f64 {
+    let mut angle = angle;
+    angle += 90.0;
+    if angle >= 360.0 {
+        angle -= 360.0;
+    } else if angle < 0.0 {
+        angle += 360.0;
+    }
+
+    angle
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{test::fields, AsteroidField};
+    use proptest::prelude::*;
+
+    macro_rules! assert_feq {
+        ($left:expr, $right:expr) => {
+            let left = $left;
+            let right = $right;
+            assert!(
+                (left - right).abs() < f64::EPSILON,
+                "{} is not close enough to {}",
+                left,
+                right
+            );
+        };
+    }
+
+    #[test]
+    fn change_angle_tests() {
+        assert_feq!(0.0, change_angle(270.0));
+        assert_feq!(90.0, change_angle(0.0));
+        assert_feq!(180.0, change_angle(90.0));
+        assert_feq!(270.0, change_angle(180.0));
+    }
+
+    #[test]
+    fn matches_exact_directions() {
+        let field = AsteroidField::parse(
+            ".#..##.###...#######
+            ##.############..##.
+            .#.######.########.#
+            .###.#######.####.#.
+            #####.##.#.##.###.##
+            ..#####..#.#########
+            ####################
+            #.####....###.#.#.##
+            ##.#################
+            #####.##.###..####..
+            ..######..##.#######
+            ####.##.####...##..#
+            .#####..#.######.###
+            ##...#.##########...
+            #.##########.#######
+            .####.#.###.###.#.##
+            ....##.##.###..#####
+            .#.#.###########.###
+            #.#.#.#####.####.###
+            ###.##.####.##.#..##",
+        )
+        .unwrap();
+        // From every station, both group the same asteroids together, and
+        // sweep the groups in the same order
+        for &station in field.asteroids() {
+            let exact = field.directions(station);
+            let float = sweep(build_index(station, field.asteroids()));
+            assert_eq!(exact.into_values().collect::<Vec<_>>(), float);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_exact_directions(field in fields()) {
+            let field = AsteroidField::parse(&field).unwrap();
+            let best = field.best_station();
+            prop_assert_eq!(best_station(field.asteroids()), best);
+            if let Some((station, _)) = best {
+                prop_assert_eq!(
+                    vaporization_order(station, field.asteroids()),
+                    field.vaporization_order(station).collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+}
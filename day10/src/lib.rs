@@ -0,0 +1,892 @@
+use aoc_runner::{Reporter, Solution};
+use rayon::prelude::*;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+#[cfg(test)]
+mod float;
+mod sight;
+mod svg;
+
+/// The way from one asteroid to another, reduced so that every asteroid
+/// along the same line of sight has the same direction. Directions are
+/// ordered clockwise, starting from straight up, the way the laser sweeps.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Direction {
+    dx: isize,
+    dy: isize,
+}
+
+impl Direction {
+    pub const UP: Self = Self { dx: 0, dy: -1 };
+    pub const RIGHT: Self = Self { dx: 1, dy: 0 };
+    pub const DOWN: Self = Self { dx: 0, dy: 1 };
+    pub const LEFT: Self = Self { dx: -1, dy: 0 };
+
+    fn new(dx: isize, dy: isize) -> Self {
+        let gcd = gcd(dx.unsigned_abs(), dy.unsigned_abs()) as isize;
+        Self {
+            dx: dx / gcd,
+            dy: dy / gcd,
+        }
+    }
+
+    // Turning from this direction to the other is clockwise when this is
+    // positive, with y pointing down
+    fn cross(&self, other: &Self) -> isize {
+        self.dx * other.dy - self.dy * other.dx
+    }
+}
+
+impl Ord for Direction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Sweep::default().compare(*self, *other)
+    }
+}
+
+impl PartialOrd for Direction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Read a direction written as `dx,dy`, with y pointing down, such as `0,-1`
+/// for straight up.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (dx, dy) = s
+            .split_once(',')
+            .ok_or_else(|| format!("Expected a direction like 0,-1, found {:?}", s))?;
+        let parse = |d: &str| {
+            d.trim()
+                .parse::<isize>()
+                .map_err(|e| format!("Bad direction {}: {}", s, e))
+        };
+        match (parse(dx)?, parse(dy)?) {
+            (0, 0) => Err("A direction can't be 0,0".to_owned()),
+            (dx, dy) => Ok(Self::new(dx, dy)),
+        }
+    }
+}
+
+/// Which way the laser turns as it sweeps.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rotation {
+    Clockwise,
+    Counterclockwise,
+}
+
+/// Where the laser starts its sweep, and which way it turns from there.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Sweep {
+    /// The first direction the laser fires in. Asteroids straight along it
+    /// are the first to go.
+    pub start: Direction,
+    pub rotation: Rotation,
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Self {
+            start: Direction::UP,
+            rotation: Rotation::Clockwise,
+        }
+    }
+}
+
+impl Sweep {
+    /// Which of two directions the laser reaches first.
+    pub fn compare(&self, a: Direction, b: Direction) -> Ordering {
+        // Mirroring everything left to right turns a counterclockwise sweep
+        // into a clockwise one
+        let mirror = |d: Direction| match self.rotation {
+            Rotation::Clockwise => d,
+            Rotation::Counterclockwise => Direction { dx: -d.dx, ..d },
+        };
+        let (start, a, b) = (mirror(self.start), mirror(a), mirror(b));
+        // From the start round to just before its opposite is the first
+        // half of the sweep, and the rest of the way round the second.
+        // Within a half, one direction comes before another when turning
+        // from it to the other is clockwise.
+        let half = |d: &Direction| {
+            let cross = start.cross(d);
+            let dot = start.dx * d.dx + start.dy * d.dy;
+            !(cross > 0 || (cross == 0 && dot > 0))
+        };
+        half(&a).cmp(&half(&b)).then_with(|| 0.cmp(&a.cross(&b)))
+    }
+}
+
+/// Which station to pick when more than one sees the most asteroids.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Ties {
+    /// The first, reading from the top left
+    #[default]
+    First,
+    /// The last, reading from the top left
+    Last,
+    /// The closest to a point, and the first of those still tied
+    Nearest(Point),
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let t = b;
+        b = a % b;
+        a = t;
+    }
+    a
+}
+
+/// A place in the asteroid field, counting from the top left.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// The characters a map of the field is drawn with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Legend {
+    /// Any of these is an asteroid, such as `X` where the puzzle's examples
+    /// mark the station
+    pub asteroids: String,
+    pub empty: String,
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        Self {
+            asteroids: "#".to_owned(),
+            empty: ".".to_owned(),
+        }
+    }
+}
+
+/// A map of the asteroids in the area.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AsteroidField {
+    width: usize,
+    height: usize,
+    asteroids: Vec<Point>,
+}
+
+impl AsteroidField {
+    /// Read a map with a `#` for each asteroid and a `.` for each empty
+    /// space. Every row has to be as wide as the first.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        Self::parse_with(input, &Legend::default())
+    }
+
+    /// Read a map using the legend's characters. Leading and trailing
+    /// whitespace is ignored on each row, and blank lines at the end.
+    pub fn parse_with(input: &str, legend: &Legend) -> Result<Self, String> {
+        let mut asteroids = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (y, row) in input.trim_end().lines().map(str::trim).enumerate() {
+            let mut columns = 0;
+            for (x, c) in row.chars().enumerate() {
+                if legend.asteroids.contains(c) {
+                    asteroids.push(Point::new(x, y));
+                } else if !legend.empty.contains(c) {
+                    return Err(format!(
+                        "Expected an asteroid or empty space at row {}, column {}, found {:?}",
+                        y + 1,
+                        x + 1,
+                        c
+                    ));
+                }
+                columns += 1;
+            }
+            match width {
+                Some(width) if width != columns => {
+                    return Err(format!(
+                        "Row {} is {} wide, but the rows before it are {} wide",
+                        y + 1,
+                        columns,
+                        width
+                    ))
+                }
+                _ => width = Some(columns),
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            asteroids,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Every asteroid, a row at a time from the top left.
+    pub fn asteroids(&self) -> &[Point] {
+        &self.asteroids
+    }
+
+    // Every other asteroid grouped by its direction from `station`, along
+    // with how far away it is, squared
+    fn directions(&self, station: Point) -> BTreeMap<Direction, Vec<(Point, usize)>> {
+        let mut directions = BTreeMap::<_, Vec<_>>::new();
+        for &asteroid in &self.asteroids {
+            if asteroid == station {
+                continue;
+            }
+            let dx = asteroid.x as isize - station.x as isize;
+            let dy = asteroid.y as isize - station.y as isize;
+            directions
+                .entry(Direction::new(dx, dy))
+                .or_default()
+                .push((asteroid, (dx * dx + dy * dy) as usize));
+        }
+        directions
+    }
+
+    /// The asteroids that can be seen from `station`, which are the closest
+    /// ones in each direction, in the order the laser would sweep them.
+    pub fn visible_from(&self, station: Point) -> Vec<Point> {
+        self.directions(station)
+            .into_values()
+            .filter_map(|asteroids| asteroids.into_iter().min_by_key(|a| a.1))
+            .map(|(asteroid, _)| asteroid)
+            .collect()
+    }
+
+    /// The same asteroids as `visible_from`, in reading order, but found by
+    /// checking every other asteroid for anything on the line of sight to
+    /// it. It's slower, but a very different way to get the same answer.
+    pub fn in_line_of_sight(&self, station: Point) -> Vec<Point> {
+        sight::visible_from(self, station)
+    }
+
+    /// The asteroid that can see the most others, and how many it sees, or
+    /// `None` if there are no asteroids at all. Ties go to the first
+    /// asteroid, reading from the top left.
+    pub fn best_station(&self) -> Option<(Point, usize)> {
+        self.best_station_with(Ties::First)
+    }
+
+    /// The asteroid that can see the most others, picking between any that
+    /// see as many as each other with `ties`.
+    pub fn best_station_with(&self, ties: Ties) -> Option<(Point, usize)> {
+        let distance = |p: Point, to: Point| {
+            let (dx, dy) = (p.x as isize - to.x as isize, p.y as isize - to.y as isize);
+            dx * dx + dy * dy
+        };
+        let mut best: Option<(Point, usize)> = None;
+        for (station, count) in self.visibility() {
+            let better = best.is_none_or(|(current, most)| match count.cmp(&most) {
+                Ordering::Greater => true,
+                Ordering::Less => false,
+                Ordering::Equal => match ties {
+                    Ties::First => false,
+                    Ties::Last => true,
+                    Ties::Nearest(to) => distance(station, to) < distance(current, to),
+                },
+            });
+            if better {
+                best = Some((station, count));
+            }
+        }
+        best
+    }
+
+    /// How many other asteroids each asteroid can see, in reading order.
+    pub fn visibility(&self) -> Vec<(Point, usize)> {
+        // Every offset between two asteroids fits in a grid twice the
+        // field's size, so the direction each offset reduces to can be
+        // worked out once for the whole field, rather than once for every
+        // pair of asteroids. That only pays off while the grid is no bigger
+        // than the number of pairs, though. A big field with few asteroids
+        // in it would need far more memory for the grid than checking each
+        // pair, so those group every pair by direction instead.
+        let (width, height) = (self.width as u64, self.height as u64);
+        let cells = (2 * width + 1).saturating_mul(2 * height + 1);
+        let pairs = (self.asteroids.len() as u64).pow(2);
+        if cells > pairs || cells > u32::MAX as u64 {
+            return self
+                .asteroids
+                .par_iter()
+                .map(|&station| {
+                    let directions = self
+                        .asteroids
+                        .iter()
+                        .filter(|&&asteroid| asteroid != station)
+                        .map(|asteroid| {
+                            let dx = asteroid.x as isize - station.x as isize;
+                            let dy = asteroid.y as isize - station.y as isize;
+                            Direction::new(dx, dy)
+                        })
+                        .collect::<HashSet<_>>();
+                    (station, directions.len())
+                })
+                .collect();
+        }
+
+        // Each station marks off the directions it has seen in its own grid,
+        // stamping them with its number so the grid never needs clearing
+        // between stations. The grid is smaller than `u32::MAX`, and there
+        // can't be more asteroids than cells.
+        let (width, height) = (self.width as isize, self.height as isize);
+        let side = 2 * width + 1;
+        let cell = |dx: isize, dy: isize| ((dy + height) * side + dx + width) as usize;
+        let mut reduced = vec![0u32; cells as usize];
+        for dy in -height..=height {
+            for dx in -width..=width {
+                if (dx, dy) != (0, 0) {
+                    let direction = Direction::new(dx, dy);
+                    reduced[cell(dx, dy)] = cell(direction.dx, direction.dy) as u32;
+                }
+            }
+        }
+
+        self.asteroids
+            .par_iter()
+            .enumerate()
+            .map_init(
+                || vec![0u32; reduced.len()],
+                |seen, (i, &station)| {
+                    let stamp = i as u32 + 1;
+                    let mut count = 0;
+                    for &asteroid in &self.asteroids {
+                        if asteroid == station {
+                            continue;
+                        }
+                        let dx = asteroid.x as isize - station.x as isize;
+                        let dy = asteroid.y as isize - station.y as isize;
+                        let direction = reduced[cell(dx, dy)] as usize;
+                        if seen[direction] != stamp {
+                            seen[direction] = stamp;
+                            count += 1;
+                        }
+                    }
+                    (station, count)
+                },
+            )
+            .collect()
+    }
+
+    /// How many other asteroids each asteroid can see, as CSV with a
+    /// header and a line of `x,y,visible` for each asteroid.
+    pub fn visibility_csv(&self) -> String {
+        let mut csv = "x,y,visible\n".to_owned();
+        for (station, count) in self.visibility() {
+            csv.push_str(&format!("{},{},{}\n", station.x, station.y, count));
+        }
+        csv
+    }
+
+    /// Every other asteroid, in the order the laser at `station` vaporizes
+    /// them. Each sweep round vaporizes the closest asteroid left in every
+    /// direction, starting from straight up and going clockwise.
+    pub fn vaporization_order(&self, station: Point) -> impl Iterator<Item = Point> {
+        self.vaporization_order_with(station, &Sweep::default())
+    }
+
+    /// Every other asteroid, in the order a laser at `station` sweeping the
+    /// way `sweep` says vaporizes them.
+    pub fn vaporization_order_with(
+        &self,
+        station: Point,
+        sweep: &Sweep,
+    ) -> impl Iterator<Item = Point> {
+        // An asteroid goes in the round after every closer one in its
+        // direction
+        let mut order = vec![];
+        for (direction, mut asteroids) in self.directions(station) {
+            asteroids.sort_by_key(|a| a.1);
+            for (round, (asteroid, _)) in asteroids.into_iter().enumerate() {
+                order.push((round, direction, asteroid));
+            }
+        }
+        order.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| sweep.compare(a.1, b.1)));
+        order.into_iter().map(|(_, _, asteroid)| asteroid)
+    }
+
+    /// Draw the field as an SVG image, marking `station` and numbering the
+    /// first `vaporized` asteroids its laser vaporizes, sweeping the way
+    /// `sweep` says.
+    pub fn svg(&self, station: Point, sweep: &Sweep, vaporized: usize) -> String {
+        svg::render(self, station, sweep, vaporized)
+    }
+
+    /// The `n`th asteroid the laser at `station` vaporizes, counting from 1.
+    pub fn nth_vaporized(&self, station: Point, n: usize) -> Result<Point, String> {
+        self.nth_vaporized_with(station, &Sweep::default(), n)
+    }
+
+    /// The `n`th asteroid a laser at `station` sweeping the way `sweep` says
+    /// vaporizes, counting from 1.
+    pub fn nth_vaporized_with(
+        &self,
+        station: Point,
+        sweep: &Sweep,
+        n: usize,
+    ) -> Result<Point, String> {
+        if n == 0 {
+            return Err("Asteroids are counted from 1".to_owned());
+        }
+        let order = self
+            .vaporization_order_with(station, sweep)
+            .collect::<Vec<_>>();
+        order
+            .get(n - 1)
+            .copied()
+            .ok_or_else(|| format!("Only {} asteroids to vaporize, not {}", order.len(), n))
+    }
+}
+
+fn best_station(field: &AsteroidField) -> Result<(Point, usize), String> {
+    field
+        .best_station()
+        .ok_or_else(|| "There are no asteroids to build a station on".to_owned())
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = AsteroidField;
+
+    fn parse(input: &str) -> Result<AsteroidField, String> {
+        AsteroidField::parse(input)
+    }
+
+    fn part1(field: &AsteroidField) -> Result<String, String> {
+        let (_, count) = best_station(field)?;
+        Ok(count.to_string())
+    }
+
+    fn part2(field: &AsteroidField) -> Result<String, String> {
+        let (station, _) = best_station(field)?;
+        let asteroid = field.nth_vaporized(station, 200)?;
+        Ok((asteroid.x * 100 + asteroid.y).to_string())
+    }
+}
+
+/// Report the best station and draw what it can see: `@` is the station,
+/// `#` a visible asteroid and `+` one hidden behind another.
+pub fn explain(input: &str, reporter: &mut dyn Reporter) -> Result<(), String> {
+    let field = AsteroidField::parse(input)?;
+    let (station, count) = best_station(&field)?;
+
+    let mut map = vec![vec!['.'; field.width()]; field.height()];
+    for asteroid in field.asteroids() {
+        map[asteroid.y][asteroid.x] = '+';
+    }
+    for asteroid in field.visible_from(station) {
+        map[asteroid.y][asteroid.x] = '#';
+    }
+    map[station.y][station.x] = '@';
+
+    reporter.report(
+        "best station",
+        &format!("{} sees {} asteroids", station, count),
+    );
+    reporter.report(
+        "visibility map",
+        &map.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    Ok(())
+}
+
+aoc_runner::register!(year: 2019, day: 10, solution: Day10, explain: explain);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    /// Random fields of up to 11 by 11, about two fifths asteroids.
+    pub(crate) fn fields() -> impl Strategy<Value = String> {
+        (1usize..12, 1usize..12).prop_flat_map(|(width, height)| {
+            proptest::collection::vec(proptest::bool::weighted(0.4), width * height).prop_map(
+                move |cells| {
+                    cells
+                        .chunks(width)
+                        .map(|row| row.iter().map(|&a| if a { '#' } else { '.' }).collect())
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                },
+            )
+        })
+    }
+
+    #[test]
+    fn directions_sweep_clockwise() {
+        let sweep = [
+            (0, -1),
+            (1, -3),
+            (1, -1),
+            (1, 0),
+            (2, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-3, -1),
+        ];
+        let directions = sweep
+            .iter()
+            .map(|&(dx, dy)| Direction::new(dx, dy))
+            .collect::<Vec<_>>();
+        let mut sorted = directions.clone();
+        sorted.sort();
+        assert_eq!(sorted, directions);
+        assert_eq!(Direction::new(0, -7), Direction::new(0, -1));
+        assert_eq!(Direction::new(-4, 6), Direction::new(-2, 3));
+        assert_ne!(Direction::new(2, 2), Direction::new(-2, -2));
+    }
+
+    macro_rules! validate_field {
+        ({$field:expr} => $count:expr, ($x:expr, $y:expr)) => {
+            let field = AsteroidField::parse($field).unwrap();
+            let (station, count) = field.best_station().unwrap();
+            assert_eq!($x, station.x, "X-coordinate mismatch");
+            assert_eq!($y, station.y, "Y-coordinate mismatch");
+            assert_eq!($count, count, "Count mismatch");
+        };
+    }
+
+    #[test]
+    fn case_1() {
+        validate_field! {
+            {
+r"......#.#.
+#..#.#....
+..#######.
+.#.#.###..
+.#..#.....
+..#....#.#
+#..#....#.
+.##.#..###
+##...#..#.
+.#....####"
+            } => 33, (5, 8)
+        };
+    }
+
+    #[test]
+    fn case_2() {
+        validate_field! {
+            {
+r"#.#...#.#.
+.###....#.
+.#....#...
+##.#.#.#.#
+....#.#.#.
+.##..###.#
+..#...##..
+..##....##
+......#...
+.####.###."
+            } => 35, (1, 2)
+        };
+    }
+
+    #[test]
+    fn case_3() {
+        validate_field! {
+            {
+r".#..#..###
+####.###.#
+....###.#.
+..###.##.#
+##.##.#.#.
+....###..#
+..#.#..#.#
+#..#.#.###
+.##...##.#
+.....#.#.."
+            } => 41, (6, 3)
+        };
+    }
+
+    #[test]
+    fn case_4() {
+        validate_field! {
+            {
+r".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##"
+            } => 210, (11, 13)
+        };
+    }
+
+    #[test]
+    fn case_sweep() {
+        let field = {
+            r".#..##.###...#######
+            ##.############..##.
+            .#.######.########.#
+            .###.#######.####.#.
+            #####.##.#.##.###.##
+            ..#####..#.#########
+            ####################
+            #.####....###.#.#.##
+            ##.#################
+            #####.##.###..####..
+            ..######..##.#######
+            ####.##.####...##..#
+            .#####..#.######.###
+            ##...#.##########...
+            #.##########.#######
+            .####.#.###.###.#.##
+            ....##.##.###..#####
+            .#.#.###########.###
+            #.#.#.#####.####.###
+            ###.##.####.##.#..##"
+        };
+
+        let field = AsteroidField::parse(field).unwrap();
+        let station = Point::new(11, 13);
+        assert_eq!(field.best_station(), Some((station, 210)));
+        assert_eq!(field.visible_from(station).len(), 210);
+        let order = field.vaporization_order(station).collect::<Vec<_>>();
+        // Every other asteroid, each exactly once
+        assert_eq!(order.len(), 299);
+        assert_eq!(order.iter().collect::<HashSet<_>>().len(), 299);
+        let nth = |n: usize| (order[n - 1].x, order[n - 1].y);
+        assert_eq!(nth(1), (11, 12));
+        assert_eq!(nth(2), (12, 1));
+        assert_eq!(nth(3), (12, 2));
+        assert_eq!(nth(10), (12, 8));
+        assert_eq!(nth(20), (16, 0));
+        assert_eq!(nth(50), (16, 9));
+        assert_eq!(nth(100), (10, 16));
+        assert_eq!(nth(199), (9, 6));
+        assert_eq!(nth(200), (8, 2));
+        assert_eq!(nth(201), (10, 9));
+        assert_eq!(nth(299), (11, 1));
+
+        assert_eq!(field.nth_vaporized(station, 200), Ok(Point::new(8, 2)));
+        assert_eq!(
+            field.nth_vaporized(station, 300),
+            Err("Only 299 asteroids to vaporize, not 300".to_owned())
+        );
+        assert!(field.nth_vaporized(station, 0).is_err());
+
+        // Nothing to vaporize from the only asteroid, and nowhere to build a
+        // station without any
+        let lonely = AsteroidField::parse("#.").unwrap();
+        assert_eq!(lonely.best_station(), Some((Point::new(0, 0), 0)));
+        assert_eq!(lonely.visibility_csv(), "x,y,visible\n0,0,0\n");
+        assert_eq!(lonely.vaporization_order(Point::new(0, 0)).count(), 0);
+        assert_eq!(AsteroidField::parse("..\n..").unwrap().best_station(), None);
+    }
+
+    proptest! {
+        #[test]
+        fn counts_every_direction_once(field in fields()) {
+            let field = AsteroidField::parse(&field).unwrap();
+            for (station, count) in field.visibility() {
+                prop_assert_eq!(count, field.visible_from(station).len());
+            }
+        }
+    }
+
+    #[test]
+    fn sweeps_any_way_round() {
+        let sweep = |start, rotation, order: &[(isize, isize)]| {
+            let sweep = Sweep { start, rotation };
+            let directions = order
+                .iter()
+                .map(|&(dx, dy)| Direction::new(dx, dy))
+                .collect::<Vec<_>>();
+            let mut sorted = directions.clone();
+            sorted.sort_by(|&a, &b| sweep.compare(a, b));
+            assert_eq!(sorted, directions, "{:?}", sweep);
+        };
+        sweep(
+            Direction::RIGHT,
+            Rotation::Clockwise,
+            &[(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1)],
+        );
+        sweep(
+            Direction::UP,
+            Rotation::Counterclockwise,
+            &[(0, -1), (-1, -1), (-1, 0), (-2, 1), (0, 1), (1, 0), (3, -1)],
+        );
+        sweep(
+            "1,1".parse().unwrap(),
+            Rotation::Counterclockwise,
+            &[(1, 1), (2, -1), (0, -1), (-1, 0), (-1, 1), (0, 1), (1, 2)],
+        );
+        assert_eq!("0,-3".parse(), Ok(Direction::UP));
+        assert!("0,0".parse::<Direction>().is_err());
+        assert!("up".parse::<Direction>().is_err());
+
+        // The first few from the puzzle's example, going the other way round
+        let field = AsteroidField::parse(
+            ".#....#####...#..
+            ##...##.#####..##
+            ##...#...#.#####.
+            ..#.....#...###..
+            ..#.#.....#....##",
+        )
+        .unwrap();
+        let backwards = Sweep {
+            rotation: Rotation::Counterclockwise,
+            ..Sweep::default()
+        };
+        let order = field
+            .vaporization_order_with(Point::new(8, 3), &backwards)
+            .take(4)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            [
+                Point::new(8, 1),
+                Point::new(7, 0),
+                Point::new(6, 0),
+                Point::new(6, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn breaks_ties() {
+        let field = AsteroidField::parse("#.#\n...\n#.#").unwrap();
+        assert_eq!(field.best_station(), Some((Point::new(0, 0), 3)));
+        assert_eq!(
+            field.best_station_with(Ties::Last),
+            Some((Point::new(2, 2), 3))
+        );
+        assert_eq!(
+            field.best_station_with(Ties::Nearest(Point::new(2, 1))),
+            Some((Point::new(2, 0), 3))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_fields() {
+        assert_eq!(
+            AsteroidField::parse("#..\n.#\n..#"),
+            Err("Row 2 is 2 wide, but the rows before it are 3 wide".to_owned())
+        );
+        assert_eq!(
+            AsteroidField::parse("#..\n.#o\n..#"),
+            Err("Expected an asteroid or empty space at row 2, column 3, found 'o'".to_owned())
+        );
+
+        // The examples mark the station with an X
+        let example = ".#....#####...#..\n##...##.#####..##\n##...#...#.#####.";
+        assert!(AsteroidField::parse(example).is_ok());
+        let example = example.replacen('#', "X", 1);
+        assert!(AsteroidField::parse(&example).is_err());
+        let legend = Legend {
+            asteroids: "#X".to_owned(),
+            ..Legend::default()
+        };
+        let field = AsteroidField::parse_with(&example, &legend).unwrap();
+        assert_eq!(field.asteroids()[0], Point::new(1, 0));
+        assert_eq!((field.width(), field.height()), (17, 3));
+
+        // Trailing blank lines are fine
+        assert_eq!(AsteroidField::parse("#.\n\n").unwrap().height(), 1);
+    }
+
+    #[test]
+    fn maps_visibility() {
+        // The puzzle's first example, with the count for each asteroid
+        let field = AsteroidField::parse(".#..#\n.....\n#####\n....#\n...##").unwrap();
+        let counts = field
+            .visibility()
+            .into_iter()
+            .map(|(_, count)| count)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![7, 7, 6, 7, 7, 7, 5, 7, 8, 7]);
+        let csv = field.visibility_csv();
+        assert!(csv.starts_with("x,y,visible\n1,0,7\n4,0,7\n0,2,6\n"));
+        assert_eq!(csv.lines().count(), 11);
+    }
+
+    #[test]
+    fn counts_visibility_in_sparse_fields() {
+        // A table of every offset in a field this size would need terabytes
+        let field = AsteroidField {
+            width: 1_000_000,
+            height: 1_000_000,
+            asteroids: vec![
+                Point::new(0, 0),
+                Point::new(500_000, 500_000),
+                Point::new(999_999, 0),
+                Point::new(999_999, 999_999),
+            ],
+        };
+        let counts = field
+            .visibility()
+            .into_iter()
+            .map(|(_, count)| count)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![2, 3, 3, 2]);
+        assert_eq!(
+            field.best_station(),
+            Some((Point::new(500_000, 500_000), 3))
+        );
+    }
+
+    #[test]
+    fn explains_visibility() {
+        let mut steps = vec![];
+        explain(".#..#\n.....\n#####\n....#\n...##", &mut steps).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    "best station".to_owned(),
+                    "(3, 4) sees 8 asteroids".to_owned()
+                ),
+                (
+                    "visibility map".to_owned(),
+                    ".+..#\n.....\n#####\n....#\n...@#".to_owned()
+                ),
+            ]
+        );
+    }
+}
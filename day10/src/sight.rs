@@ -0,0 +1,71 @@
+use crate::{gcd, AsteroidField, Point};
+use std::collections::HashSet;
+
+/// Whether an asteroid sits on one of the grid points strictly between
+/// `from` and `to`. Stepping by the direction between them, reduced by
+/// their gcd, lands on every one of those points.
+pub fn is_blocked(asteroids: &HashSet<Point>, from: Point, to: Point) -> bool {
+    let dx = to.x as isize - from.x as isize;
+    let dy = to.y as isize - from.y as isize;
+    let steps = gcd(dx.unsigned_abs(), dy.unsigned_abs()) as isize;
+    (1..steps).any(|i| {
+        let x = from.x as isize + dx / steps * i;
+        let y = from.y as isize + dy / steps * i;
+        asteroids.contains(&Point::new(x as usize, y as usize))
+    })
+}
+
+/// Every asteroid nothing else blocks the view of from `station`, checking
+/// each one on its own.
+pub fn visible_from(field: &AsteroidField, station: Point) -> Vec<Point> {
+    let asteroids = field.asteroids().iter().copied().collect::<HashSet<_>>();
+    field
+        .asteroids()
+        .iter()
+        .copied()
+        .filter(|&asteroid| asteroid != station && !is_blocked(&asteroids, station, asteroid))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::fields;
+    use proptest::prelude::*;
+
+    fn assert_agree(field: &AsteroidField) {
+        for &station in field.asteroids() {
+            let by_direction = field
+                .visible_from(station)
+                .into_iter()
+                .collect::<HashSet<_>>();
+            let by_sight = visible_from(field, station);
+            assert_eq!(by_sight.len(), by_direction.len(), "from {}", station);
+            assert!(
+                by_sight.iter().all(|a| by_direction.contains(a)),
+                "from {}",
+                station
+            );
+        }
+    }
+
+    #[test]
+    fn blocks_lines_of_sight() {
+        let field = AsteroidField::parse("#.#.#\n.....\n#...#").unwrap();
+        let asteroids = field.asteroids().iter().copied().collect::<HashSet<_>>();
+        assert!(is_blocked(&asteroids, Point::new(0, 0), Point::new(4, 0)));
+        assert!(!is_blocked(&asteroids, Point::new(0, 0), Point::new(2, 0)));
+        // (2, 1) isn't an asteroid, and (1, 0.5) isn't on the grid
+        assert!(!is_blocked(&asteroids, Point::new(0, 0), Point::new(4, 2)));
+        assert!(!is_blocked(&asteroids, Point::new(0, 2), Point::new(2, 0)));
+        assert_eq!(field.in_line_of_sight(Point::new(2, 0)).len(), 4);
+        assert_agree(&field);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_directions(field in fields()) {
+            assert_agree(&AsteroidField::parse(&field).unwrap());
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{AsteroidField, Point, Sweep};
+
+/// Draw every asteroid in the field as a grey dot, with the station in red
+/// and the first `vaporized` asteroids its laser vaporizes in orange,
+/// numbered in the order they go, like the puzzle's worked example.
+pub fn render(field: &AsteroidField, station: Point, sweep: &Sweep, vaporized: usize) -> String {
+    // Each space in the field is one unit across, centered on its
+    // coordinates
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-0.5 -0.5 {} {}\">\n",
+        field.width(),
+        field.height()
+    );
+    svg += &format!(
+        "  <rect x=\"-0.5\" y=\"-0.5\" width=\"{}\" height=\"{}\" fill=\"black\"/>\n",
+        field.width(),
+        field.height()
+    );
+
+    let circle = |p: Point, radius: f64, color: &str| {
+        format!(
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+            p.x, p.y, radius, color
+        )
+    };
+    for &asteroid in field.asteroids() {
+        if asteroid != station {
+            svg += &circle(asteroid, 0.2, "gray");
+        }
+    }
+    svg += &circle(station, 0.4, "red");
+
+    for (i, asteroid) in field
+        .vaporization_order_with(station, sweep)
+        .take(vaporized)
+        .enumerate()
+    {
+        svg += &circle(asteroid, 0.4, "#ff7f0e");
+        svg += &format!(
+            "  <text x=\"{}\" y=\"{}\" font-size=\"0.4\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+            asteroid.x,
+            asteroid.y,
+            i + 1
+        );
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbers_vaporized_asteroids() {
+        let field = AsteroidField::parse(
+            ".#....#####...#..
+            ##...##.#####..##
+            ##...#...#.#####.
+            ..#.....#...###..
+            ..#.#.....#....##",
+        )
+        .unwrap();
+        let svg = render(&field, Point::new(8, 3), &Sweep::default(), 9);
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-0.5 -0.5 17 5\">"));
+        assert!(svg.contains("cx=\"8\" cy=\"3\" r=\"0.4\" fill=\"red\""));
+        // Every asteroid but the station, then the nine vaporized ones again
+        assert_eq!(svg.matches("<circle").count(), field.asteroids().len() + 9);
+        assert_eq!(svg.matches("<text").count(), 9);
+        // The first nine, as the puzzle numbers them
+        assert!(svg.contains("x=\"8\" y=\"1\" font-size=\"0.4\" text-anchor=\"middle\" dominant-baseline=\"central\">1<"));
+        assert!(svg.contains("x=\"15\" y=\"1\" font-size=\"0.4\" text-anchor=\"middle\" dominant-baseline=\"central\">9<"));
+        assert!(!svg.contains(">10<"));
+
+        // Asking for more than there are numbers every one of them
+        let svg = render(&field, Point::new(8, 3), &Sweep::default(), 100);
+        assert_eq!(svg.matches("<text").count(), field.asteroids().len() - 1);
+    }
+}
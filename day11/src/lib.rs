@@ -0,0 +1,309 @@
+use aoc_runner::Solution;
+use geometry::{Direction, Pos, Robot, Turn};
+use int_code_emulator::{parse_str, Program, ValueIo};
+use std::collections::HashMap;
+
+mod png;
+pub use colors::Rgb;
+pub use png::Raster;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    // The program reads and paints black as 0 and white as 1
+    fn from_value(value: i128) -> Option<Color> {
+        match value {
+            0 => Some(Color::Black),
+            1 => Some(Color::White),
+            _ => None,
+        }
+    }
+
+    fn value(self) -> i128 {
+        match self {
+            Color::Black => 0,
+            Color::White => 1,
+        }
+    }
+}
+
+// The program asks for a left turn with 0 and a right turn with 1
+fn turn_from_value(value: i128) -> Option<Turn> {
+    match value {
+        0 => Some(Turn::Left),
+        1 => Some(Turn::Right),
+        _ => None,
+    }
+}
+
+/// What the robot did while painting, to sanity check a run or compare one
+/// robot program against another.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// How many times the robot painted a panel, counting every coat
+    pub operations: usize,
+    /// How many panels were painted more than once
+    pub repainted: usize,
+    /// The top left and bottom right panels of the smallest box around every
+    /// panel painted, or `None` if none were
+    pub bounds: Option<(Pos, Pos)>,
+}
+
+impl Stats {
+    fn new(coats: &HashMap<Pos, usize>) -> Self {
+        let min_x = coats.keys().map(|p| p.0).min();
+        let max_x = coats.keys().map(|p| p.0).max();
+        let min_y = coats.keys().map(|p| p.1).min();
+        let max_y = coats.keys().map(|p| p.1).max();
+        let bounds = match (min_x, max_x, min_y, max_y) {
+            (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) => {
+                Some(((min_x, min_y), (max_x, max_y)))
+            }
+            _ => None,
+        };
+        Self {
+            operations: coats.values().sum(),
+            repainted: coats.values().filter(|&&n| n > 1).count(),
+            bounds,
+        }
+    }
+}
+
+/// Drives the hull painting robot's program, reading the camera to it and
+/// carrying out its instructions.
+pub struct Painter<'a> {
+    mem: &'a mut Vec<i128>,
+}
+
+impl<'a> Painter<'a> {
+    pub fn new(mem: &'a mut Vec<i128>) -> Painter<'a> {
+        Self { mem }
+    }
+
+    /// Run the robot from the given starting panel's color until its program
+    /// exits, returning the color of every panel it painted.
+    pub fn run(self, init: Color) -> Result<HashMap<Pos, Color>, String> {
+        self.run_with(init, |_| {}).map(|(painted, _)| painted)
+    }
+
+    /// Run the robot like `run`, handing it to `painted` each time it has
+    /// painted a panel and moved on. Returns what it did along with the
+    /// panels it painted, or which step the program gave an instruction the
+    /// robot can't carry out.
+    pub fn run_with(
+        self,
+        init: Color,
+        mut painted: impl FnMut(&Robot<Color>),
+    ) -> Result<(HashMap<Pos, Color>, Stats), String> {
+        let io = ValueIo::new();
+        let mut program = Program::new(self.mem, &io);
+        let mut robot = Robot::new((0, 0), Direction::Up);
+        let mut coats = HashMap::new();
+        io.push_input(init.value());
+        // Each camera reading is answered with a color to paint and a way to
+        // turn
+        for step in 1.. {
+            if !next_output(&mut program, &io, step)? {
+                break;
+            }
+            if !next_output(&mut program, &io, step)? {
+                return Err(format!(
+                    "Step {}: the program exited after painting, without turning",
+                    step
+                ));
+            }
+            let output = io.take_output();
+            let color = Color::from_value(output[0]).ok_or_else(|| {
+                format!(
+                    "Step {}: expected a color of 0 or 1, found {}",
+                    step, output[0]
+                )
+            })?;
+            let turn = turn_from_value(output[1]).ok_or_else(|| {
+                format!(
+                    "Step {}: expected a turn of 0 or 1, found {}",
+                    step, output[1]
+                )
+            })?;
+            robot.mark(color);
+            *coats.entry(robot.pos).or_insert(0) += 1;
+            robot.turn(turn);
+            robot.advance();
+            painted(&robot);
+            let camera = robot.here().copied().unwrap_or(Color::Black);
+            io.push_input(camera.value());
+        }
+        let stats = Stats::new(&coats);
+        Ok((robot.into_visited(), stats))
+    }
+}
+
+// Run the program until it writes its next output, returning false if it
+// exits first. The robot only reads the camera once for each panel, so a
+// program that reads again before it has painted and turned is an error.
+fn next_output(program: &mut Program<&ValueIo>, io: &ValueIo, step: usize) -> Result<bool, String> {
+    loop {
+        if program.wants_input() && !io.has_input() {
+            return Err(format!(
+                "Step {}: the program read the camera again before painting and turning",
+                step
+            ));
+        }
+        let output = program.gives_output();
+        if !program.step() {
+            return Ok(false);
+        }
+        if output {
+            return Ok(true);
+        }
+    }
+}
+
+/// Render the painted hull, with white panels drawn as `#`.
+pub fn render(map: &HashMap<Pos, Color>) -> String {
+    draw(map, None)
+}
+
+/// Render the hull the robot has painted so far, with the robot drawn as an
+/// arrow pointing the way it's facing.
+pub fn render_robot(robot: &Robot<Color>) -> String {
+    draw(robot.visited(), Some((robot.pos, robot.facing)))
+}
+
+fn draw(map: &HashMap<Pos, Color>, robot: Option<(Pos, Direction)>) -> String {
+    let positions = map.keys().chain(robot.as_ref().map(|(pos, _)| pos));
+    let min_x = positions.clone().map(|p| p.0).min().unwrap_or(0);
+    let max_x = positions.clone().map(|p| p.0).max().unwrap_or(-1);
+    let min_y = positions.clone().map(|p| p.1).min().unwrap_or(0);
+    let max_y = positions.map(|p| p.1).max().unwrap_or(-1);
+
+    let mut image = String::new();
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let draw = match robot {
+                Some((pos, facing)) if pos == (x, y) => match facing {
+                    Direction::Up => '^',
+                    Direction::Right => '>',
+                    Direction::Down => 'v',
+                    Direction::Left => '<',
+                },
+                _ => match map.get(&(x, y)) {
+                    Some(Color::White) => '#',
+                    Some(Color::Black) | None => ' ',
+                },
+            };
+            image.push(draw);
+        }
+        image.push('\n');
+    }
+    image
+}
+
+/// Draw the painted hull as a PNG.
+pub fn png(map: &HashMap<Pos, Color>, raster: &Raster) -> Result<Vec<u8>, String> {
+    raster.render(map)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse_str(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        let mut mem = mem.clone();
+        let painted = Painter::new(&mut mem).run(Color::Black)?;
+        Ok(painted.len().to_string())
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        let mut mem = mem.clone();
+        let painted = Painter::new(&mut mem).run(Color::White)?;
+        Ok(render(&painted))
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 11, solution: Day11);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn paints_and_turns() {
+        // Paint white and turn left four times over, then paint the panel
+        // it's back on black if the camera saw it white
+        let program = vec![
+            3, 100, 104, 1, 104, 0, 3, 100, 104, 1, 104, 0, 3, 100, 104, 1, 104, 0, 3, 100, 104, 1,
+            104, 0, 3, 100, 1002, 100, -1, 100, 1001, 100, 1, 100, 4, 100, 104, 0, 99,
+        ];
+        let painted = Painter::new(&mut program.clone())
+            .run(Color::Black)
+            .unwrap();
+        assert_eq!(painted.len(), 4);
+        assert!(matches!(painted[&(0, 0)], Color::Black));
+        for panel in &[(-1, 0), (-1, 1), (0, 1)] {
+            assert!(matches!(painted[panel], Color::White));
+        }
+        assert_eq!(render(&painted), "# \n##\n");
+
+        let mut frames = vec![];
+        // The robot after each panel it paints
+        let (_, stats) = Painter::new(&mut program.clone())
+            .run_with(Color::Black, |robot| frames.push(render_robot(robot)))
+            .unwrap();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[0], "<#\n");
+        assert_eq!(frames[1], "##\nv \n");
+        assert_eq!(frames[4], "< \n##\n");
+        assert_eq!(render(&HashMap::new()), "");
+
+        // The panel it started on is painted twice
+        assert_eq!(
+            stats,
+            Stats {
+                operations: 5,
+                repainted: 1,
+                bounds: Some(((-1, 0), (0, 1))),
+            }
+        );
+        let (painted, stats) = Painter::new(&mut vec![3, 0, 99])
+            .run_with(Color::Black, |_| {})
+            .unwrap();
+        assert!(painted.is_empty());
+        assert_eq!(stats, Stats::default());
+    }
+
+    #[test]
+    fn rejects_bad_instructions() {
+        let run = |mut program: Vec<i128>| Painter::new(&mut program).run(Color::Black);
+        // A good step, then a color that doesn't exist
+        assert_eq!(
+            run(vec![3, 100, 104, 1, 104, 0, 3, 100, 104, 2, 104, 0, 99]),
+            Err("Step 2: expected a color of 0 or 1, found 2".to_owned())
+        );
+        assert_eq!(
+            run(vec![3, 100, 104, 1, 104, -1, 99]),
+            Err("Step 1: expected a turn of 0 or 1, found -1".to_owned())
+        );
+        assert_eq!(
+            run(vec![3, 100, 104, 1, 99]),
+            Err("Step 1: the program exited after painting, without turning".to_owned())
+        );
+        // Reading the camera twice for one panel, or never painting at all
+        let again = "Step 1: the program read the camera again before painting and turning";
+        assert_eq!(run(vec![3, 100, 3, 100, 99]), Err(again.to_owned()));
+        assert_eq!(run(vec![3, 100, 104, 1, 3, 100, 99]), Err(again.to_owned()));
+        assert_eq!(
+            run(vec![3, 100, 104, 1, 104, 0, 3, 100, 3, 100, 99]),
+            Err(again.replace("Step 1", "Step 2"))
+        );
+    }
+}
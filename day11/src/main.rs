@@ -1,186 +1,152 @@
-use int_code_emulator::{parse, PipedIo, Program};
+use aoc_runner::Solution;
+use day11::{render, render_robot, Color, Day11, Painter, Raster};
 use std::{
-    collections::{hash_map::Entry, HashMap},
-    isize,
-    str::FromStr,
-    sync::mpsc::{self, Receiver, Sender},
+    env, fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
 };
 
-#[derive(Copy, Clone)]
-enum Color {
-    Black,
-    White,
-}
-
-trait IoRepr {
-    fn repr(&self) -> &'static str;
-}
-
-impl IoRepr for Color {
-    fn repr(&self) -> &'static str {
-        match self {
-            Color::Black => "0",
-            Color::White => "1",
-        }
-    }
-}
-
-impl FromStr for Color {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Color, ()> {
-        match s.trim() {
-            "0" => Ok(Color::Black),
-            "1" => Ok(Color::White),
-            _ => Err(()),
-        }
-    }
-}
-
-#[derive(Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Copy, Clone)]
-enum Turn {
-    Left,
-    Right,
-}
-
-impl FromStr for Turn {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Turn, ()> {
-        match s.trim() {
-            "0" => Ok(Turn::Left),
-            "1" => Ok(Turn::Right),
-            _ => Err(()),
-        }
-    }
-}
-
-impl Direction {
-    fn turn(&mut self, turn: Turn) {
-        *self = match (*self, turn) {
-            (Direction::Up, Turn::Left) | (Direction::Down, Turn::Right) => Direction::Left,
-            (Direction::Up, Turn::Right) | (Direction::Down, Turn::Left) => Direction::Right,
-            (Direction::Left, Turn::Right) | (Direction::Right, Turn::Left) => Direction::Up,
-            (Direction::Right, Turn::Right) | (Direction::Left, Turn::Left) => Direction::Down,
-        }
-    }
-
-    fn next(&self, (x, y): (isize, isize)) -> (isize, isize) {
-        match self {
-            Direction::Up => (x, y + 1),
-            Direction::Down => (x, y - 1),
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-        }
-    }
-}
-
-struct Painter<'a> {
-    tx: Sender<String>,
-    rx: Receiver<String>,
-    program: Option<Program<'a, PipedIo>>,
-}
-
-impl<'a> Painter<'a> {
-    fn new(mem: &'a mut Vec<i128>) -> Painter<'a> {
-        let (tx1, rx1) = mpsc::channel();
-        let (tx2, rx2) = mpsc::channel();
-        let io1 = PipedIo::new((String::new(), rx1), (String::new(), tx2));
-        Self {
-            program: Some(Program::new(mem, io1)),
-            rx: rx2,
-            tx: tx1,
-        }
-    }
-
-    fn track_painter(
-        init: Color,
-        rx: Receiver<String>,
-        tx: Sender<String>,
-    ) -> HashMap<(isize, isize), Color> {
-        let mut direction = Direction::Up;
-        let mut visited = HashMap::new();
-        let mut pos: (isize, isize) = (0, 0);
-        tx.send(format!("{}\n", init.repr()))
-            .expect("Failed to send initializing color");
-        while let Ok(val) = rx.recv() {
-            let color = val.parse::<Color>().unwrap();
-            let turn = rx.recv().expect("turn").parse::<Turn>().unwrap();
-            visited
-                .entry(pos)
-                .and_modify(|c| *c = color)
-                .or_insert(color);
-            direction.turn(turn);
-            pos = direction.next(pos);
-            let to_send = visited.get(&pos).map(|c| c.repr()).unwrap_or("0");
-            if let Err(_) = tx.send(format!("{}\n", to_send)) {
-                break;
+const USAGE: &str = "Usage: day11 [--part 1|2] [--start-color black|white] [--input PATH] \
+                     [--png PATH] [--scale N] [--colors BLACK,WHITE] [--watch] [--fps N]";
+
+// Runs the robot starting on a black panel for part 1, and on a white one for
+// part 2, reporting how many panels it paints each time and drawing the
+// registration identifier it paints from white. How many times it paints, how
+// many panels it paints over again and how far it gets are reported too.
+// `--part` runs only one of them, and `--start-color` starts it on another
+// color. `--input` reads another program. `--png` also saves the last hull
+// painted as an image, with each panel `--scale` pixels across and painted in
+// `--colors`, given as `rrggbb`. `--watch` redraws the hull in the terminal
+// after every panel the robot paints, no faster than `--fps` frames a second.
+fn main() -> Result<(), String> {
+    let mut parts = vec![(1, Color::Black), (2, Color::White)];
+    let mut start = None;
+    let mut path = None;
+    let mut png = None;
+    let mut watch = false;
+    let mut fps = 30;
+    let mut raster = Raster::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args
+                    .next()
+                    .ok_or_else(|| format!("--part needs 1 or 2\n{}", USAGE))?;
+                parts = match part.as_str() {
+                    "1" => vec![(1, Color::Black)],
+                    "2" => vec![(2, Color::White)],
+                    _ => return Err(format!("Bad part {}\n{}", part, USAGE)),
+                };
+            }
+            "--start-color" => {
+                let color = args
+                    .next()
+                    .ok_or_else(|| format!("--start-color needs black or white\n{}", USAGE))?;
+                start = Some(match color.as_str() {
+                    "black" => Color::Black,
+                    "white" => Color::White,
+                    _ => return Err(format!("Bad start color {}\n{}", color, USAGE)),
+                });
+            }
+            "--input" => {
+                let p = args
+                    .next()
+                    .ok_or_else(|| format!("--input needs a path\n{}", USAGE))?;
+                path = Some(PathBuf::from(p));
+            }
+            "--png" => {
+                png = Some(
+                    args.next()
+                        .ok_or_else(|| format!("--png needs a path\n{}", USAGE))?,
+                )
+            }
+            "--scale" => {
+                let scale = args
+                    .next()
+                    .ok_or_else(|| format!("--scale needs a number\n{}", USAGE))?;
+                raster.scale = scale
+                    .parse()
+                    .map_err(|e| format!("Bad scale {}: {}", scale, e))?;
+            }
+            "--colors" => {
+                let colors = args
+                    .next()
+                    .ok_or_else(|| format!("--colors needs two colors\n{}", USAGE))?;
+                let (black, white) = colors
+                    .split_once(',')
+                    .ok_or_else(|| format!("--colors needs two colors\n{}", USAGE))?;
+                raster.black = black.parse()?;
+                raster.white = white.parse()?;
+                raster.unpainted = raster.black;
             }
+            "--watch" => watch = true,
+            "--fps" => {
+                let f = args
+                    .next()
+                    .ok_or_else(|| format!("--fps needs a number\n{}", USAGE))?;
+                fps = match f.parse() {
+                    Ok(0) | Err(_) => return Err(format!("Bad fps {}\n{}", f, USAGE)),
+                    Ok(fps) => fps,
+                };
+            }
+            _ => return Err(format!("Unexpected argument {}\n{}", arg, USAGE)),
         }
-
-        visited
-    }
-
-    fn run(mut self, init: Color) -> HashMap<(isize, isize), Color> {
-        let program = self.program.take().unwrap();
-        let (rx, tx) = (self.rx, self.tx);
-        let thread = std::thread::spawn(move || Self::track_painter(init, rx, tx));
-        program.run();
-        thread.join().expect("Could not join thread")
     }
-}
 
-fn draw(map: HashMap<(isize, isize), Color>) {
-    let (min_x, max_x, min_y, max_y) = map.iter().fold(
-        (isize::MAX, isize::MIN, isize::MAX, isize::MIN),
-        |(mut min_x, mut max_x, mut min_y, mut max_y), (&(x, y), _)| {
-            if x < min_x {
-                min_x = x;
-            } else if x > max_x {
-                max_x = x;
+    let path = path.unwrap_or_else(|| day11::DAY.input_path());
+    let input = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mem = Day11::parse(&input)?;
+    let mut painted = None;
+    for (part, color) in parts {
+        let color = start.unwrap_or(color);
+        let mut mem = mem.clone();
+        let frame = Duration::from_secs(1) / fps;
+        let mut next = Instant::now();
+        let (hull, stats) = Painter::new(&mut mem).run_with(color, |robot| {
+            if watch {
+                thread::sleep(next.saturating_duration_since(Instant::now()));
+                next = Instant::now() + frame;
+                // Clear the screen and draw from the top left
+                print!("\x1b[2J\x1b[H{}", render_robot(robot));
             }
-
-            if y < min_y {
-                min_y = y;
-            } else if y > max_y {
-                max_y = y;
+        })?;
+
+        let name = match color {
+            Color::Black => "black",
+            Color::White => "white",
+        };
+        println!("Part {}, starting on a {} panel:", part, name);
+        println!("  Panels painted: {}", hull.len());
+        println!("  Paint operations: {}", stats.operations);
+        println!("  Panels painted more than once: {}", stats.repainted);
+        if let Some(((min_x, min_y), (max_x, max_y))) = stats.bounds {
+            println!(
+                "  Hull spans ({}, {}) to ({}, {}), {} by {} panels",
+                min_x,
+                min_y,
+                max_x,
+                max_y,
+                max_x - min_x + 1,
+                max_y - min_y + 1
+            );
+        }
+        // Starting on white paints the identifier, but starting on black
+        // only paints a mess too big to be worth drawing
+        if matches!(color, Color::White) {
+            for line in render(&hull).lines() {
+                println!("  {}", line);
             }
-
-            (min_x, max_x, min_y, max_y)
-        },
-    );
-
-    for y in (min_y..=max_y).rev() {
-        for x in min_x..=max_x {
-            let draw = match map.get(&(x, y)) {
-                Some(c) => match c {
-                    Color::Black => " ",
-                    Color::White => "#",
-                },
-                None => " ",
-            };
-            print!("{}", draw);
         }
-        println!();
+        painted = Some(hull);
     }
-}
-
-fn main() {
-    let mut mem = parse("day11/input.txt").expect("Failed to parse int code");
-    let mut mem_clone = mem.clone();
-    let painter = Painter::new(&mut mem_clone);
-    let painted = painter.run(Color::Black);
-    println!("Total locations painted: {}", painted.len());
 
-    let painter = Painter::new(&mut mem);
-    let painted = painter.run(Color::White);
-    draw(painted);
+    if let (Some(path), Some(painted)) = (png, painted) {
+        fs::write(&path, day11::png(&painted, &raster)?)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        println!("Hull drawn to {}", path);
+    }
+    Ok(())
 }
@@ -0,0 +1,93 @@
+use crate::Color;
+use colors::Rgb;
+use image::RgbImage;
+use std::{collections::HashMap, io::Cursor};
+
+/// How to draw the painted hull into a PNG.
+#[derive(Clone, Debug)]
+pub struct Raster {
+    /// How many pixels across each panel is drawn
+    pub scale: u32,
+    pub black: Rgb,
+    pub white: Rgb,
+    /// Panels the robot never painted, which are black on the real hull
+    pub unpainted: Rgb,
+}
+
+impl Default for Raster {
+    fn default() -> Self {
+        Self {
+            scale: 10,
+            black: Rgb([0, 0, 0]),
+            white: Rgb([255, 255, 255]),
+            unpainted: Rgb([0, 0, 0]),
+        }
+    }
+}
+
+impl Raster {
+    /// Draw every panel between the furthest the robot painted, with a
+    /// panel's margin around them, as a PNG.
+    pub fn render(&self, map: &HashMap<(isize, isize), Color>) -> Result<Vec<u8>, String> {
+        colors::check_scale(self.scale.into())?;
+        let min_x = map.keys().map(|p| p.0).min().ok_or("Nothing was painted")?;
+        let max_x = map.keys().map(|p| p.0).max().unwrap();
+        let min_y = map.keys().map(|p| p.1).min().unwrap();
+        let max_y = map.keys().map(|p| p.1).max().unwrap();
+
+        let panels = |span: isize| (span as u32 + 3) * self.scale;
+        let (width, height) = (panels(max_x - min_x), panels(max_y - min_y));
+        let image = RgbImage::from_fn(width, height, |x, y| {
+            let x = min_x - 1 + (x / self.scale) as isize;
+            let y = min_y - 1 + (y / self.scale) as isize;
+            let color = match map.get(&(x, y)) {
+                Some(Color::Black) => self.black,
+                Some(Color::White) => self.white,
+                None => self.unpainted,
+            };
+            image::Rgb(color.0)
+        });
+
+        let mut png = vec![];
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .map_err(|e| format!("Failed to encode the PNG: {}", e))?;
+        Ok(png)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rasterizes_hull() {
+        let mut map = HashMap::new();
+        map.insert((0, 0), Color::White);
+        map.insert((1, 0), Color::Black);
+        map.insert((1, 1), Color::White);
+        let raster = Raster {
+            scale: 2,
+            unpainted: Rgb([128, 128, 128]),
+            ..Raster::default()
+        };
+        let png = raster.render(&map).unwrap();
+        let image = image::load_from_memory(&png).unwrap().to_rgb8();
+        // Two panels each way, with a panel's margin all around
+        assert_eq!(image.dimensions(), (8, 8));
+        let at = |x: u32, y: u32| image.get_pixel(x, y).0;
+        assert_eq!(at(0, 0), raster.unpainted.0);
+        assert_eq!(at(2, 2), raster.white.0);
+        assert_eq!(at(3, 3), raster.white.0);
+        assert_eq!(at(4, 2), raster.black.0);
+        assert_eq!(at(4, 4), raster.white.0);
+        assert_eq!(at(2, 4), raster.unpainted.0);
+
+        let zero = Raster {
+            scale: 0,
+            ..raster.clone()
+        };
+        assert!(zero.render(&map).is_err());
+        assert!(raster.render(&HashMap::new()).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use aoc_runner::Solution;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Moon {
+    pos: [i64; 3],
+    vel: [i64; 3],
+}
+
+impl Moon {
+    fn new(pos: [i64; 3]) -> Self {
+        Self { pos, vel: [0; 3] }
+    }
+
+    fn potential_energy(&self) -> i64 {
+        self.pos.iter().map(|p| p.abs()).sum()
+    }
+
+    fn kinetic_energy(&self) -> i64 {
+        self.vel.iter().map(|v| v.abs()).sum()
+    }
+
+    fn energy(&self) -> i64 {
+        self.potential_energy() * self.kinetic_energy()
+    }
+}
+
+/// Moons orbiting Jupiter, pulling on each other along every axis.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct System {
+    moons: Vec<Moon>,
+}
+
+impl System {
+    pub fn new(positions: &[[i64; 3]]) -> Self {
+        Self {
+            moons: positions.iter().copied().map(Moon::new).collect(),
+        }
+    }
+
+    /// Apply gravity between every pair of moons, then move each moon by
+    /// its velocity.
+    pub fn step(&mut self) {
+        for axis in 0..3 {
+            self.step_axis(axis);
+        }
+    }
+
+    // The axes never affect each other, so they can be stepped separately
+    fn step_axis(&mut self, axis: usize) {
+        for i in 0..self.moons.len() {
+            for j in i + 1..self.moons.len() {
+                let pull = (self.moons[j].pos[axis] - self.moons[i].pos[axis]).signum();
+                self.moons[i].vel[axis] += pull;
+                self.moons[j].vel[axis] -= pull;
+            }
+        }
+
+        for moon in &mut self.moons {
+            moon.pos[axis] += moon.vel[axis];
+        }
+    }
+
+    pub fn energy(&self) -> i64 {
+        self.moons.iter().map(Moon::energy).sum()
+    }
+
+    fn axis(&self, axis: usize) -> Vec<(i64, i64)> {
+        self.moons
+            .iter()
+            .map(|moon| (moon.pos[axis], moon.vel[axis]))
+            .collect()
+    }
+
+    /// Count the steps until every moon is back where it started, moving
+    /// at the speed it started with.
+    pub fn period(&self) -> u64 {
+        // Each step can be undone, so the first state to repeat is always
+        // the initial one. Finding when each axis repeats on its own and
+        // taking the LCM is much faster than waiting for all three at once.
+        (0..3)
+            .map(|axis| {
+                let initial = self.axis(axis);
+                let mut system = self.clone();
+                let mut steps = 0;
+                loop {
+                    system.step_axis(axis);
+                    steps += 1;
+                    if system.axis(axis) == initial {
+                        break steps;
+                    }
+                }
+            })
+            .fold(1, lcm)
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let t = b;
+        b = a % b;
+        a = t;
+    }
+    a
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+/// Parse one moon per line, e.g. `<x=-1, y=0, z=2>`.
+pub fn parse(input: &str) -> Result<System, String> {
+    let positions = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(parse_line)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(System::new(&positions))
+}
+
+fn parse_line(line: &str) -> Result<[i64; 3], String> {
+    let coords = line
+        .strip_prefix('<')
+        .and_then(|l| l.strip_suffix('>'))
+        .ok_or_else(|| format!("Expected a position like <x=1, y=2, z=3>, got {}", line))?;
+
+    let mut pos = [0; 3];
+    let mut parts = coords.split(',').map(str::trim);
+    for (p, name) in pos.iter_mut().zip(&["x=", "y=", "z="]) {
+        let part = parts
+            .next()
+            .ok_or_else(|| format!("Missing {} in {}", &name[..1], line))?;
+        *p = part
+            .strip_prefix(name)
+            .ok_or_else(|| format!("Expected {}, got {}", name, part))?
+            .parse()
+            .map_err(|e| format!("Error parsing {}: {}", part, e))?;
+    }
+
+    match parts.next() {
+        Some(extra) => Err(format!("Unexpected {} in {}", extra, line)),
+        None => Ok(pos),
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = System;
+
+    fn parse(input: &str) -> Result<System, String> {
+        parse(input)
+    }
+
+    fn part1(system: &System) -> Result<String, String> {
+        let mut system = system.clone();
+        for _ in 0..1000 {
+            system.step();
+        }
+        Ok(system.energy().to_string())
+    }
+
+    fn part2(system: &System) -> Result<String, String> {
+        Ok(system.period().to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 12, solution: Day12);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_1: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+    const SAMPLE_2: &str = "<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>";
+
+    fn energy_after(input: &str, steps: usize) -> i64 {
+        let mut system = parse(input).unwrap();
+        for _ in 0..steps {
+            system.step();
+        }
+        system.energy()
+    }
+
+    #[test]
+    fn parses_moons() {
+        let system = parse(SAMPLE_1).unwrap();
+        assert_eq!(system.moons.len(), 4);
+        assert_eq!(system.moons[1], Moon::new([2, -10, -7]));
+        assert!(parse("<x=1, y=2>").is_err());
+        assert!(parse("<x=1, y=2, z=3, w=4>").is_err());
+        assert!(parse("x=1, y=2, z=3").is_err());
+        assert!(parse("<x=1, y=b, z=3>").is_err());
+    }
+
+    #[test]
+    fn steps_moons() {
+        let mut system = parse(SAMPLE_1).unwrap();
+        system.step();
+        assert_eq!(
+            system.moons,
+            vec![
+                Moon {
+                    pos: [2, -1, 1],
+                    vel: [3, -1, -1]
+                },
+                Moon {
+                    pos: [3, -7, -4],
+                    vel: [1, 3, 3]
+                },
+                Moon {
+                    pos: [1, -7, 5],
+                    vel: [-3, 1, -3]
+                },
+                Moon {
+                    pos: [2, 2, 0],
+                    vel: [-1, -3, 1]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn total_energy() {
+        assert_eq!(energy_after(SAMPLE_1, 10), 179);
+        assert_eq!(energy_after(SAMPLE_2, 100), 1940);
+    }
+
+    #[test]
+    fn period() {
+        assert_eq!(parse(SAMPLE_1).unwrap().period(), 2772);
+        assert_eq!(parse(SAMPLE_2).unwrap().period(), 4_686_774_924);
+    }
+}
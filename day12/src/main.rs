@@ -0,0 +1,12 @@
+use aoc_runner::Solution;
+use day12::Day12;
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day12::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let system = Day12::parse(&input)?;
+    println!("Total energy after 1000 steps: {}", Day12::part1(&system)?);
+    println!("Steps until the moons repeat: {}", Day12::part2(&system)?);
+    Ok(())
+}
@@ -0,0 +1,316 @@
+use aoc_runner::Solution;
+use int_code_emulator::{parse_str, Io, Program};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    convert::TryFrom,
+    io::{self, BufRead, Write},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl TryFrom<i128> for Tile {
+    type Error = String;
+
+    fn try_from(id: i128) -> Result<Self, String> {
+        match id {
+            0 => Ok(Tile::Empty),
+            1 => Ok(Tile::Wall),
+            2 => Ok(Tile::Block),
+            3 => Ok(Tile::Paddle),
+            4 => Ok(Tile::Ball),
+            _ => Err(format!("Unexpected tile id {}", id)),
+        }
+    }
+}
+
+impl Tile {
+    fn repr(self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '#',
+            Tile::Block => '=',
+            Tile::Paddle => '_',
+            Tile::Ball => 'o',
+        }
+    }
+}
+
+/// What the arcade has drawn so far, along with the score segment.
+#[derive(Debug, Default)]
+pub struct Screen {
+    tiles: HashMap<(i128, i128), Tile>,
+    score: i128,
+}
+
+impl Screen {
+    // Every draw instruction is an x, y pair followed by a tile id. The
+    // position (-1, 0) is reserved for the score instead.
+    fn draw(&mut self, x: i128, y: i128, value: i128) -> Result<(), String> {
+        if (x, y) == (-1, 0) {
+            self.score = value;
+        } else {
+            let tile = Tile::try_from(value).map_err(|e| format!("{} at ({}, {})", e, x, y))?;
+            self.tiles.insert((x, y), tile);
+        }
+        Ok(())
+    }
+
+    pub fn score(&self) -> i128 {
+        self.score
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.values().filter(|&&t| t == tile).count()
+    }
+
+    fn find(&self, tile: Tile) -> Option<i128> {
+        self.tiles
+            .iter()
+            .find(|(_, &t)| t == tile)
+            .map(|(&(x, _), _)| x)
+    }
+
+    pub fn render(&self) -> String {
+        let width = self.tiles.keys().map(|&(x, _)| x).max().unwrap_or(-1) + 1;
+        let height = self.tiles.keys().map(|&(_, y)| y).max().unwrap_or(-1) + 1;
+        let mut screen = format!("Score: {}\n", self.score);
+        for y in 0..height {
+            let row = (0..width)
+                .map(|x| {
+                    self.tiles
+                        .get(&(x, y))
+                        .copied()
+                        .unwrap_or(Tile::Empty)
+                        .repr()
+                })
+                .collect::<String>();
+            screen += row.trim_end();
+            screen.push('\n');
+        }
+        screen
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Player {
+    /// Keep the paddle under the ball until every block is gone
+    Autopilot,
+    /// Draw the screen and ask which way to move on every frame
+    Manual,
+}
+
+impl Player {
+    fn joystick(self, screen: &Screen) -> i128 {
+        match self {
+            Player::Autopilot => match (screen.find(Tile::Ball), screen.find(Tile::Paddle)) {
+                (Some(ball), Some(paddle)) => (ball - paddle).signum(),
+                _ => 0,
+            },
+            Player::Manual => {
+                print!(
+                    "{}Move (a = left, d = right, enter = stay): ",
+                    screen.render()
+                );
+                io::stdout().flush().expect("Failed to flush stdout");
+                let mut buffer = String::new();
+                io::stdin()
+                    .lock()
+                    .read_line(&mut buffer)
+                    .expect("failed to read from stdin");
+                match buffer.trim() {
+                    "a" => -1,
+                    "d" => 1,
+                    _ => 0,
+                }
+            }
+        }
+    }
+}
+
+// Connects the arcade program to its screen and joystick
+struct Cabinet {
+    screen: RefCell<Screen>,
+    pending: RefCell<Vec<i128>>,
+    player: Player,
+    // Anything the program drew that the screen can't show, for `run` to
+    // stop the program on
+    error: RefCell<Option<String>>,
+}
+
+impl Io for Cabinet {
+    fn read(&self) -> String {
+        self.player.joystick(&self.screen.borrow()).to_string()
+    }
+
+    fn write(&self, output: &str) {
+        let mut pending = self.pending.borrow_mut();
+        pending.push(
+            output
+                .trim()
+                .parse()
+                .expect("Arcade output should be a number"),
+        );
+        if let [x, y, value] = pending[..] {
+            if let Err(error) = self.screen.borrow_mut().draw(x, y, value) {
+                self.error.borrow_mut().get_or_insert(error);
+            }
+            pending.clear();
+        }
+    }
+}
+
+fn run(mem: &[i128], quarters: Option<i128>, player: Player) -> Result<Screen, String> {
+    let mut mem = mem.to_vec();
+    if let Some(quarters) = quarters {
+        mem[0] = quarters;
+    }
+
+    let cabinet = Cabinet {
+        screen: RefCell::new(Screen::default()),
+        pending: RefCell::new(vec![]),
+        player,
+        error: RefCell::new(None),
+    };
+    let mut program = Program::new(&mut mem, &cabinet);
+    while program.step() {
+        if let Some(error) = cabinet.error.borrow_mut().take() {
+            return Err(error);
+        }
+    }
+    Ok(cabinet.screen.into_inner())
+}
+
+/// Run the arcade without any quarters, which just draws the first screen.
+pub fn draw(mem: &[i128]) -> Result<Screen, String> {
+    run(mem, None, Player::Autopilot)
+}
+
+/// Insert quarters and play until the game ends.
+pub fn play(mem: &[i128], player: Player) -> Result<Screen, String> {
+    run(mem, Some(2), player)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse_str(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        Ok(draw(mem)?.count(Tile::Block).to_string())
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        let screen = play(mem, Player::Autopilot)?;
+        match screen.count(Tile::Block) {
+            0 => Ok(screen.score().to_string()),
+            left => Err(format!("Game over with {} blocks left", left)),
+        }
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 13, solution: Day13);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cabinet(player: Player) -> Cabinet {
+        Cabinet {
+            screen: RefCell::new(Screen::default()),
+            pending: RefCell::new(vec![]),
+            player,
+            error: RefCell::new(None),
+        }
+    }
+
+    fn write_all(cabinet: &Cabinet, values: &[i128]) {
+        for value in values {
+            cabinet.write(&value.to_string());
+        }
+    }
+
+    #[test]
+    fn draws_tiles() {
+        let cabinet = cabinet(Player::Autopilot);
+        write_all(&cabinet, &[1, 2, 3, 6, 5, 4]);
+        let screen = cabinet.screen.into_inner();
+        assert_eq!(screen.tiles.get(&(1, 2)), Some(&Tile::Paddle));
+        assert_eq!(screen.tiles.get(&(6, 5)), Some(&Tile::Ball));
+        assert_eq!(screen.count(Tile::Block), 0);
+    }
+
+    #[test]
+    fn waits_for_complete_instructions() {
+        let cabinet = cabinet(Player::Autopilot);
+        write_all(&cabinet, &[1, 2]);
+        assert!(cabinet.screen.borrow().tiles.is_empty());
+        write_all(&cabinet, &[2]);
+        assert_eq!(cabinet.screen.borrow().count(Tile::Block), 1);
+    }
+
+    #[test]
+    fn updates_score() {
+        let cabinet = cabinet(Player::Autopilot);
+        write_all(&cabinet, &[-1, 0, 12345]);
+        let screen = cabinet.screen.into_inner();
+        assert_eq!(screen.score(), 12345);
+        assert!(screen.tiles.is_empty());
+    }
+
+    #[test]
+    fn autopilot_follows_ball() {
+        let cabinet = cabinet(Player::Autopilot);
+        write_all(&cabinet, &[3, 20, 3, 5, 10, 4]);
+        assert_eq!(cabinet.read(), "1");
+        write_all(&cabinet, &[5, 10, 0, 1, 11, 4]);
+        assert_eq!(cabinet.read(), "-1");
+        write_all(&cabinet, &[1, 11, 0, 3, 12, 4]);
+        assert_eq!(cabinet.read(), "0");
+    }
+
+    #[test]
+    fn renders_screen() {
+        let cabinet = cabinet(Player::Autopilot);
+        #[rustfmt::skip]
+        write_all(&cabinet, &[
+            0, 0, 1, 1, 0, 1, 2, 0, 1,
+            0, 1, 1, 1, 1, 4, 2, 1, 2,
+            0, 2, 1, 1, 2, 3, 2, 2, 1,
+            -1, 0, 7,
+        ]);
+        assert_eq!(
+            cabinet.screen.into_inner().render(),
+            "Score: 7\n###\n#o=\n#_#\n"
+        );
+    }
+
+    #[test]
+    fn plays_program() {
+        // Draw a block at (0, 0), a score of 10, then exit
+        let mem = vec![104, 0, 104, 0, 104, 2, 104, -1, 104, 0, 104, 10, 99];
+        let screen = draw(&mem).unwrap();
+        assert_eq!(screen.count(Tile::Block), 1);
+        assert_eq!(screen.score(), 10);
+    }
+
+    #[test]
+    fn rejects_unknown_tiles() {
+        assert_eq!(Tile::try_from(4), Ok(Tile::Ball));
+        assert_eq!(Tile::try_from(5), Err("Unexpected tile id 5".to_owned()));
+        // Draw a block, then a tile that doesn't exist, then loop forever
+        let mem = vec![104, 0, 104, 0, 104, 2, 104, 3, 104, 1, 104, 7, 1105, 1, 12];
+        assert_eq!(draw(&mem).unwrap_err(), "Unexpected tile id 7 at (3, 1)");
+    }
+}
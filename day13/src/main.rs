@@ -0,0 +1,20 @@
+use aoc_runner::Solution;
+use day13::{play, Day13, Player};
+use std::{env, fs};
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day13::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let mem = Day13::parse(&input)?;
+
+    // `cargo run --release -p day13 -- play` to play the game yourself
+    if env::args().nth(1).as_deref() == Some("play") {
+        let screen = play(&mem, Player::Manual)?;
+        print!("{}Game over!", screen.render());
+        return Ok(());
+    }
+
+    println!("Block tiles: {}", Day13::part1(&mem)?);
+    println!("Final score: {}", Day13::part2(&mem)?);
+    Ok(())
+}
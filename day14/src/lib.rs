@@ -0,0 +1,259 @@
+use aoc_runner::Solution;
+use std::collections::{HashMap, HashSet};
+
+const ORE: &str = "ORE";
+const FUEL: &str = "FUEL";
+
+#[derive(Debug)]
+struct Reaction {
+    output: u64,
+    inputs: Vec<(String, u64)>,
+}
+
+/// Every reaction the nanofactory knows, keyed by the chemical it makes.
+#[derive(Debug)]
+pub struct Nanofactory {
+    reactions: HashMap<String, Reaction>,
+    // Chemicals ordered so each one comes before everything it's made
+    // from, starting with FUEL
+    order: Vec<String>,
+}
+
+impl Nanofactory {
+    fn new(reactions: HashMap<String, Reaction>) -> Result<Self, String> {
+        fn visit<'a>(
+            chemical: &'a str,
+            reactions: &'a HashMap<String, Reaction>,
+            visiting: &mut HashSet<&'a str>,
+            visited: &mut HashSet<&'a str>,
+            order: &mut Vec<String>,
+        ) -> Result<(), String> {
+            if chemical == ORE || visited.contains(chemical) {
+                return Ok(());
+            }
+            if !visiting.insert(chemical) {
+                return Err(format!("{} is needed to make itself", chemical));
+            }
+
+            let reaction = reactions
+                .get(chemical)
+                .ok_or_else(|| format!("No reaction makes {}", chemical))?;
+            for (input, _) in &reaction.inputs {
+                visit(input, reactions, visiting, visited, order)?;
+            }
+
+            visiting.remove(chemical);
+            visited.insert(chemical);
+            order.push(chemical.to_owned());
+            Ok(())
+        }
+
+        let mut order = vec![];
+        visit(
+            FUEL,
+            &reactions,
+            &mut HashSet::new(),
+            &mut HashSet::new(),
+            &mut order,
+        )?;
+        order.reverse();
+        Ok(Self { reactions, order })
+    }
+
+    /// The least ORE needed to make the requested amount of FUEL.
+    pub fn ore_for(&self, fuel: u64) -> u64 {
+        // Since every chemical is visited after everything that consumes
+        // it, its total demand is known by then. Any surplus from rounding
+        // up to whole reactions is simply never used.
+        let mut needed = HashMap::new();
+        needed.insert(FUEL, fuel);
+        for chemical in &self.order {
+            let amount = needed.get(chemical.as_str()).copied().unwrap_or(0);
+            let reaction = &self.reactions[chemical];
+            let runs = amount.div_ceil(reaction.output);
+            for (input, quantity) in &reaction.inputs {
+                *needed.entry(input.as_str()).or_insert(0) += runs * quantity;
+            }
+        }
+
+        needed.get(ORE).copied().unwrap_or(0)
+    }
+
+    /// The most FUEL that can be made with the given ORE.
+    pub fn max_fuel(&self, ore: u64) -> u64 {
+        let per_fuel = self.ore_for(1);
+        if per_fuel == 0 || per_fuel > ore {
+            return 0;
+        }
+
+        // Leftovers make every extra FUEL cheaper, so one unit's cost gives
+        // a lower bound. Double it to find an upper bound, then narrow in.
+        let mut low = ore / per_fuel;
+        let mut high = low * 2;
+        while self.ore_for(high) <= ore {
+            low = high;
+            high *= 2;
+        }
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.ore_for(mid) <= ore {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        low
+    }
+}
+
+fn parse_chemical(s: &str) -> Result<(String, u64), String> {
+    let mut parts = s.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(quantity), Some(name), None) => {
+            let quantity = quantity
+                .parse()
+                .map_err(|e| format!("Error parsing {}: {}", quantity, e))?;
+            Ok((name.to_owned(), quantity))
+        }
+        _ => Err(format!("Expected a quantity and a chemical, got {}", s)),
+    }
+}
+
+/// Parse one reaction per line, e.g. `7 A, 1 B => 1 C`.
+pub fn parse(input: &str) -> Result<Nanofactory, String> {
+    let mut reactions = HashMap::new();
+    for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let mut sides = line.split("=>");
+        let (inputs, output) = match (sides.next(), sides.next(), sides.next()) {
+            (Some(inputs), Some(output), None) => (inputs, output),
+            _ => return Err(format!("Expected a reaction like 1 A => 1 B, got {}", line)),
+        };
+
+        let inputs = inputs
+            .split(',')
+            .map(parse_chemical)
+            .collect::<Result<Vec<_>, _>>()?;
+        let (name, output) = parse_chemical(output)?;
+        if output == 0 {
+            return Err(format!("{} never produces anything", line));
+        }
+        if reactions
+            .insert(name.clone(), Reaction { output, inputs })
+            .is_some()
+        {
+            return Err(format!("More than one reaction makes {}", name));
+        }
+    }
+
+    Nanofactory::new(reactions)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Nanofactory;
+
+    fn parse(input: &str) -> Result<Nanofactory, String> {
+        parse(input)
+    }
+
+    fn part1(factory: &Nanofactory) -> Result<String, String> {
+        Ok(factory.ore_for(1).to_string())
+    }
+
+    fn part2(factory: &Nanofactory) -> Result<String, String> {
+        Ok(factory.max_fuel(1_000_000_000_000).to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 14, solution: Day14);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = "10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL";
+
+    const EXAMPLE_2: &str = "9 ORE => 2 A
+8 ORE => 3 B
+7 ORE => 5 C
+3 A, 4 B => 1 AB
+5 B, 7 C => 1 BC
+4 C, 1 A => 1 CA
+2 AB, 3 BC, 4 CA => 1 FUEL";
+
+    const EXAMPLE_3: &str = "157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT";
+
+    const EXAMPLE_4: &str = "2 VPVL, 7 FWMGM, 2 CXFTF, 11 MNCFX => 1 STKFG
+17 NVRVD, 3 JNWZP => 8 VPVL
+53 STKFG, 6 MNCFX, 46 VJHF, 81 HVMC, 68 CXFTF, 25 GNMV => 1 FUEL
+22 VJHF, 37 MNCFX => 5 FWMGM
+139 ORE => 4 NVRVD
+144 ORE => 7 JNWZP
+5 MNCFX, 7 RFSQX, 2 FWMGM, 2 VPVL, 19 CXFTF => 3 HVMC
+5 VJHF, 7 MNCFX, 9 VPVL, 37 CXFTF => 6 GNMV
+145 ORE => 6 MNCFX
+1 NVRVD => 8 CXFTF
+1 VJHF, 6 MNCFX => 4 RFSQX
+176 ORE => 6 VJHF";
+
+    const EXAMPLE_5: &str = "171 ORE => 8 CNZTR
+7 ZLQW, 3 BMBT, 9 XCVML, 26 XMNCP, 1 WPTQ, 2 MZWV, 1 RJRHP => 4 PLWSL
+114 ORE => 4 BHXH
+14 VRPVC => 6 BMBT
+6 BHXH, 18 KTJDG, 12 WPTQ, 7 PLWSL, 31 FHTLT, 37 ZDVW => 1 FUEL
+6 WPTQ, 2 BMBT, 8 ZLQW, 18 KTJDG, 1 XMNCP, 6 MZWV, 1 RJRHP => 6 FHTLT
+15 XDBXC, 2 LTCX, 1 VRPVC => 6 ZLQW
+13 WPTQ, 10 LTCX, 3 RJRHP, 14 XMNCP, 2 MZWV, 1 ZLQW => 1 ZDVW
+5 BMBT => 4 WPTQ
+189 ORE => 9 KTJDG
+1 MZWV, 17 XDBXC, 3 XCVML => 2 XMNCP
+12 VRPVC, 27 CNZTR => 2 XDBXC
+15 KTJDG, 12 BHXH => 5 XCVML
+3 BHXH, 2 VRPVC => 7 MZWV
+121 ORE => 7 VRPVC
+7 XCVML => 6 RJRHP
+5 BHXH, 4 VRPVC => 5 LTCX";
+
+    #[test]
+    fn ore_for_one_fuel() {
+        assert_eq!(parse(EXAMPLE_1).unwrap().ore_for(1), 31);
+        assert_eq!(parse(EXAMPLE_2).unwrap().ore_for(1), 165);
+        assert_eq!(parse(EXAMPLE_3).unwrap().ore_for(1), 13_312);
+        assert_eq!(parse(EXAMPLE_4).unwrap().ore_for(1), 180_697);
+        assert_eq!(parse(EXAMPLE_5).unwrap().ore_for(1), 2_210_736);
+    }
+
+    #[test]
+    fn fuel_for_a_trillion_ore() {
+        let ore = 1_000_000_000_000;
+        assert_eq!(parse(EXAMPLE_3).unwrap().max_fuel(ore), 82_892_753);
+        assert_eq!(parse(EXAMPLE_4).unwrap().max_fuel(ore), 5_586_022);
+        assert_eq!(parse(EXAMPLE_5).unwrap().max_fuel(ore), 460_664);
+    }
+
+    #[test]
+    fn rejects_bad_reactions() {
+        assert!(parse("1 ORE => 1 A").is_err());
+        assert!(parse("1 A => 1 FUEL").is_err());
+        assert!(parse("1 FUEL => 1 A\n1 A => 1 FUEL").is_err());
+        assert!(parse("1 ORE => 1 FUEL\n2 ORE => 1 FUEL").is_err());
+        assert!(parse("1 ORE -> 1 FUEL").is_err());
+        assert!(parse("ORE => 1 FUEL").is_err());
+        assert!(parse("1 ORE => 0 FUEL").is_err());
+    }
+}
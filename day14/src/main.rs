@@ -0,0 +1,12 @@
+use aoc_runner::Solution;
+use day14::Day14;
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day14::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let factory = Day14::parse(&input)?;
+    println!("Ore for 1 fuel: {}", Day14::part1(&factory)?);
+    println!("Fuel from a trillion ore: {}", Day14::part2(&factory)?);
+    Ok(())
+}
@@ -0,0 +1,346 @@
+use aoc_runner::Solution;
+use geometry::{Direction, Turn};
+use int_code_emulator::{parse_str, AsciiIo, Program};
+use std::fmt::{self, Display, Formatter};
+
+// The camera draws the robot as the way it's facing
+fn facing(c: u8) -> Option<Direction> {
+    match c {
+        b'^' => Some(Direction::Up),
+        b'>' => Some(Direction::Right),
+        b'v' => Some(Direction::Down),
+        b'<' => Some(Direction::Left),
+        _ => None,
+    }
+}
+
+/// One instruction for the vacuum robot.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Step {
+    Left,
+    Right,
+    Forward(usize),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Left => write!(f, "L"),
+            Step::Right => write!(f, "R"),
+            Step::Forward(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+fn render(steps: &[Step]) -> String {
+    steps
+        .iter()
+        .map(Step::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The robot's memory only fits this many characters per line.
+const MAX_LINE: usize = 20;
+
+/// A movement routine: the main routine calls the functions A, B and C by
+/// index.
+#[derive(Debug, PartialEq)]
+pub struct Routine {
+    pub main: Vec<usize>,
+    pub functions: Vec<Vec<Step>>,
+}
+
+impl Routine {
+    /// Compress a path into at most three functions, each short enough to
+    /// fit in the robot's memory.
+    pub fn compress(path: &[Step]) -> Option<Self> {
+        fn search<'a>(
+            path: &'a [Step],
+            functions: &mut Vec<&'a [Step]>,
+            main: &mut Vec<usize>,
+        ) -> bool {
+            if path.is_empty() {
+                return true;
+            }
+            // "A,B,..." only fits half as many calls as characters
+            if main.len() == MAX_LINE.div_ceil(2) {
+                return false;
+            }
+
+            for i in 0..functions.len() {
+                if path.starts_with(functions[i]) {
+                    main.push(i);
+                    if search(&path[functions[i].len()..], functions, main) {
+                        return true;
+                    }
+                    main.pop();
+                }
+            }
+
+            if functions.len() < 3 {
+                for len in 1..=path.len() {
+                    if render(&path[..len]).len() > MAX_LINE {
+                        break;
+                    }
+
+                    functions.push(&path[..len]);
+                    main.push(functions.len() - 1);
+                    if search(&path[len..], functions, main) {
+                        return true;
+                    }
+                    main.pop();
+                    functions.pop();
+                }
+            }
+
+            false
+        }
+
+        let mut functions = vec![];
+        let mut main = vec![];
+        if !search(path, &mut functions, &mut main) {
+            return None;
+        }
+
+        Some(Self {
+            main,
+            functions: functions.into_iter().map(<[Step]>::to_vec).collect(),
+        })
+    }
+
+    /// Expand the routine back into the path it follows.
+    pub fn expand(&self) -> Vec<Step> {
+        self.main
+            .iter()
+            .flat_map(|&i| self.functions[i].iter().copied())
+            .collect()
+    }
+
+    /// The routine as the robot expects to be told it, declining the
+    /// continuous video feed.
+    pub fn to_input(&self) -> String {
+        let main = self
+            .main
+            .iter()
+            .map(|&i| ((b'A' + i as u8) as char).to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut input = main + "\n";
+        for i in 0..3 {
+            input += &self.functions.get(i).map(|f| render(f)).unwrap_or_default();
+            input.push('\n');
+        }
+        input + "n\n"
+    }
+}
+
+/// The view from the ASCII cameras.
+#[derive(Debug)]
+pub struct Scaffold {
+    grid: Vec<Vec<u8>>,
+}
+
+impl Scaffold {
+    pub fn new(camera: &str) -> Self {
+        Self {
+            grid: camera
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(|l| l.bytes().collect())
+                .collect(),
+        }
+    }
+
+    fn at(&self, (x, y): (isize, isize)) -> Option<u8> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        self.grid
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+    }
+
+    // A robot is always standing on scaffolding, unless it's tumbling
+    // through space (X)
+    fn is_scaffold(&self, pos: (isize, isize)) -> bool {
+        match self.at(pos) {
+            Some(c) => c == b'#' || facing(c).is_some(),
+            None => false,
+        }
+    }
+
+    /// Sum the alignment parameters of every scaffold intersection.
+    pub fn alignment(&self) -> usize {
+        let mut sum = 0;
+        for (y, row) in self.grid.iter().enumerate() {
+            for x in 0..row.len() {
+                let pos = (x as isize, y as isize);
+                let is_intersection = self.is_scaffold(pos)
+                    && Direction::ALL.iter().all(|d| self.is_scaffold(d.next(pos)));
+                if is_intersection {
+                    sum += x * y;
+                }
+            }
+        }
+        sum
+    }
+
+    fn robot(&self) -> Option<((isize, isize), Direction)> {
+        self.grid.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .find_map(|(x, &c)| facing(c).map(|d| ((x as isize, y as isize), d)))
+        })
+    }
+
+    /// Follow the scaffold from the robot to its far end, going straight
+    /// through intersections.
+    pub fn path(&self) -> Vec<Step> {
+        let (mut pos, mut direction) = match self.robot() {
+            Some(robot) => robot,
+            None => return vec![],
+        };
+
+        let mut path = vec![];
+        loop {
+            if self.is_scaffold(direction.next(pos)) {
+                let mut steps = 0;
+                while self.is_scaffold(direction.next(pos)) {
+                    pos = direction.next(pos);
+                    steps += 1;
+                }
+                path.push(Step::Forward(steps));
+            } else if self.is_scaffold(direction.turn(Turn::Left).next(pos)) {
+                direction = direction.turn(Turn::Left);
+                path.push(Step::Left);
+            } else if self.is_scaffold(direction.turn(Turn::Right).next(pos)) {
+                direction = direction.turn(Turn::Right);
+                path.push(Step::Right);
+            } else {
+                return path;
+            }
+        }
+    }
+}
+
+/// Run the camera program and return what it sees.
+pub fn camera(mem: &[i128]) -> Scaffold {
+    let io = AsciiIo::new();
+    let mut mem = mem.to_vec();
+    Program::new(&mut mem, &io).run();
+    Scaffold::new(&io.text())
+}
+
+/// Wake the robot, walk it over the whole scaffold and return the dust it
+/// collected.
+pub fn collect_dust(mem: &[i128]) -> Result<i128, String> {
+    let path = camera(mem).path();
+    let routine = Routine::compress(&path)
+        .ok_or_else(|| format!("Could not fit {} in memory", render(&path)))?;
+
+    let io = AsciiIo::with_input(&routine.to_input());
+    let mut mem = mem.to_vec();
+    mem[0] = 2;
+    Program::new(&mut mem, &io).run();
+    io.values()
+        .last()
+        .copied()
+        .ok_or_else(|| format!("The robot didn't report any dust:\n{}", io.text()))
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse_str(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        Ok(camera(mem).alignment().to_string())
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        Ok(collect_dust(mem)?.to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 17, solution: Day17);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INTERSECTIONS: &str = "
+..#..........
+..#..........
+#######...###
+#.#...#...#.#
+#############
+..#...#...#..
+..#####...^..";
+
+    const WALK: &str = "
+#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......";
+
+    #[test]
+    fn alignment() {
+        assert_eq!(Scaffold::new(INTERSECTIONS).alignment(), 76);
+    }
+
+    #[test]
+    fn path() {
+        assert_eq!(
+            render(&Scaffold::new(WALK).path()),
+            "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+        );
+    }
+
+    #[test]
+    fn compress() {
+        let path = Scaffold::new(WALK).path();
+        let routine = Routine::compress(&path).unwrap();
+        assert_eq!(routine.expand(), path);
+        assert!(routine.functions.len() <= 3);
+        for line in routine.to_input().lines() {
+            assert!(line.len() <= MAX_LINE, "{} is too long", line);
+        }
+        assert!(routine.to_input().ends_with("\nn\n"));
+    }
+
+    #[test]
+    fn routine_input() {
+        let routine = Routine {
+            main: vec![0, 1, 0],
+            functions: vec![
+                vec![Step::Right, Step::Forward(8)],
+                vec![Step::Left, Step::Forward(12)],
+            ],
+        };
+        assert_eq!(routine.to_input(), "A,B,A\nR,8\nL,12\n\nn\n");
+    }
+
+    #[test]
+    fn incompressible_paths() {
+        let path = (1..=40).map(Step::Forward).collect::<Vec<_>>();
+        assert_eq!(Routine::compress(&path), None);
+    }
+}
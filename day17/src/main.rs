@@ -0,0 +1,12 @@
+use aoc_runner::Solution;
+use day17::Day17;
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day17::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let mem = Day17::parse(&input)?;
+    println!("Sum of alignment parameters: {}", Day17::part1(&mem)?);
+    println!("Dust collected: {}", Day17::part2(&mem)?);
+    Ok(())
+}
@@ -0,0 +1,354 @@
+use aoc_runner::Solution;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+};
+
+const KEYS: usize = 26;
+
+/// Where a key can be reached from, and which doors are in the way.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Edge {
+    key: usize,
+    distance: usize,
+    doors: u32,
+}
+
+/// A map of the vault, with one or more entrances.
+#[derive(Clone, Debug)]
+pub struct Vault {
+    grid: Vec<Vec<u8>>,
+}
+
+impl Vault {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let grid = input
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(|l| l.bytes().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        for (y, row) in grid.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                match c {
+                    b'#' | b'.' | b'@' | b'a'..=b'z' | b'A'..=b'Z' => {}
+                    _ => return Err(format!("Unexpected {:?} at ({}, {})", c as char, x, y)),
+                }
+            }
+        }
+
+        let vault = Self { grid };
+        if vault.entrances().is_empty() {
+            return Err("The vault has no entrance".to_owned());
+        }
+        Ok(vault)
+    }
+
+    fn entrances(&self) -> Vec<(usize, usize)> {
+        self.find(|c| c == b'@')
+    }
+
+    fn find(&self, f: impl Fn(u8) -> bool) -> Vec<(usize, usize)> {
+        let mut found = vec![];
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if f(c) {
+                    found.push((x, y));
+                }
+            }
+        }
+        found
+    }
+
+    fn at(&self, (x, y): (usize, usize)) -> u8 {
+        self.grid
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(b'#')
+    }
+
+    /// Wall off the middle of the vault so that a single entrance becomes
+    /// four, one per quadrant. Vaults that already have several entrances
+    /// are left as they are.
+    pub fn split(&self) -> Result<Self, String> {
+        let entrances = self.entrances();
+        if entrances.len() > 1 {
+            return Ok(self.clone());
+        }
+
+        let (x, y) = entrances[0];
+        if x == 0 || y == 0 {
+            return Err(format!("The entrance at ({}, {}) is on the edge", x, y));
+        }
+        let mut vault = self.clone();
+        for (dy, row) in ["@#@", "###", "@#@"].iter().enumerate() {
+            for (dx, c) in row.bytes().enumerate() {
+                let (x, y) = (x + dx - 1, y + dy - 1);
+                match vault.grid.get_mut(y).and_then(|row| row.get_mut(x)) {
+                    Some(cell) if *cell == b'.' || *cell == b'@' => *cell = c,
+                    _ => return Err(format!("Can't split the vault at ({}, {})", x, y)),
+                }
+            }
+        }
+        Ok(vault)
+    }
+
+    // Breadth-first search from one spot to every key reachable from it,
+    // noting the doors along the way. Keys don't stop the search: picking
+    // one up on the way to another is the same as going to it first.
+    fn edges_from(&self, start: (usize, usize)) -> Vec<Edge> {
+        let mut edges = vec![];
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert(start);
+        queue.push_back((start, 0, 0));
+        while let Some(((x, y), distance, mut doors)) = queue.pop_front() {
+            match self.at((x, y)) {
+                c @ b'a'..=b'z' if distance > 0 => edges.push(Edge {
+                    key: (c - b'a') as usize,
+                    distance,
+                    doors,
+                }),
+                c @ b'A'..=b'Z' => doors |= 1 << (c - b'A'),
+                _ => {}
+            }
+
+            for next in [
+                (x + 1, y),
+                (x, y + 1),
+                (x.wrapping_sub(1), y),
+                (x, y.wrapping_sub(1)),
+            ] {
+                if self.at(next) != b'#' && seen.insert(next) {
+                    queue.push_back((next, distance + 1, doors));
+                }
+            }
+        }
+        edges
+    }
+
+    /// The fewest steps needed for the robots, one at each entrance, to
+    /// collect every key. Only one robot moves at a time.
+    pub fn collect_keys(&self) -> Result<usize, String> {
+        let keys = self.find(|c| c.is_ascii_lowercase());
+        let all_keys = keys
+            .iter()
+            .fold(0u32, |all, &pos| all | 1 << (self.at(pos) - b'a'));
+
+        // Keys are nodes 0..26 and the entrances come after them
+        let mut graph = vec![vec![]; KEYS];
+        for &pos in &keys {
+            graph[(self.at(pos) - b'a') as usize] = self.edges_from(pos);
+        }
+        let entrances = self.entrances();
+        for &pos in &entrances {
+            graph.push(self.edges_from(pos));
+        }
+
+        let start = (0..entrances.len()).map(|i| KEYS + i).collect::<Vec<_>>();
+        let mut best = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        best.insert((start.clone(), 0), 0);
+        queue.push(Reverse((0, start, 0u32)));
+        while let Some(Reverse((distance, robots, collected))) = queue.pop() {
+            if collected == all_keys {
+                return Ok(distance);
+            }
+            if best
+                .get(&(robots.clone(), collected))
+                .is_some_and(|&d| d < distance)
+            {
+                continue;
+            }
+
+            for (i, &node) in robots.iter().enumerate() {
+                for edge in &graph[node] {
+                    let key = 1 << edge.key;
+                    if collected & key != 0 || edge.doors & !collected != 0 {
+                        continue;
+                    }
+
+                    let mut robots = robots.clone();
+                    robots[i] = edge.key;
+                    let state = (robots, collected | key);
+                    let distance = distance + edge.distance;
+                    if best.get(&state).is_none_or(|&d| distance < d) {
+                        best.insert(state.clone(), distance);
+                        queue.push(Reverse((distance, state.0, state.1)));
+                    }
+                }
+            }
+        }
+
+        Err("Some keys can't be reached".to_owned())
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vault;
+
+    fn parse(input: &str) -> Result<Vault, String> {
+        Vault::parse(input)
+    }
+
+    fn part1(vault: &Vault) -> Result<String, String> {
+        Ok(vault.collect_keys()?.to_string())
+    }
+
+    fn part2(vault: &Vault) -> Result<String, String> {
+        Ok(vault.split()?.collect_keys()?.to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 18, solution: Day18);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn steps(vault: &str) -> usize {
+        Vault::parse(vault).unwrap().collect_keys().unwrap()
+    }
+
+    fn split_steps(vault: &str) -> usize {
+        Vault::parse(vault)
+            .unwrap()
+            .split()
+            .unwrap()
+            .collect_keys()
+            .unwrap()
+    }
+
+    #[test]
+    fn one_robot() {
+        assert_eq!(
+            steps(
+                "
+#########
+#b.A.@.a#
+#########"
+            ),
+            8
+        );
+        assert_eq!(
+            steps(
+                "
+########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################"
+            ),
+            86
+        );
+        assert_eq!(
+            steps(
+                "
+########################
+#...............b.C.D.f#
+#.######################
+#.....@.a.B.c.d.A.e.F.g#
+########################"
+            ),
+            132
+        );
+        assert_eq!(
+            steps(
+                "
+#################
+#i.G..c...e..H.p#
+########.########
+#j.A..b...f..D.o#
+########@########
+#k.E..a...g..B.n#
+########.########
+#l.F..d...h..C.m#
+#################"
+            ),
+            136
+        );
+        assert_eq!(
+            steps(
+                "
+########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################"
+            ),
+            81
+        );
+    }
+
+    #[test]
+    fn four_robots() {
+        assert_eq!(
+            split_steps(
+                "
+#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######"
+            ),
+            8
+        );
+        assert_eq!(
+            split_steps(
+                "
+###############
+#d.ABC.#.....a#
+######@#@######
+###############
+######@#@######
+#b.....#.....c#
+###############"
+            ),
+            24
+        );
+        assert_eq!(
+            split_steps(
+                "
+#############
+#DcBa.#.GhKl#
+#.###@#@#I###
+#e#d#####j#k#
+###C#@#@###J#
+#fEbA.#.FgHi#
+#############"
+            ),
+            32
+        );
+        assert_eq!(
+            split_steps(
+                "
+#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba...BcIJ#
+#####.@.#####
+#nK.L...G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############"
+            ),
+            72
+        );
+    }
+
+    #[test]
+    fn rejects_bad_vaults() {
+        assert!(Vault::parse("#a.#").is_err());
+        assert!(Vault::parse("#a?@#").is_err());
+        assert_eq!(
+            Vault::parse("#@.A.a#").unwrap().collect_keys(),
+            Err("Some keys can't be reached".to_owned())
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use aoc_runner::Solution;
+use day18::Day18;
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day18::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let vault = Day18::parse(&input)?;
+    println!("Steps to collect every key: {}", Day18::part1(&vault)?);
+    println!("Steps with four robots: {}", Day18::part2(&vault)?);
+    Ok(())
+}
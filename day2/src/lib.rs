@@ -0,0 +1,306 @@
+use aoc_runner::Solution;
+
+// Models the possible commands available to this "machine"
+enum OpCode {
+    Add { r1: usize, r2: usize, o: usize },
+    Mul { r1: usize, r2: usize, o: usize },
+    Exit,
+}
+
+impl OpCode {
+    /// Generate an OpCode from a specific region of memory
+    fn from(mem: &[usize], idx: usize) -> Self {
+        let op_code = mem[idx];
+        match op_code {
+            1 => OpCode::Add {
+                r1: mem[idx + 1],
+                r2: mem[idx + 2],
+                o: mem[idx + 3],
+            },
+            2 => OpCode::Mul {
+                r1: mem[idx + 1],
+                r2: mem[idx + 2],
+                o: mem[idx + 3],
+            },
+            99 => OpCode::Exit,
+            _ => panic!("Unexpected opcode: {}", op_code),
+        }
+    }
+
+    // Execute the OpCode against the passed in memory
+    fn exec(self, mem: &mut [usize]) -> bool {
+        match self {
+            OpCode::Add { r1, r2, o } => {
+                mem[o] = mem[r1] + mem[r2];
+                true
+            }
+            OpCode::Mul { r1, r2, o } => {
+                mem[o] = mem[r1] * mem[r2];
+                true
+            }
+            OpCode::Exit => false,
+        }
+    }
+}
+
+// Models a simple machine with memory and a program counter
+struct Program<'a> {
+    mem: &'a mut [usize],
+    ctr: usize,
+}
+
+impl<'a> Program<'a> {
+    fn new(mem: &'a mut [usize]) -> Self {
+        Program { mem, ctr: 0 }
+    }
+
+    fn noun(&mut self, noun: usize) {
+        self.mem[1] = noun;
+    }
+
+    fn verb(&mut self, verb: usize) {
+        self.mem[2] = verb;
+    }
+
+    // Process the op codes in memory until an exit opcode is reached
+    fn run(mut self) {
+        loop {
+            let op_code = OpCode::from(self.mem, self.ctr);
+            if !op_code.exec(self.mem) {
+                break;
+            }
+
+            self.ctr += 4;
+        }
+    }
+}
+
+pub const WANTED_RESULT: usize = 19_690_720;
+
+/// Run a copy of the program with the given noun and verb, returning what
+/// ends up at address 0.
+pub fn output(mem: &[usize], noun: usize, verb: usize) -> usize {
+    let mut mem = mem.to_vec();
+    let mut program = Program::new(&mut mem);
+    program.noun(noun);
+    program.verb(verb);
+    program.run();
+    mem[0]
+}
+
+// Every noun and verb, in the order they're tried
+fn pairs() -> impl Iterator<Item = (usize, usize)> {
+    (0..100).flat_map(|noun| (0..100).map(move |verb| (noun, verb)))
+}
+
+/// Restore the program to its "1202 program alarm" state and run it.
+pub fn restore_gravity_assist(mem: &[usize]) -> usize {
+    output(mem, 12, 2)
+}
+
+/// Find the first noun and verb that produce the target, solving directly
+/// when the program allows it.
+pub fn find_noun_verb(mem: &[usize], target: usize) -> Option<(usize, usize)> {
+    solve_linear(mem, target).or_else(|| brute_force(mem, target))
+}
+
+/// Try every noun and verb until one produces the target.
+pub fn brute_force(mem: &[usize], target: usize) -> Option<(usize, usize)> {
+    pairs().find(|&(noun, verb)| output(mem, noun, verb) == target)
+}
+
+/// The output as `constant + a * noun + b * verb`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Affine {
+    constant: i128,
+    a: i128,
+    b: i128,
+}
+
+impl Affine {
+    // The gravity assist program's output is affine in the noun and verb,
+    // so three runs are enough to find its coefficients. A few more check
+    // that the program really is affine.
+    fn fit(mem: &[usize]) -> Option<Self> {
+        let f = |noun, verb| output(mem, noun, verb) as i128;
+        let constant = f(0, 0);
+        let affine = Self {
+            constant,
+            a: f(1, 0) - constant,
+            b: f(0, 1) - constant,
+        };
+        let fits = [(99, 99), (37, 58), (64, 3)]
+            .iter()
+            .all(|&(noun, verb)| f(noun, verb) == affine.at(noun, verb));
+        Some(affine).filter(|_| fits)
+    }
+
+    fn at(&self, noun: usize, verb: usize) -> i128 {
+        self.constant + self.a * noun as i128 + self.b * verb as i128
+    }
+
+    // Solve for the verb given each noun in turn, keeping only the answers
+    // the program really gives, in case it only looked affine
+    fn checked_solutions<'a>(
+        self,
+        mem: &'a [usize],
+        target: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.solutions(target)
+            .filter(move |&(noun, verb)| output(mem, noun, verb) == target)
+    }
+
+    fn solutions(self, target: usize) -> impl Iterator<Item = (usize, usize)> {
+        let remaining = target as i128 - self.constant;
+        (0..100usize).flat_map(move |noun| {
+            let rest = remaining - self.a * noun as i128;
+            let verbs = match self.b {
+                // Without a verb term, any verb works as long as the noun does
+                0 if rest == 0 => 0..100,
+                0 => 0..0,
+                b if rest % b == 0 && (0..100).contains(&(rest / b)) => {
+                    let verb = (rest / b) as usize;
+                    verb..verb + 1
+                }
+                _ => 0..0,
+            };
+            verbs.map(move |verb| (noun, verb))
+        })
+    }
+}
+
+// Solve for the target directly, giving up on programs that aren't affine
+fn solve_linear(mem: &[usize], target: usize) -> Option<(usize, usize)> {
+    Affine::fit(mem)?.checked_solutions(mem, target).next()
+}
+
+/// Every noun and verb that produce the target, rather than just the first.
+pub fn all_solutions(mem: &[usize], target: usize) -> Vec<(usize, usize)> {
+    match Affine::fit(mem) {
+        Some(affine) => affine.checked_solutions(mem, target).collect(),
+        None => pairs()
+            .filter(|&(noun, verb)| output(mem, noun, verb) == target)
+            .collect(),
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(mem: &Vec<usize>) -> Result<String, String> {
+        Ok(restore_gravity_assist(mem).to_string())
+    }
+
+    fn part2(mem: &Vec<usize>) -> Result<String, String> {
+        find_noun_verb(mem, WANTED_RESULT)
+            .map(|(noun, verb)| (100 * noun + verb).to_string())
+            .ok_or_else(|| format!("No noun and verb produce {}", WANTED_RESULT))
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 2, solution: Day2);
+
+pub fn parse(input: &str) -> Vec<usize> {
+    input
+        .split(',')
+        .filter_map(|s| s.trim().parse::<usize>().ok())
+        .collect::<Vec<_>>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        validate_program(&mut [1, 0, 0, 0, 99], &[2, 0, 0, 0, 99]);
+        validate_program(&mut [2, 3, 0, 3, 99], &[2, 3, 0, 6, 99]);
+        validate_program(&mut [2, 4, 4, 5, 99, 0], &[2, 4, 4, 5, 99, 9801]);
+        validate_program(
+            &mut [1, 1, 1, 4, 99, 5, 6, 0, 99],
+            &[30, 1, 1, 4, 2, 5, 6, 0, 99],
+        );
+    }
+
+    // Padded out so any noun or verb is a valid address
+    fn padded(program: &[usize]) -> Vec<usize> {
+        let mut mem = program.to_vec();
+        mem.resize(100, 0);
+        mem
+    }
+
+    #[test]
+    fn test_solve_linear() {
+        // mem[0] = 100 * noun + verb + 7
+        let mem = padded(&[
+            1, 0, 0, 3, 2, 1, 20, 19, 1, 19, 2, 0, 1, 0, 21, 0, 99, 0, 0, 0, 100, 7,
+        ]);
+        assert_eq!(output(&mem, 42, 10), 4217);
+        assert_eq!(solve_linear(&mem, 4217), Some((42, 10)));
+        assert_eq!(brute_force(&mem, 4217), Some((42, 10)));
+        assert_eq!(solve_linear(&mem, 3), None);
+    }
+
+    #[test]
+    fn test_solve_nonlinear() {
+        // mem[0] = noun * verb
+        let mem = padded(&[1, 0, 0, 3, 2, 1, 2, 0, 99]);
+        assert_eq!(solve_linear(&mem, 2436), None);
+        assert_eq!(brute_force(&mem, 2436), Some((28, 87)));
+        assert_eq!(find_noun_verb(&mem, 2436), Some((28, 87)));
+        assert_eq!(find_noun_verb(&mem, 9999), None);
+    }
+
+    #[test]
+    fn test_restore_gravity_assist() {
+        // mem[0] = noun * verb
+        let mem = padded(&[1, 0, 0, 3, 2, 1, 2, 0, 99]);
+        assert_eq!(restore_gravity_assist(&mem), 24);
+    }
+
+    #[test]
+    fn test_all_solutions() {
+        // mem[0] = 3 * noun + verb
+        let mem = padded(&[1, 0, 0, 3, 2, 1, 14, 13, 1, 13, 2, 0, 99, 0, 3]);
+        let solutions = all_solutions(&mem, 200);
+        assert_eq!(solutions.first(), Some(&(34, 98)));
+        assert_eq!(solutions.last(), Some(&(66, 2)));
+        assert_eq!(solutions.len(), 33);
+        assert!(solutions
+            .iter()
+            .all(|&(noun, verb)| output(&mem, noun, verb) == 200));
+
+        let mem = padded(&[1, 0, 0, 3, 2, 1, 2, 0, 99]);
+        assert_eq!(
+            all_solutions(&mem, 2436),
+            vec![(28, 87), (29, 84), (42, 58), (58, 42), (84, 29), (87, 28)]
+        );
+
+        // Fitting a line through a program that isn't one only lists the
+        // pairs that really work
+        let wrong = Affine {
+            constant: 0,
+            a: 1,
+            b: 0,
+        };
+        assert_eq!(wrong.solutions(28).count(), 100);
+        assert_eq!(
+            wrong.checked_solutions(&mem, 28).collect::<Vec<_>>(),
+            vec![(28, 1)]
+        );
+    }
+
+    fn validate_program(input: &mut [usize], expected: &[usize]) {
+        let program = Program::new(input);
+        program.run();
+        for (l, r) in input.iter().zip(expected.iter()) {
+            assert_eq!(l, r);
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use aoc_runner::Solution;
+use int_code_emulator::{parse_str, AsciiIo, Program};
+use std::fmt::{self, Display, Formatter};
+
+/// The droid only has room for this many instructions.
+const MAX_INSTRUCTIONS: usize = 15;
+
+/// Jump when there's a hole in the next three tiles and ground to land on.
+pub const WALK: &str = "\
+NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+WALK
+";
+
+/// As with walking, but only jump if the droid can either step or jump
+/// again once it lands.
+pub const RUN: &str = "\
+NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+NOT E T
+NOT T T
+OR H T
+AND T J
+RUN
+";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+    Walk,
+    Run,
+}
+
+impl Mode {
+    /// How many tiles ahead the droid can see.
+    fn range(self) -> usize {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+
+    fn registers(self) -> Vec<u8> {
+        (b'A'..b'A' + self.range() as u8)
+            .chain(b"TJ".iter().copied())
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Op {
+    And,
+    Or,
+    Not,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Instruction {
+    pub op: Op,
+    pub read: u8,
+    pub write: u8,
+}
+
+/// A springscript program, along with whether the droid walks or runs it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Springscript {
+    pub mode: Mode,
+    pub instructions: Vec<Instruction>,
+}
+
+impl Springscript {
+    pub fn parse(script: &str) -> Result<Self, String> {
+        let mut instructions = vec![];
+        for line in script.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let mode = match line {
+                "WALK" => Some(Mode::Walk),
+                "RUN" => Some(Mode::Run),
+                _ => None,
+            };
+            if let Some(mode) = mode {
+                let script = Self { mode, instructions };
+                script.validate()?;
+                return Ok(script);
+            }
+
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            let (op, read, write) = match parts.as_slice() {
+                [op, read, write] if read.len() == 1 && write.len() == 1 => (op, read, write),
+                _ => return Err(format!("Expected an instruction, found {:?}", line)),
+            };
+            let op = match *op {
+                "AND" => Op::And,
+                "OR" => Op::Or,
+                "NOT" => Op::Not,
+                _ => return Err(format!("Unknown instruction {:?}", op)),
+            };
+            instructions.push(Instruction {
+                op,
+                read: read.as_bytes()[0],
+                write: write.as_bytes()[0],
+            });
+        }
+
+        Err("Springscript must end with WALK or RUN".to_owned())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.instructions.len() > MAX_INSTRUCTIONS {
+            return Err(format!(
+                "The droid can only hold {} instructions, not {}",
+                MAX_INSTRUCTIONS,
+                self.instructions.len()
+            ));
+        }
+
+        let registers = self.mode.registers();
+        for instruction in &self.instructions {
+            if !registers.contains(&instruction.read) {
+                return Err(format!("Can't read from {}", instruction.read as char));
+            }
+            if instruction.write != b'T' && instruction.write != b'J' {
+                return Err(format!("Can't write to {}", instruction.write as char));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the droid jumps, given what its sensors can see ahead.
+    pub fn jumps(&self, ground: &[bool]) -> bool {
+        let (mut t, mut j) = (false, false);
+        for instruction in &self.instructions {
+            let read = match instruction.read {
+                b'T' => t,
+                b'J' => j,
+                sensor => ground[(sensor - b'A') as usize],
+            };
+            let write = if instruction.write == b'T' {
+                &mut t
+            } else {
+                &mut j
+            };
+            *write = match instruction.op {
+                Op::And => read && *write,
+                Op::Or => read || *write,
+                Op::Not => !read,
+            };
+        }
+        j
+    }
+
+    /// Whether the droid makes it across a stretch of hull, starting on its
+    /// first tile. Past the end of the hull there's solid ground.
+    pub fn survives(&self, hull: &[bool]) -> bool {
+        let ground = |i: usize| hull.get(i).copied().unwrap_or(true);
+        let mut position = 0;
+        while position < hull.len() {
+            let ahead = (1..=self.mode.range())
+                .map(|i| ground(position + i))
+                .collect::<Vec<_>>();
+            position += if self.jumps(&ahead) { 4 } else { 1 };
+            if !ground(position) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Display for Springscript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for instruction in &self.instructions {
+            let op = match instruction.op {
+                Op::And => "AND",
+                Op::Or => "OR",
+                Op::Not => "NOT",
+            };
+            writeln!(
+                f,
+                "{} {} {}",
+                op, instruction.read as char, instruction.write as char
+            )?;
+        }
+        match self.mode {
+            Mode::Walk => writeln!(f, "WALK"),
+            Mode::Run => writeln!(f, "RUN"),
+        }
+    }
+}
+
+fn parse_hull(line: &str) -> Vec<bool> {
+    line.chars().map(|c| c != '.').collect()
+}
+
+/// How a survey of the hull went.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The droid made it across and reported the hull damage.
+    Damage(i128),
+    /// The droid fell in a hole, on the hull shown here.
+    Fell(Vec<bool>),
+}
+
+/// Send the droid across the hull running the given springscript.
+pub fn survey(mem: &[i128], script: &Springscript) -> Result<Outcome, String> {
+    let io = AsciiIo::with_input(&script.to_string());
+    let mut mem = mem.to_vec();
+    Program::new(&mut mem, &io).run();
+    if let Some(&damage) = io.values().last() {
+        return Ok(Outcome::Damage(damage));
+    }
+
+    // The first frame of the droid's last moments shows the hull it's
+    // standing on, before it has moved
+    let text = io.text();
+    text.lines()
+        .find(|l| l.contains('#') && l.chars().all(|c| c == '#' || c == '.'))
+        .map(|l| Outcome::Fell(parse_hull(l)))
+        .ok_or_else(|| format!("The droid didn't report anything useful:\n{}", text))
+}
+
+/// Look for a springscript of at most `max_len` instructions that gets the
+/// droid across, shortest first. Candidates are checked against every hull
+/// the droid has already fallen on before sending it out again.
+pub fn search(
+    mode: Mode,
+    max_len: usize,
+    mut droid: impl FnMut(&Springscript) -> Result<Outcome, String>,
+) -> Result<Option<(Springscript, i128)>, String> {
+    let mut choices = vec![];
+    for &op in &[Op::And, Op::Or, Op::Not] {
+        for read in mode.registers() {
+            for &write in b"TJ" {
+                choices.push(Instruction { op, read, write });
+            }
+        }
+    }
+
+    let mut hulls: Vec<Vec<bool>> = vec![];
+    for len in 1..=max_len.min(MAX_INSTRUCTIONS) {
+        // Count through every combination of choices, like an odometer
+        let mut picks = vec![0; len];
+        loop {
+            let script = Springscript {
+                mode,
+                instructions: picks.iter().map(|&i| choices[i]).collect(),
+            };
+            // A script that never writes to J never jumps
+            let writes_j = script.instructions.iter().any(|i| i.write == b'J');
+            if writes_j && hulls.iter().all(|h| script.survives(h)) {
+                match droid(&script)? {
+                    Outcome::Damage(damage) => return Ok(Some((script, damage))),
+                    Outcome::Fell(hull) => {
+                        if hulls.contains(&hull) {
+                            return Err(format!(
+                                "The droid fell on a hull it should have crossed with\n{}",
+                                script
+                            ));
+                        }
+                        hulls.push(hull);
+                    }
+                }
+            }
+
+            match picks.iter().rposition(|&i| i + 1 < choices.len()) {
+                Some(i) => {
+                    picks[i] += 1;
+                    for pick in &mut picks[i + 1..] {
+                        *pick = 0;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn damage(mem: &[i128], script: &str) -> Result<String, String> {
+    match survey(mem, &Springscript::parse(script)?)? {
+        Outcome::Damage(damage) => Ok(damage.to_string()),
+        Outcome::Fell(_) => Err(format!("The droid fell running\n{}", script)),
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse_str(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        damage(mem, WALK)
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        damage(mem, RUN)
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 21, solution: Day21);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_and_prints_springscript() {
+        for &script in &[WALK, RUN] {
+            assert_eq!(Springscript::parse(script).unwrap().to_string(), script);
+        }
+        assert!(Springscript::parse("NOT A J").is_err());
+        assert!(Springscript::parse("NOT E J\nWALK").is_err());
+        assert!(Springscript::parse("NOT A B\nWALK").is_err());
+        assert!(Springscript::parse("XOR A J\nRUN").is_err());
+        assert!(Springscript::parse(&"NOT A J\n".repeat(16)).is_err());
+    }
+
+    #[test]
+    fn crosses_hulls() {
+        let walk = Springscript::parse(WALK).unwrap();
+        let run = Springscript::parse(RUN).unwrap();
+        let naive = Springscript::parse("NOT A J\nWALK").unwrap();
+
+        let gap = parse_hull("#####.#..########");
+        assert!(walk.survives(&gap));
+        assert!(run.survives(&gap));
+        assert!(!naive.survives(&gap));
+
+        // Walking jumps too early here, landing where it can't go on
+        let trap = parse_hull("#####.#.##..#.####");
+        assert!(!walk.survives(&trap));
+        assert!(run.survives(&trap));
+    }
+
+    #[test]
+    fn searches_for_scripts() {
+        let hidden = ["#####.########", "####.#.#####", "#####...#######"]
+            .iter()
+            .map(|h| parse_hull(h))
+            .collect::<Vec<_>>();
+        let mut surveys = 0;
+        let found = search(Mode::Walk, 3, |script| {
+            surveys += 1;
+            Ok(hidden
+                .iter()
+                .find(|h| !script.survives(h))
+                .map(|h| Outcome::Fell(h.clone()))
+                .unwrap_or(Outcome::Damage(42)))
+        })
+        .unwrap();
+
+        let (script, damage) = found.unwrap();
+        assert_eq!(damage, 42);
+        assert!(hidden.iter().all(|h| script.survives(h)));
+        assert!(surveys <= hidden.len() + 1);
+
+        assert_eq!(search(Mode::Walk, 0, |_| Ok(Outcome::Damage(0))), Ok(None));
+    }
+}
@@ -0,0 +1,29 @@
+use aoc_runner::Solution;
+use day21::{search, survey, Day21, Mode};
+use std::{env, fs};
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day21::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let mem = Day21::parse(&input)?;
+
+    // `cargo run --release -p day21 -- search [max instructions]` to look
+    // for a walking script instead of using the hand-written one
+    if env::args().nth(1).as_deref() == Some("search") {
+        let max_len = match env::args().nth(2) {
+            Some(max) => max
+                .parse()
+                .map_err(|e| format!("Bad instruction count {}: {}", max, e))?,
+            None => 4,
+        };
+        match search(Mode::Walk, max_len, |script| survey(&mem, script))? {
+            Some((script, damage)) => print!("Hull damage: {}, found with\n{}", damage, script),
+            None => println!("No script of up to {} instructions works", max_len),
+        }
+        return Ok(());
+    }
+
+    println!("Hull damage walking: {}", Day21::part1(&mem)?);
+    println!("Hull damage running: {}", Day21::part2(&mem)?);
+    Ok(())
+}
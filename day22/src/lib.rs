@@ -0,0 +1,237 @@
+use aoc_runner::Solution;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Technique {
+    NewStack,
+    Cut(i128),
+    Increment(i128),
+}
+
+impl Technique {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let number = |n: &str| {
+            n.trim()
+                .parse()
+                .map_err(|e| format!("Bad number in {:?}: {}", line, e))
+        };
+        if line == "deal into new stack" {
+            Ok(Technique::NewStack)
+        } else if let Some(n) = line.strip_prefix("cut ") {
+            Ok(Technique::Cut(number(n)?))
+        } else if let Some(n) = line.strip_prefix("deal with increment ") {
+            Ok(Technique::Increment(number(n)?))
+        } else {
+            Err(format!("Unknown technique {:?}", line))
+        }
+    }
+}
+
+/// A shuffle of a deck, as the linear function `a * card + b` (mod the
+/// deck size) giving where each card ends up.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shuffle {
+    a: i128,
+    b: i128,
+    size: i128,
+}
+
+impl Shuffle {
+    fn identity(size: i128) -> Self {
+        Self { a: 1, b: 0, size }
+    }
+
+    /// Combine the techniques into one shuffle of a deck of `size` cards.
+    /// Dealing with an increment that shares a factor with the deck size
+    /// would put more than one card in the same place, so that's an error.
+    pub fn new(techniques: &[Technique], size: i128) -> Result<Self, String> {
+        if size < 1 {
+            return Err(format!("Can't shuffle a deck of {} cards", size));
+        }
+        // Reducing every operand first keeps the products in `then` small
+        techniques
+            .iter()
+            .try_fold(Self::identity(size), |shuffle, technique| {
+                let (a, b) = match *technique {
+                    Technique::NewStack => (-1, -1),
+                    Technique::Cut(n) => (1, -n.rem_euclid(size)),
+                    Technique::Increment(n) => {
+                        let reduced = n.rem_euclid(size);
+                        if gcd(reduced, size) != 1 {
+                            return Err(format!(
+                                "Dealing with increment {} loses cards in a deck of {}",
+                                n, size
+                            ));
+                        }
+                        (reduced, 0)
+                    }
+                };
+                Ok(shuffle.then(Self { a, b, size }))
+            })
+    }
+
+    // Shuffle with self, then with next
+    fn then(self, next: Self) -> Self {
+        Self {
+            a: (next.a * self.a).rem_euclid(self.size),
+            b: (next.a * self.b + next.b).rem_euclid(self.size),
+            size: self.size,
+        }
+    }
+
+    /// Shuffle the deck `times` times over, by repeated squaring.
+    pub fn repeat(self, mut times: u64) -> Self {
+        let mut result = Self::identity(self.size);
+        let mut square = self;
+        while times > 0 {
+            if times & 1 == 1 {
+                result = result.then(square);
+            }
+            square = square.then(square);
+            times >>= 1;
+        }
+        result
+    }
+
+    /// Where a card ends up after shuffling.
+    pub fn position(&self, card: i128) -> i128 {
+        (self.a * card + self.b).rem_euclid(self.size)
+    }
+
+    /// Which card ends up at a position after shuffling.
+    pub fn card_at(&self, position: i128) -> Result<i128, String> {
+        let inverse = inverse(self.a, self.size)
+            .ok_or_else(|| format!("The shuffle loses cards in a deck of {}", self.size))?;
+        Ok(((position - self.b) * inverse).rem_euclid(self.size))
+    }
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Modular inverse through the extended Euclidean algorithm
+fn inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (mut r, mut next_r) = (modulus, a.rem_euclid(modulus));
+    let (mut t, mut next_t) = (0, 1);
+    while next_r != 0 {
+        let q = r / next_r;
+        (r, next_r) = (next_r, r - q * next_r);
+        (t, next_t) = (next_t, t - q * next_t);
+    }
+    if r == 1 {
+        Some(t.rem_euclid(modulus))
+    } else {
+        None
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<Technique>;
+
+    fn parse(input: &str) -> Result<Vec<Technique>, String> {
+        input
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(Technique::parse)
+            .collect()
+    }
+
+    fn part1(techniques: &Vec<Technique>) -> Result<String, String> {
+        Ok(Shuffle::new(techniques, 10_007)?.position(2019).to_string())
+    }
+
+    fn part2(techniques: &Vec<Technique>) -> Result<String, String> {
+        Shuffle::new(techniques, 119_315_717_514_047)?
+            .repeat(101_741_582_076_661)
+            .card_at(2020)
+            .map(|card| card.to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 22, solution: Day22);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn deck(techniques: &str) -> Vec<i128> {
+        let shuffle = Shuffle::new(&Day22::parse(techniques).unwrap(), 10).unwrap();
+        (0..10).map(|p| shuffle.card_at(p).unwrap()).collect()
+    }
+
+    #[test]
+    fn small_decks() {
+        assert_eq!(
+            deck("deal with increment 7\ndeal into new stack\ndeal into new stack"),
+            vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]
+        );
+        assert_eq!(
+            deck("cut 6\ndeal with increment 7\ndeal into new stack"),
+            vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]
+        );
+        assert_eq!(
+            deck("deal with increment 7\ndeal with increment 9\ncut -2"),
+            vec![6, 3, 0, 7, 4, 1, 8, 5, 2, 9]
+        );
+        assert_eq!(
+            deck(
+                "deal into new stack
+                 cut -2
+                 deal with increment 7
+                 cut 8
+                 cut -4
+                 deal with increment 7
+                 cut 3
+                 deal with increment 9
+                 deal with increment 3
+                 cut -1"
+            ),
+            vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6]
+        );
+    }
+
+    #[test]
+    fn repeated_shuffles() {
+        let techniques = Day22::parse("cut 6\ndeal with increment 7\ndeal into new stack").unwrap();
+        let shuffle = Shuffle::new(&techniques, 10_007).unwrap();
+        let mut card = 2019;
+        for times in 0..20 {
+            assert_eq!(shuffle.repeat(times).position(2019), card);
+            card = shuffle.position(card);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_techniques() {
+        assert!(Technique::parse("riffle").is_err());
+        assert!(Technique::parse("cut x").is_err());
+        assert_eq!(
+            Shuffle::new(&[Technique::Increment(2)], 10),
+            Err("Dealing with increment 2 loses cards in a deck of 10".to_owned())
+        );
+        assert!(Shuffle::new(&[Technique::Increment(0)], 10).is_err());
+        assert!(Shuffle::new(&[Technique::Increment(1)], 0).is_err());
+    }
+
+    #[test]
+    fn reduces_huge_operands() {
+        // Far bigger than the deck, but the same as cutting 6 and dealing
+        // with 7
+        let techniques = [
+            Technique::Cut(i128::MAX - 1),
+            Technique::Increment(i128::MAX),
+            Technique::NewStack,
+        ];
+        let shuffle = Shuffle::new(&techniques, 10).unwrap();
+        let cards = (0..10)
+            .map(|p| shuffle.card_at(p).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(cards, vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+    }
+}
@@ -0,0 +1,12 @@
+use aoc_runner::Solution;
+use day22::Day22;
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day22::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let techniques = Day22::parse(&input)?;
+    println!("Position of card 2019: {}", Day22::part1(&techniques)?);
+    println!("Card at position 2020: {}", Day22::part2(&techniques)?);
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use aoc_runner::Solution;
+use int_code_emulator::{parse_str, Io, Program, Scheduler};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
+
+const COMPUTERS: usize = 50;
+const NAT: i128 = 255;
+/// How many instructions each computer runs before the next gets a turn.
+const SLICE: usize = 1_000;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Packet {
+    pub x: i128,
+    pub y: i128,
+}
+
+/// A network interface. Reading never blocks: with nothing queued the
+/// computer reads -1, which also tells us it's waiting for packets.
+struct Nic {
+    inbox: RefCell<VecDeque<i128>>,
+    outbox: RefCell<Vec<i128>>,
+    idle_reads: Cell<usize>,
+}
+
+impl Nic {
+    // Each computer's first input is its address
+    fn new(address: usize) -> Self {
+        Self {
+            inbox: RefCell::new(vec![address as i128].into()),
+            outbox: RefCell::new(vec![]),
+            idle_reads: Cell::new(0),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.idle_reads.get() >= 2
+            && self.inbox.borrow().is_empty()
+            && self.outbox.borrow().is_empty()
+    }
+}
+
+impl Io for Nic {
+    fn read(&self) -> String {
+        match self.inbox.borrow_mut().pop_front() {
+            Some(value) => {
+                self.idle_reads.set(0);
+                value.to_string()
+            }
+            None => {
+                self.idle_reads.set(self.idle_reads.get() + 1);
+                "-1".to_owned()
+            }
+        }
+    }
+
+    fn write(&self, output: &str) {
+        self.idle_reads.set(0);
+        let value = output.parse().expect("Failed to parse output");
+        self.outbox.borrow_mut().push(value);
+    }
+}
+
+/// What the NAT is told about.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Signal {
+    /// A packet was sent to address 255.
+    Packet(Packet),
+    /// Every computer is waiting on an empty queue.
+    Idle,
+}
+
+pub struct Network {
+    nics: Vec<Nic>,
+}
+
+impl Network {
+    fn new(size: usize) -> Self {
+        Self {
+            nics: (0..size).map(Nic::new).collect(),
+        }
+    }
+
+    pub fn send(&self, address: usize, packet: Packet) {
+        self.nics[address]
+            .inbox
+            .borrow_mut()
+            .extend(&[packet.x, packet.y]);
+    }
+
+    // Deliver every complete packet waiting in an outbox, returning the
+    // ones sent to the NAT
+    fn route(&self) -> Result<Vec<Packet>, String> {
+        let mut nat = vec![];
+        for nic in &self.nics {
+            let mut outbox = nic.outbox.borrow_mut();
+            let complete = outbox.len() / 3 * 3;
+            for chunk in outbox.drain(..complete).collect::<Vec<_>>().chunks(3) {
+                let packet = Packet {
+                    x: chunk[1],
+                    y: chunk[2],
+                };
+                match chunk[0] {
+                    NAT => nat.push(packet),
+                    address if (0..self.nics.len() as i128).contains(&address) => {
+                        self.send(address as usize, packet)
+                    }
+                    address => return Err(format!("No computer at address {}", address)),
+                }
+            }
+        }
+        Ok(nat)
+    }
+
+    fn is_idle(&self) -> bool {
+        self.nics.iter().all(Nic::is_idle)
+    }
+
+    /// Boot a network of computers all running the same program, telling
+    /// the NAT what happens until it has an answer.
+    pub fn run<R>(
+        mem: &[i128],
+        size: usize,
+        mut nat: impl FnMut(&Network, Signal) -> Option<R>,
+    ) -> Result<R, String> {
+        let network = Self::new(size);
+        let mut mems = vec![mem.to_vec(); size];
+        let mut scheduler = Scheduler::new();
+        for (mem, nic) in mems.iter_mut().zip(&network.nics) {
+            scheduler.spawn(Program::new(mem, nic));
+        }
+
+        loop {
+            let running = scheduler.tick(SLICE);
+            for packet in network.route()? {
+                if let Some(answer) = nat(&network, Signal::Packet(packet)) {
+                    return Ok(answer);
+                }
+            }
+            if network.is_idle() {
+                if let Some(answer) = nat(&network, Signal::Idle) {
+                    return Ok(answer);
+                }
+            }
+            if !running {
+                return Err("Every computer shut down".to_owned());
+            }
+        }
+    }
+}
+
+/// The Y value of the first packet sent to the NAT.
+pub fn first_nat_packet(mem: &[i128]) -> Result<i128, String> {
+    Network::run(mem, COMPUTERS, |_, signal| match signal {
+        Signal::Packet(packet) => Some(packet.y),
+        Signal::Idle => None,
+    })
+}
+
+/// The first Y value the NAT delivers to address 0 twice in a row, waking
+/// the network whenever it goes idle.
+pub fn first_repeated_wake_up(mem: &[i128]) -> Result<i128, String> {
+    let mut last = None;
+    let mut delivered = None;
+    Network::run(mem, COMPUTERS, |network, signal| {
+        match signal {
+            Signal::Packet(packet) => last = Some(packet),
+            Signal::Idle => {
+                let packet = last?;
+                if delivered == Some(packet.y) {
+                    return Some(packet.y);
+                }
+                delivered = Some(packet.y);
+                network.send(0, packet);
+            }
+        }
+        None
+    })
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse_str(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        Ok(first_nat_packet(mem)?.to_string())
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        Ok(first_repeated_wake_up(mem)?.to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 23, solution: Day23);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nics_never_block() {
+        let nic = Nic::new(7);
+        assert_eq!(nic.read(), "7");
+        assert_eq!(nic.read(), "-1");
+        assert!(!nic.is_idle());
+        assert_eq!(nic.read(), "-1");
+        assert!(nic.is_idle());
+        nic.write("3");
+        assert!(!nic.is_idle());
+    }
+
+    #[test]
+    fn routes_packets() {
+        let network = Network::new(2);
+        for value in &["1", "5", "6", "255", "7", "8", "0"] {
+            network.nics[0].write(value);
+        }
+        assert_eq!(network.route(), Ok(vec![Packet { x: 7, y: 8 }]));
+        assert_eq!(*network.nics[1].inbox.borrow(), vec![1, 5, 6]);
+        assert_eq!(*network.nics[0].outbox.borrow(), vec![0]);
+
+        network.nics[1].write("4");
+        network.nics[1].write("1");
+        network.nics[1].write("2");
+        assert!(network.route().is_err());
+    }
+
+    #[test]
+    fn reports_to_the_nat() {
+        // Every computer sends its address and 42 to the NAT, then waits
+        let mem = vec![3, 100, 104, 255, 4, 100, 104, 42, 3, 101, 1105, 1, 8];
+        assert_eq!(
+            Network::run(&mem, 3, |_, signal| match signal {
+                Signal::Packet(packet) if packet.x == 2 => Some(packet.y),
+                _ => None,
+            }),
+            Ok(42)
+        );
+    }
+
+    #[test]
+    fn wakes_idle_networks() {
+        // Computer 0 sends Y = 0 to the NAT, then sends back whatever it
+        // receives with one added to Y. The others stay quiet.
+        let mem = parse_str(
+            "3,100,1005,100,35,104,255,104,0,104,0,\
+             3,101,1008,101,-1,103,1005,103,11,\
+             3,102,1001,102,1,102,104,255,4,101,4,102,1105,1,11,\
+             3,101,1105,1,35",
+        );
+        let mut wake_ups = 0;
+        let mut last = None;
+        let answer = Network::run(&mem, 2, |network, signal| {
+            match signal {
+                Signal::Packet(packet) => last = Some(packet),
+                Signal::Idle => {
+                    let packet = last.take()?;
+                    wake_ups += 1;
+                    if packet.y == 3 {
+                        return Some(wake_ups);
+                    }
+                    network.send(0, packet);
+                }
+            }
+            None
+        });
+        assert_eq!(answer, Ok(4));
+    }
+}
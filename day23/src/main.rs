@@ -0,0 +1,12 @@
+use aoc_runner::Solution;
+use day23::Day23;
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day23::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let mem = Day23::parse(&input)?;
+    println!("First Y sent to the NAT: {}", Day23::part1(&mem)?);
+    println!("First Y the NAT delivers twice: {}", Day23::part2(&mem)?);
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use aoc_runner::Solution;
+use std::collections::{HashSet, VecDeque};
+
+const SIZE: usize = 5;
+const CENTER: usize = 12;
+
+fn bit(x: usize, y: usize) -> u32 {
+    1 << (y * SIZE + x)
+}
+
+/// For every tile, the bits of its neighbours on its own level and, when
+/// levels are recursive, on the levels around and inside it.
+struct Neighbours {
+    same: [u32; SIZE * SIZE],
+    outer: [u32; SIZE * SIZE],
+    inner: [u32; SIZE * SIZE],
+}
+
+impl Neighbours {
+    fn new(recursive: bool) -> Self {
+        let mut neighbours = Self {
+            same: [0; SIZE * SIZE],
+            outer: [0; SIZE * SIZE],
+            inner: [0; SIZE * SIZE],
+        };
+        let column = |x| (0..SIZE).fold(0, |mask, y| mask | bit(x, y));
+        let row = |y| (0..SIZE).fold(0, |mask, x| mask | bit(x, y));
+
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let i = y * SIZE + x;
+                if recursive && i == CENTER {
+                    continue;
+                }
+
+                let mut same = 0;
+                if x > 0 {
+                    same |= bit(x - 1, y);
+                }
+                if x + 1 < SIZE {
+                    same |= bit(x + 1, y);
+                }
+                if y > 0 {
+                    same |= bit(x, y - 1);
+                }
+                if y + 1 < SIZE {
+                    same |= bit(x, y + 1);
+                }
+                if !recursive {
+                    neighbours.same[i] = same;
+                    continue;
+                }
+                neighbours.same[i] = same & !(1 << CENTER);
+
+                // Edges border the tiles around the center of the outer level
+                let mut outer = 0;
+                if x == 0 {
+                    outer |= bit(1, 2);
+                }
+                if x == SIZE - 1 {
+                    outer |= bit(3, 2);
+                }
+                if y == 0 {
+                    outer |= bit(2, 1);
+                }
+                if y == SIZE - 1 {
+                    outer |= bit(2, 3);
+                }
+                neighbours.outer[i] = outer;
+
+                // Tiles around the center border a whole edge of the inner level
+                neighbours.inner[i] = match (x, y) {
+                    (2, 1) => row(0),
+                    (2, 3) => row(SIZE - 1),
+                    (1, 2) => column(0),
+                    (3, 2) => column(SIZE - 1),
+                    _ => 0,
+                };
+            }
+        }
+        neighbours
+    }
+
+    fn step(&self, level: u32, outer: u32, inner: u32) -> u32 {
+        let mut next = 0;
+        for i in 0..SIZE * SIZE {
+            let count = (level & self.same[i]).count_ones()
+                + (outer & self.outer[i]).count_ones()
+                + (inner & self.inner[i]).count_ones();
+            let bug = level & 1 << i != 0;
+            if count == 1 || (!bug && count == 2) {
+                next |= 1 << i;
+            }
+        }
+        next
+    }
+}
+
+/// A 5x5 grid of bugs, one bit per tile, read left to right and top to
+/// bottom.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Eris(u32);
+
+impl Eris {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let rows = input
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>();
+        if rows.len() != SIZE || rows.iter().any(|r| r.len() != SIZE) {
+            return Err(format!("Expected a {0}x{0} grid:\n{1}", SIZE, input));
+        }
+
+        let mut bugs = 0;
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                match c {
+                    '#' => bugs |= bit(x, y),
+                    '.' | '?' => {}
+                    _ => return Err(format!("Unexpected {:?} at ({}, {})", c, x, y)),
+                }
+            }
+        }
+        Ok(Self(bugs))
+    }
+
+    /// Conveniently, the rating is the bitboard itself.
+    pub fn biodiversity(self) -> u32 {
+        self.0
+    }
+
+    /// The first layout to appear twice.
+    pub fn first_repeat(self) -> Self {
+        let neighbours = Neighbours::new(false);
+        let mut seen = HashSet::new();
+        let mut bugs = self.0;
+        while seen.insert(bugs) {
+            bugs = neighbours.step(bugs, 0, 0);
+        }
+        Self(bugs)
+    }
+
+    /// Count the bugs after some minutes, where the center tile of every
+    /// level holds another whole level.
+    pub fn recursive_bugs(self, minutes: usize) -> u32 {
+        let neighbours = Neighbours::new(true);
+        // Outermost level first
+        let mut levels = VecDeque::from(vec![self.0 & !(1 << CENTER)]);
+        for _ in 0..minutes {
+            // Bugs spread at most one level further each minute
+            levels.push_front(0);
+            levels.push_back(0);
+            let level = |i: usize| levels.get(i).copied().unwrap_or(0);
+            let next = (0..levels.len())
+                .map(|i| neighbours.step(level(i), i.checked_sub(1).map_or(0, level), level(i + 1)))
+                .collect::<VecDeque<_>>();
+            levels = next;
+
+            while levels.len() > 1 && levels.front() == Some(&0) {
+                levels.pop_front();
+            }
+            while levels.len() > 1 && levels.back() == Some(&0) {
+                levels.pop_back();
+            }
+        }
+        levels.iter().map(|l| l.count_ones()).sum()
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Eris;
+
+    fn parse(input: &str) -> Result<Eris, String> {
+        Eris::parse(input)
+    }
+
+    fn part1(eris: &Eris) -> Result<String, String> {
+        Ok(eris.first_repeat().biodiversity().to_string())
+    }
+
+    fn part2(eris: &Eris) -> Result<String, String> {
+        Ok(eris.recursive_bugs(200).to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 24, solution: Day24);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "
+....#
+#..#.
+#..##
+..#..
+#....";
+
+    #[test]
+    fn first_repeat() {
+        let eris = Eris::parse(EXAMPLE).unwrap();
+        let repeat = eris.first_repeat();
+        assert_eq!(
+            repeat,
+            Eris::parse(".....\n.....\n.....\n#....\n.#...").unwrap()
+        );
+        assert_eq!(repeat.biodiversity(), 2_129_920);
+    }
+
+    #[test]
+    fn recursive_levels() {
+        let eris = Eris::parse(EXAMPLE).unwrap();
+        assert_eq!(eris.recursive_bugs(0), 8);
+        assert_eq!(eris.recursive_bugs(10), 99);
+    }
+
+    #[test]
+    fn neighbour_counts() {
+        let neighbours = Neighbours::new(true);
+        // (3, 3) is an ordinary tile, (3, 2) borrows a whole edge from the
+        // inner level and (0, 0) borrows two tiles from the outer one
+        assert_eq!(neighbours.same[18].count_ones(), 4);
+        assert_eq!(neighbours.same[13].count_ones(), 3);
+        assert_eq!(neighbours.inner[13].count_ones(), 5);
+        assert_eq!(
+            neighbours.same[0].count_ones() + neighbours.outer[0].count_ones(),
+            4
+        );
+        assert_eq!(neighbours.same[CENTER], 0);
+    }
+
+    #[test]
+    fn rejects_bad_grids() {
+        assert!(Eris::parse("#####").is_err());
+        assert!(Eris::parse("....#\n#..#.\n#.x##\n..#..\n#....").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use aoc_runner::Solution;
+use day24::Day24;
+use std::fs;
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day24::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let eris = Day24::parse(&input)?;
+    println!("Biodiversity of the first repeat: {}", Day24::part1(&eris)?);
+    println!("Bugs after 200 minutes: {}", Day24::part2(&eris)?);
+    Ok(())
+}
@@ -0,0 +1,400 @@
+use aoc_runner::Solution;
+use int_code_emulator::{parse_str, AsciiIo, Program};
+use std::collections::HashSet;
+
+/// Items that end the game, or leave the droid unable to go on. The
+/// infinite loop is exactly that, so these can't be found out by trying.
+const DANGEROUS: &[&str] = &[
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
+
+const CHECKPOINT: &str = "Security Checkpoint";
+
+/// Something that can be sent commands and answers with text, like the
+/// droid.
+pub trait Console {
+    fn send(&mut self, command: &str) -> Result<String, String>;
+}
+
+/// The droid, running on the emulator one instruction at a time so that it
+/// can stop whenever it asks for a command.
+pub struct Droid<'a> {
+    program: Program<'a, &'a AsciiIo>,
+    io: &'a AsciiIo,
+}
+
+impl<'a> Droid<'a> {
+    /// Boot the droid, returning it along with its opening message.
+    pub fn boot(mem: &'a mut Vec<i128>, io: &'a AsciiIo) -> (Self, String) {
+        let mut droid = Self {
+            program: Program::new(mem, io),
+            io,
+        };
+        droid.run();
+        let intro = droid.io.take_text();
+        (droid, intro)
+    }
+
+    fn run(&mut self) {
+        // Stop once the droid asks for a command it hasn't been given yet
+        while (self.io.has_input() || !self.program.wants_input()) && self.program.step() {}
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.program.has_exited()
+    }
+}
+
+impl<'a> Console for Droid<'a> {
+    fn send(&mut self, command: &str) -> Result<String, String> {
+        if !self.is_running() {
+            return Err(format!("The droid has shut down, it can't {:?}", command));
+        }
+        self.io.push_input(command.trim());
+        self.io.push_input("\n");
+        self.run();
+        Ok(self.io.take_text())
+    }
+}
+
+/// A room as the droid describes it.
+#[derive(Debug, PartialEq)]
+pub struct Room {
+    pub name: String,
+    pub doors: Vec<String>,
+    pub items: Vec<String>,
+}
+
+impl Room {
+    /// Read the last room described in some output. Being thrown out of
+    /// a room describes both the room and where the droid ended up.
+    pub fn parse(output: &str) -> Option<Self> {
+        let start = output.rfind("== ")?;
+        let mut lines = output[start..].lines();
+        let name = lines.next()?.trim_matches(|c| c == '=' || c == ' ');
+
+        let mut room = Room {
+            name: name.to_owned(),
+            doors: vec![],
+            items: vec![],
+        };
+        let mut list = None;
+        for line in lines {
+            match line {
+                "Doors here lead:" => list = Some(&mut room.doors),
+                "Items here:" => list = Some(&mut room.items),
+                _ => match (line.strip_prefix("- "), list.as_mut()) {
+                    (Some(entry), Some(list)) => list.push(entry.to_owned()),
+                    _ => list = None,
+                },
+            }
+        }
+        Some(room)
+    }
+}
+
+fn opposite(direction: &str) -> &'static str {
+    match direction {
+        "north" => "south",
+        "south" => "north",
+        "east" => "west",
+        _ => "east",
+    }
+}
+
+/// Pull the keypad password out of the droid's farewell.
+fn password(output: &str) -> Option<String> {
+    let start = output.find("typing ")? + "typing ".len();
+    let digits = output[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    Some(digits).filter(|d| !d.is_empty())
+}
+
+/// What exploring the ship found.
+#[derive(Debug, Default)]
+struct Survey {
+    visited: HashSet<String>,
+    items: Vec<String>,
+    /// Directions from the start to the checkpoint, and from there on to
+    /// the pressure-sensitive floor
+    checkpoint: Option<(Vec<String>, String)>,
+}
+
+// Depth first search of the ship, picking up everything safe along the way
+// and returning to where it started
+fn explore(
+    console: &mut impl Console,
+    room: Room,
+    path: &mut Vec<String>,
+    survey: &mut Survey,
+) -> Result<(), String> {
+    survey.visited.insert(room.name.clone());
+    for item in room.items {
+        if !DANGEROUS.contains(&item.as_str()) {
+            console.send(&format!("take {}", item))?;
+            survey.items.push(item);
+        }
+    }
+
+    let back = path.last().map(|d| opposite(d));
+    if room.name == CHECKPOINT {
+        // Going on without the right weight just throws the droid back
+        let floor = room
+            .doors
+            .iter()
+            .find(|&d| Some(d.as_str()) != back)
+            .ok_or("The checkpoint doesn't lead anywhere")?;
+        survey.checkpoint = Some((path.clone(), floor.clone()));
+        return Ok(());
+    }
+
+    for door in room.doors {
+        if Some(door.as_str()) == back {
+            continue;
+        }
+        let output = console.send(&door)?;
+        let next =
+            Room::parse(&output).ok_or_else(|| format!("Lost going {}:\n{}", door, output))?;
+        if !survey.visited.contains(&next.name) {
+            path.push(door.clone());
+            explore(console, next, path, survey)?;
+            path.pop();
+        }
+        console.send(opposite(&door))?;
+    }
+    Ok(())
+}
+
+/// Explore the ship, then try every combination of the items found on the
+/// pressure-sensitive floor until one gets the droid through.
+pub fn find_password(console: &mut impl Console, intro: &str) -> Result<String, String> {
+    let start = Room::parse(intro).ok_or_else(|| format!("Didn't start in a room:\n{}", intro))?;
+    let mut survey = Survey::default();
+    explore(console, start, &mut vec![], &mut survey)?;
+    let (path, floor) = survey.checkpoint.ok_or("Never found the checkpoint")?;
+
+    for direction in &path {
+        console.send(direction)?;
+    }
+    for item in &survey.items {
+        console.send(&format!("drop {}", item))?;
+    }
+
+    // Walk through the combinations in Gray code order, so only one item
+    // is taken or dropped between attempts
+    let items = &survey.items;
+    let mut held = 0u64;
+    for i in 0..1u64 << items.len() {
+        let combination = i ^ (i >> 1);
+        let changed = held ^ combination;
+        if changed != 0 {
+            let item = &items[changed.trailing_zeros() as usize];
+            let verb = if combination & changed != 0 {
+                "take"
+            } else {
+                "drop"
+            };
+            console.send(&format!("{} {}", verb, item))?;
+        }
+        held = combination;
+
+        if let Some(password) = password(&console.send(&floor)?) {
+            return Ok(password);
+        }
+    }
+
+    Err(format!("No combination of {} works", items.join(", ")))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse_str(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        let io = AsciiIo::new();
+        let mut mem = mem.clone();
+        let (mut droid, intro) = Droid::boot(&mut mem, &io);
+        find_password(&mut droid, &intro)
+    }
+
+    // The last star comes from having all the others
+    fn part2(_: &Vec<i128>) -> Result<String, String> {
+        Ok("Merry Christmas!".to_owned())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 25, solution: Day25);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    // Doors, with where they lead, and items
+    type Layout = (Vec<(&'static str, &'static str)>, Vec<&'static str>);
+
+    /// A tiny ship that talks like the real one.
+    struct Ship {
+        rooms: HashMap<&'static str, Layout>,
+        here: &'static str,
+        held: Vec<&'static str>,
+        password: Vec<&'static str>,
+        commands: usize,
+    }
+
+    impl Ship {
+        fn new() -> Self {
+            let mut rooms = HashMap::new();
+            rooms.insert(
+                "Hull Breach",
+                (vec![("north", "Kitchen"), ("east", CHECKPOINT)], vec![]),
+            );
+            rooms.insert(
+                "Kitchen",
+                (
+                    vec![("south", "Hull Breach"), ("east", "Hallway")],
+                    vec!["mug", "infinite loop"],
+                ),
+            );
+            rooms.insert(
+                "Hallway",
+                (
+                    vec![("west", "Kitchen")],
+                    vec!["spool of cat6", "weather machine"],
+                ),
+            );
+            rooms.insert(
+                CHECKPOINT,
+                (
+                    vec![
+                        ("west", "Hull Breach"),
+                        ("east", "Pressure-Sensitive Floor"),
+                    ],
+                    vec!["tambourine"],
+                ),
+            );
+            Self {
+                rooms,
+                here: "Hull Breach",
+                held: vec![],
+                password: vec!["mug", "weather machine"],
+                commands: 0,
+            }
+        }
+
+        fn describe(&self, name: &str) -> String {
+            let (doors, items) = &self.rooms[name];
+            let mut text = format!("\n\n\n== {} ==\nA room.\n\nDoors here lead:\n", name);
+            for (door, _) in doors {
+                text += &format!("- {}\n", door);
+            }
+            if !items.is_empty() {
+                text += "\nItems here:\n";
+                for item in items {
+                    text += &format!("- {}\n", item);
+                }
+            }
+            text + "\nCommand?\n"
+        }
+    }
+
+    impl Console for Ship {
+        fn send(&mut self, command: &str) -> Result<String, String> {
+            self.commands += 1;
+            if let Some(item) = command.strip_prefix("take ") {
+                let items = &mut self.rooms.get_mut(self.here).unwrap().1;
+                let i = items
+                    .iter()
+                    .position(|i| *i == item)
+                    .ok_or("No such item")?;
+                assert_ne!(item, "infinite loop", "Took the infinite loop");
+                self.held.push(items.remove(i));
+                return Ok(format!("\nYou take the {}.\n\nCommand?\n", item));
+            }
+            if let Some(item) = command.strip_prefix("drop ") {
+                let i = self
+                    .held
+                    .iter()
+                    .position(|i| *i == item)
+                    .ok_or("Not held")?;
+                let item = self.held.remove(i);
+                self.rooms.get_mut(self.here).unwrap().1.push(item);
+                return Ok(format!("\nYou drop the {}.\n\nCommand?\n", item));
+            }
+
+            let (doors, _) = &self.rooms[self.here];
+            let &(_, to) = doors
+                .iter()
+                .find(|(d, _)| *d == command)
+                .ok_or("You can't go that way.")?;
+            if to != "Pressure-Sensitive Floor" {
+                self.here = to;
+                return Ok(self.describe(to));
+            }
+
+            let mut held = self.held.clone();
+            held.sort_unstable();
+            if held == self.password {
+                Ok("\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n- west\n\n\
+                    A loud, robotic voice says \"Analysis complete! You may proceed.\" and you enter the cockpit.\n\
+                    Santa notices your small droid, looks puzzled for a moment, realizes what has happened, and radios your ship directly.\n\
+                    \"Oh, hello! You should be able to get in by typing 2424308736 on the keypad at the main airlock.\"\n"
+                    .to_owned())
+            } else {
+                Ok(format!(
+                    "\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n- west\n\n\
+                     A loud, robotic voice says \"Alert! Droids on this ship are heavier than the detected value!\" \
+                     and you are ejected back to the checkpoint.\n{}",
+                    self.describe(CHECKPOINT)
+                ))
+            }
+        }
+    }
+
+    #[test]
+    fn parses_rooms() {
+        let ship = Ship::new();
+        assert_eq!(
+            Room::parse(&ship.describe("Kitchen")),
+            Some(Room {
+                name: "Kitchen".to_owned(),
+                doors: vec!["south".to_owned(), "east".to_owned()],
+                items: vec!["mug".to_owned(), "infinite loop".to_owned()],
+            })
+        );
+        assert_eq!(Room::parse("\nYou take the mug.\n\nCommand?\n"), None);
+    }
+
+    #[test]
+    fn reads_passwords() {
+        assert_eq!(
+            password("You should be able to get in by typing 1234 on the keypad"),
+            Some("1234".to_owned())
+        );
+        assert_eq!(password("typing on the keypad"), None);
+        assert_eq!(password("Alert!"), None);
+    }
+
+    #[test]
+    fn solves_the_ship() {
+        let mut ship = Ship::new();
+        let intro = ship.describe("Hull Breach");
+        assert_eq!(
+            find_password(&mut ship, &intro),
+            Ok("2424308736".to_owned())
+        );
+        assert_eq!(ship.here, CHECKPOINT);
+        assert!(!ship.held.contains(&"spool of cat6"));
+    }
+}
@@ -0,0 +1,32 @@
+use aoc_runner::Solution;
+use day25::{Console, Day25, Droid};
+use int_code_emulator::AsciiIo;
+use std::{
+    env, fs,
+    io::{self, BufRead},
+};
+
+fn main() -> Result<(), String> {
+    let input = fs::read_to_string(day25::DAY.input_path())
+        .map_err(|e| format!("Failed to open input: {}", e))?;
+    let mem = Day25::parse(&input)?;
+
+    // `cargo run --release -p day25 -- play` to explore the ship yourself
+    if env::args().nth(1).as_deref() == Some("play") {
+        let io = AsciiIo::new();
+        let mut mem = mem;
+        let (mut droid, intro) = Droid::boot(&mut mem, &io);
+        print!("{}", intro);
+        for command in io::stdin().lock().lines() {
+            let command = command.map_err(|e| format!("Failed to read a command: {}", e))?;
+            print!("{}", droid.send(&command)?);
+            if !droid.is_running() {
+                break;
+            }
+        }
+        return Ok(());
+    }
+
+    println!("Password for the airlock: {}", Day25::part1(&mem)?);
+    Ok(())
+}
@@ -0,0 +1,1340 @@
+mod loops;
+mod png;
+mod stream;
+mod svg;
+mod view;
+
+pub use colors::Rgb;
+pub use loops::{Loops, SelfCrossing};
+pub use png::Raster;
+pub use stream::{read_wires, RouteReader};
+pub use view::{explore, Viewer};
+
+use aoc_runner::{Reporter, Solution};
+use std::{
+    collections::{BTreeSet, HashMap},
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+/// How far a wire may stray from the central port along either axis. Any
+/// two points this close to the port are few enough steps apart to count in
+/// a u64.
+pub const MAX_COORDINATE: i64 = i64::MAX / 4;
+/// How many steps long a wire may be, so that two wires' steps together
+/// still fit in a u64.
+pub const MAX_STEPS: u64 = u64::MAX / 2;
+
+/// A point on the grid, with the central port at the origin and y
+/// increasing upwards.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    /// How far the point is from the central port.
+    pub fn manhattan_distance(&self) -> u64 {
+        self.flat_distance_to(&Point::default())
+    }
+
+    /// How far apart two points are along the grid. Panics if that doesn't
+    /// fit in a u64, which can only happen for points further than
+    /// `MAX_COORDINATE` from the central port.
+    pub fn flat_distance_to(&self, other: &Self) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .checked_add(self.y.abs_diff(other.y))
+            .expect("Points are too far apart to measure")
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Polarity {
+    Vertical,
+    Horizontal,
+}
+
+struct Bounds {
+    low: i64,
+    high: i64,
+    bar: i64,
+}
+
+/// Which places two wires meet count as intersections. By default only
+/// points strictly inside both wires' segments count, and never the central
+/// port.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rules {
+    /// Also count places a wire only touches another, where one of them
+    /// turns or ends
+    pub endpoints: bool,
+    /// Also count the central port, which every wire starts from
+    pub origin: bool,
+}
+
+impl Rules {
+    // The part of a segment another can meet it on, which may be empty
+    fn span(&self, bounds: &Bounds) -> (i64, i64) {
+        if self.endpoints {
+            (bounds.low, bounds.high)
+        } else {
+            (bounds.low + 1, bounds.high - 1)
+        }
+    }
+
+    fn allows(&self, p: &Point) -> bool {
+        self.origin || *p != Point::default()
+    }
+}
+
+// The points worth knowing about where two segments on the same line run
+// along each other: the ends of the overlap, and the point closest to the
+// central port.
+fn overlap(polarity: Polarity, a: &Bounds, b: &Bounds, rules: Rules) -> Vec<Point> {
+    if a.bar != b.bar {
+        return vec![];
+    }
+    let low = rules.span(a).0.max(rules.span(b).0);
+    let high = rules.span(a).1.min(rules.span(b).1);
+    if low > high {
+        return vec![];
+    }
+
+    // When the central port doesn't count, look either side of it too
+    let mut along = vec![low, high, 0, -1, 1]
+        .into_iter()
+        .map(|t| t.clamp(low, high))
+        .filter(|&t| t != 0 || a.bar != 0 || rules.origin)
+        .collect::<Vec<_>>();
+    along.sort_unstable();
+    along.dedup();
+    along
+        .into_iter()
+        .map(|t| match polarity {
+            Polarity::Horizontal => Point { x: t, y: a.bar },
+            Polarity::Vertical => Point { x: a.bar, y: t },
+        })
+        .collect()
+}
+
+/// A straight stretch of wire, from its first point to its second.
+pub struct Segment(pub Point, pub Point);
+
+impl Segment {
+    fn polarity_and_bounds(&self) -> (Polarity, Bounds) {
+        if self.0.x == self.1.x {
+            if self.0.y < self.1.y {
+                (
+                    Polarity::Vertical,
+                    Bounds {
+                        low: self.0.y,
+                        high: self.1.y,
+                        bar: self.0.x,
+                    },
+                )
+            } else {
+                (
+                    Polarity::Vertical,
+                    Bounds {
+                        low: self.1.y,
+                        high: self.0.y,
+                        bar: self.0.x,
+                    },
+                )
+            }
+        } else {
+            if self.0.x < self.1.x {
+                (
+                    Polarity::Horizontal,
+                    Bounds {
+                        low: self.0.x,
+                        high: self.1.x,
+                        bar: self.0.y,
+                    },
+                )
+            } else {
+                (
+                    Polarity::Horizontal,
+                    Bounds {
+                        low: self.1.x,
+                        high: self.0.x,
+                        bar: self.0.y,
+                    },
+                )
+            }
+        }
+    }
+
+    /// Where this segment crosses another, and how far along each segment
+    /// that is. Segments that run along each other don't cross; see
+    /// `overlaps` for those.
+    pub fn crosses(&self, other: &Segment, rules: Rules) -> Option<(Point, u64, u64)> {
+        let point = match (self.polarity_and_bounds(), other.polarity_and_bounds()) {
+            ((Polarity::Horizontal, ..), (Polarity::Horizontal, ..))
+            | ((Polarity::Vertical, ..), (Polarity::Vertical, ..)) => None,
+            ((Polarity::Vertical, v_bounds), (Polarity::Horizontal, h_bounds))
+            | ((Polarity::Horizontal, h_bounds), (Polarity::Vertical, v_bounds)) => {
+                let (v_low, v_high) = rules.span(&v_bounds);
+                let (h_low, h_high) = rules.span(&h_bounds);
+                if h_bounds.bar < v_low
+                    || h_bounds.bar > v_high
+                    || v_bounds.bar < h_low
+                    || v_bounds.bar > h_high
+                {
+                    None
+                } else {
+                    Some(Point {
+                        x: v_bounds.bar,
+                        y: h_bounds.bar,
+                    })
+                }
+            }
+        };
+
+        point
+            .filter(|p| rules.allows(p))
+            .map(|p| (p, self.0.flat_distance_to(&p), other.0.flat_distance_to(&p)))
+    }
+
+    /// Where this segment runs along another, and how far along each
+    /// segment that is. Rather than every shared point, this only gives the
+    /// ends of the overlap and the shared point closest to the central port,
+    /// which between them are the best by either measure.
+    pub fn overlaps(&self, other: &Segment, rules: Rules) -> Vec<(Point, u64, u64)> {
+        let (polarity, bounds) = self.polarity_and_bounds();
+        let (other_polarity, other_bounds) = other.polarity_and_bounds();
+        if polarity != other_polarity {
+            return vec![];
+        }
+        overlap(polarity, &bounds, &other_bounds, rules)
+            .into_iter()
+            .map(|p| (p, self.0.flat_distance_to(&p), other.0.flat_distance_to(&p)))
+            .collect()
+    }
+
+    /// How many steps the segment covers.
+    pub fn length(&self) -> u64 {
+        let (_, Bounds { low, high, .. }) = self.polarity_and_bounds();
+        low.abs_diff(high)
+    }
+}
+
+impl Debug for Segment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{{({}, {}) - ({}, {})}}",
+            self.0.x, self.0.y, self.1.x, self.1.y
+        )
+    }
+}
+
+/// One step of a wire's route, such as `U7`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Route {
+    Up(u32),
+    Down(u32),
+    Left(u32),
+    Right(u32),
+}
+
+/// Why a step of a route couldn't be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RouteError {
+    Empty,
+    Direction(char),
+    Length(ParseIntError),
+}
+
+impl Display for RouteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::Empty => write!(f, "Empty route"),
+            RouteError::Direction(c) => {
+                write!(f, "Unknown direction {:?}, expected U, D, L or R", c)
+            }
+            RouteError::Length(e) => write!(f, "Bad route length: {}", e),
+        }
+    }
+}
+
+impl Error for RouteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RouteError::Length(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Route {
+    type Err = RouteError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let first_char = chars.next().ok_or(RouteError::Empty)?;
+        let num = chars.as_str().parse().map_err(RouteError::Length);
+        match first_char {
+            'U' => Ok(Route::Up(num?)),
+            'D' => Ok(Route::Down(num?)),
+            'L' => Ok(Route::Left(num?)),
+            'R' => Ok(Route::Right(num?)),
+            _ => Err(RouteError::Direction(first_char)),
+        }
+    }
+}
+
+/// Lays out a wire one step at a time, starting from the central port.
+#[derive(Default)]
+pub struct Runner {
+    path: Vec<Segment>,
+    cursor: Point,
+    steps: u64,
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follow the next step of the route. Fails if the wire strays further
+    /// than `MAX_COORDINATE` from the central port, or grows longer than
+    /// `MAX_STEPS`.
+    pub fn follow(&mut self, route: Route) -> Result<(), String> {
+        let (dx, dy, length) = match route {
+            Route::Up(u) => (0, 1, u),
+            Route::Down(d) => (0, -1, d),
+            Route::Left(l) => (-1, 0, l),
+            Route::Right(r) => (1, 0, r),
+        };
+
+        // Work in i128 so a wire that strays too far can't wrap around
+        let x = i128::from(self.cursor.x) + dx * i128::from(length);
+        let y = i128::from(self.cursor.y) + dy * i128::from(length);
+        let limit = i128::from(MAX_COORDINATE);
+        if x.abs() > limit || y.abs() > limit {
+            return Err(format!(
+                "The wire strays too far from the central port, to ({}, {})",
+                x, y
+            ));
+        }
+        let steps = self.steps + u64::from(length);
+        if steps > MAX_STEPS {
+            return Err(format!("The wire is longer than {} steps", MAX_STEPS));
+        }
+
+        let next = Point {
+            x: x as i64,
+            y: y as i64,
+        };
+        self.path.push(Segment(self.cursor, next));
+        self.cursor = next;
+        self.steps = steps;
+        Ok(())
+    }
+
+    pub fn finish(self) -> Vec<Segment> {
+        self.path
+    }
+}
+
+/// Lay a route out as the segments the wire covers.
+pub fn trace(routes: &[Route]) -> Result<Vec<Segment>, String> {
+    let mut runner = Runner::new();
+    for route in routes {
+        runner.follow(*route)?;
+    }
+    Ok(runner.finish())
+}
+
+// Every point where the wires cross or run along each other, along with
+// the combined steps both wires take to first reach it. O(n*m)
+fn crossings(segments1: &[Segment], segments2: &[Segment], rules: Rules) -> Vec<(Point, u64)> {
+    let mut crosses = vec![];
+    let mut s1sum = 0;
+    for s1 in segments1 {
+        let mut s2sum = 0;
+        for s2 in segments2 {
+            let overlaps = s1.overlaps(s2, rules);
+            for (p, s1dist, s2dist) in s1.crosses(s2, rules).into_iter().chain(overlaps) {
+                crosses.push((p, s1sum + s1dist, s2sum + s2dist));
+            }
+
+            s2sum += s2.length();
+        }
+
+        s1sum += s1.length();
+    }
+
+    first_visits(crosses, rules)
+}
+
+// A crossing, and the steps each wire takes to reach it that time
+type Visit = (Point, u64, u64);
+
+// Combine the visits to each point, in the order they're first seen. A wire
+// can loop back through the same crossing, so each wire's steps are counted
+// from the first time that wire gets there, even when the other wire took
+// longer that time.
+fn first_visits(visits: Vec<Visit>, rules: Rules) -> Vec<(Point, u64)> {
+    let mut firsts: Vec<Visit> = vec![];
+    let mut seen: HashMap<Point, usize> = HashMap::new();
+    for (p, steps1, steps2) in visits.into_iter().filter(|(p, ..)| rules.allows(p)) {
+        match seen.get(&p) {
+            Some(&i) => {
+                let first = &mut firsts[i];
+                first.1 = first.1.min(steps1);
+                first.2 = first.2.min(steps2);
+            }
+            None => {
+                seen.insert(p, firsts.len());
+                firsts.push((p, steps1, steps2));
+            }
+        }
+    }
+    firsts
+        .into_iter()
+        .map(|(p, steps1, steps2)| (p, steps1 + steps2))
+        .collect()
+}
+
+// A segment along with how many steps the wire takes before reaching it
+struct Placed<'a> {
+    segment: &'a Segment,
+    bounds: Bounds,
+    steps: u64,
+}
+
+// Split a wire into its horizontal and vertical segments, with the
+// verticals sorted by x
+fn place(segments: &[Segment]) -> (Vec<Placed<'_>>, Vec<Placed<'_>>) {
+    let mut horizontals = vec![];
+    let mut verticals = vec![];
+    let mut steps = 0;
+    for segment in segments {
+        let (polarity, bounds) = segment.polarity_and_bounds();
+        let placed = Placed {
+            segment,
+            bounds,
+            steps,
+        };
+        match polarity {
+            Polarity::Horizontal => horizontals.push(placed),
+            Polarity::Vertical => verticals.push(placed),
+        }
+        steps += segment.length();
+    }
+    verticals.sort_by_key(|v| v.bounds.bar);
+    (horizontals, verticals)
+}
+
+// Crossings of one wire's horizontals with the other's verticals. Only the
+// verticals within each horizontal's x range are checked, found by binary
+// search.
+fn sorted_crossings(horizontals: &[Placed], verticals: &[Placed], rules: Rules) -> Vec<Visit> {
+    let mut crosses = vec![];
+    for h in horizontals {
+        let (low, high) = rules.span(&h.bounds);
+        let start = verticals.partition_point(|v| v.bounds.bar < low);
+        for v in verticals[start..]
+            .iter()
+            .take_while(|v| v.bounds.bar <= high)
+        {
+            let (v_low, v_high) = rules.span(&v.bounds);
+            if h.bounds.bar < v_low || h.bounds.bar > v_high {
+                continue;
+            }
+
+            crosses.push(crossing(h, v));
+        }
+    }
+    crosses
+}
+
+// Where a horizontal crosses a vertical, and the steps along the horizontal
+// then the vertical to get there
+fn crossing(h: &Placed, v: &Placed) -> Visit {
+    let p = Point {
+        x: v.bounds.bar,
+        y: h.bounds.bar,
+    };
+    (
+        p,
+        h.steps + h.segment.0.flat_distance_to(&p),
+        v.steps + v.segment.0.flat_distance_to(&p),
+    )
+}
+
+// Every crossing, checking only the verticals each horizontal spans
+fn sweep(segments1: &[Segment], segments2: &[Segment], rules: Rules) -> Vec<(Point, u64)> {
+    let (h1s, v1s) = place(segments1);
+    let (h2s, v2s) = place(segments2);
+    let mut crosses = sorted_crossings(&h1s, &v2s, rules);
+    crosses.extend(
+        sorted_crossings(&h2s, &v1s, rules)
+            .into_iter()
+            .map(|(p, steps2, steps1)| (p, steps1, steps2)),
+    );
+    crosses.extend(sorted_overlaps(Polarity::Horizontal, &h1s, &h2s, rules));
+    crosses.extend(sorted_overlaps(Polarity::Vertical, &v1s, &v2s, rules));
+    first_visits(crosses, rules)
+}
+
+// Where one wire runs along the other. Once the segments are sorted by the
+// line they're on and then where they start, each only needs comparing with
+// the few after it that start before it ends.
+fn sorted_overlaps(
+    polarity: Polarity,
+    placed1: &[Placed],
+    placed2: &[Placed],
+    rules: Rules,
+) -> Vec<Visit> {
+    let mut placed = placed1
+        .iter()
+        .map(|p| (0, p))
+        .chain(placed2.iter().map(|p| (1, p)))
+        .collect::<Vec<_>>();
+    placed.sort_by_key(|(_, p)| (p.bounds.bar, p.bounds.low));
+
+    let mut visits = vec![];
+    for (i, &(wire, a)) in placed.iter().enumerate() {
+        let along = placed[i + 1..]
+            .iter()
+            .take_while(|(_, b)| b.bounds.bar == a.bounds.bar && b.bounds.low <= a.bounds.high);
+        for &(other, b) in along {
+            if other == wire {
+                continue;
+            }
+            let (first, second) = if wire == 0 { (a, b) } else { (b, a) };
+            for p in overlap(polarity, &first.bounds, &second.bounds, rules) {
+                visits.push((
+                    p,
+                    first.steps + first.segment.0.flat_distance_to(&p),
+                    second.steps + second.segment.0.flat_distance_to(&p),
+                ));
+            }
+        }
+    }
+    visits
+}
+
+// Something the sweep line passes. Horizontals start and end where the
+// rules allow meeting them, so at the same x they start before verticals are
+// checked and end after.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Event {
+    /// A horizontal, by index
+    Start(usize),
+    /// A wire and the index of one of its verticals
+    Check(usize, usize),
+    /// A horizontal, by index
+    End(usize),
+}
+
+// Sweep a line across x, keeping the horizontals it currently crosses in a
+// tree ordered by y, so each vertical only visits the horizontals in its y
+// range. O((n + m) log(n + m) + k)
+fn sweep_line(segments1: &[Segment], segments2: &[Segment], rules: Rules) -> Vec<(Point, u64)> {
+    let wires = [place(segments1), place(segments2)];
+    let horizontals = wires
+        .iter()
+        .enumerate()
+        .flat_map(|(wire, (hs, _))| hs.iter().map(move |h| (wire, h)))
+        .collect::<Vec<_>>();
+
+    let mut events = vec![];
+    for (i, (_, h)) in horizontals.iter().enumerate() {
+        let (low, high) = rules.span(&h.bounds);
+        if low <= high {
+            events.push((low, Event::Start(i)));
+            events.push((high, Event::End(i)));
+        }
+    }
+    for (wire, (_, vs)) in wires.iter().enumerate() {
+        for (i, v) in vs.iter().enumerate() {
+            events.push((v.bounds.bar, Event::Check(wire, i)));
+        }
+    }
+    events.sort_unstable();
+
+    let mut active = BTreeSet::new();
+    let mut crosses = vec![];
+    for (_, event) in events {
+        match event {
+            Event::Start(i) => {
+                active.insert((horizontals[i].1.bounds.bar, i));
+            }
+            Event::End(i) => {
+                active.remove(&(horizontals[i].1.bounds.bar, i));
+            }
+            Event::Check(wire, i) => {
+                let v = &wires[wire].1[i];
+                let (low, high) = rules.span(&v.bounds);
+                if low > high {
+                    continue;
+                }
+                for &(_, h) in active.range((low, 0)..=(high, usize::MAX)) {
+                    let (other, h) = horizontals[h];
+                    if other == wire {
+                        continue;
+                    }
+                    let (p, h_steps, v_steps) = crossing(h, v);
+                    if other == 0 {
+                        crosses.push((p, h_steps, v_steps));
+                    } else {
+                        crosses.push((p, v_steps, h_steps));
+                    }
+                }
+            }
+        }
+    }
+
+    let [(h1s, v1s), (h2s, v2s)] = &wires;
+    crosses.extend(sorted_overlaps(Polarity::Horizontal, h1s, h2s, rules));
+    crosses.extend(sorted_overlaps(Polarity::Vertical, v1s, v2s, rules));
+    first_visits(crosses, rules)
+}
+
+// Every point a wire reaches, and the steps it takes to first get there.
+// Unless the rules allow touching, the points a wire turns or ends on are
+// left out.
+fn walk(segments: &[Segment], rules: Rules) -> HashMap<Point, u64> {
+    let mut visits = HashMap::new();
+    let mut steps = 0;
+    for segment in segments {
+        let Segment(from, to) = segment;
+        let (dx, dy) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+        let length = segment.length();
+        let along = if rules.endpoints {
+            0..=length
+        } else {
+            1..=length.saturating_sub(1)
+        };
+        for t in along {
+            let p = Point {
+                x: from.x + dx * t as i64,
+                y: from.y + dy * t as i64,
+            };
+            visits.entry(p).or_insert(steps + t);
+        }
+        steps += length;
+    }
+    visits
+}
+
+// Every point both wires reach, found by walking them a step at a time.
+// Far too slow for real inputs, but simple enough to trust.
+fn grid(segments1: &[Segment], segments2: &[Segment], rules: Rules) -> Vec<(Point, u64)> {
+    let first = walk(segments1, rules);
+    walk(segments2, rules)
+        .into_iter()
+        .filter(|(p, _)| rules.allows(p))
+        .filter_map(|(p, steps2)| first.get(&p).map(|steps1| (p, steps1 + steps2)))
+        .collect()
+}
+
+/// How to find where the wires cross.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Algorithm {
+    /// Check every pair of segments. O(n*m)
+    Naive,
+    /// Walk every step of both wires, looking for points they share
+    Grid,
+    /// Only check the vertical segments each horizontal one spans
+    #[default]
+    Sweep,
+    /// Sweep a line across the wires, looking up the horizontal segments it
+    /// crosses in a tree
+    Tree,
+    /// Run every algorithm, failing if they disagree
+    Check,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "naive" => Ok(Algorithm::Naive),
+            "grid" => Ok(Algorithm::Grid),
+            "sweep" => Ok(Algorithm::Sweep),
+            "tree" => Ok(Algorithm::Tree),
+            "check" => Ok(Algorithm::Check),
+            _ => Err(format!(
+                "Unknown algorithm {}, expected naive, grid, sweep, tree or check",
+                s
+            )),
+        }
+    }
+}
+
+// The smallest manhattan distance and combined steps of any crossing
+fn minima(crosses: &[(Point, u64)]) -> Result<(u64, u64), String> {
+    let min_manhattan = crosses.iter().map(|(p, _)| p.manhattan_distance()).min();
+    let min_sum_dist = crosses.iter().map(|&(_, steps)| steps).min();
+    min_manhattan
+        .zip(min_sum_dist)
+        .ok_or_else(|| "The wires never cross".to_owned())
+}
+
+// Where two wires cross, found with the given algorithm
+fn find_crossings(
+    segments1: &[Segment],
+    segments2: &[Segment],
+    algorithm: Algorithm,
+    rules: Rules,
+) -> Result<Vec<(Point, u64)>, String> {
+    match algorithm {
+        Algorithm::Naive => Ok(crossings(segments1, segments2, rules)),
+        Algorithm::Grid => Ok(grid(segments1, segments2, rules)),
+        Algorithm::Sweep => Ok(sweep(segments1, segments2, rules)),
+        Algorithm::Tree => Ok(sweep_line(segments1, segments2, rules)),
+        Algorithm::Check => {
+            let naive = crossings(segments1, segments2, rules);
+            let others = [
+                ("grid", grid(segments1, segments2, rules)),
+                ("sweep", sweep(segments1, segments2, rules)),
+                ("tree", sweep_line(segments1, segments2, rules)),
+            ];
+            for (name, other) in &others {
+                if minima(&naive) != minima(other) {
+                    return Err(format!(
+                        "Algorithms disagree: naive found {:?}, {} found {:?}",
+                        minima(&naive),
+                        name,
+                        minima(other)
+                    ));
+                }
+            }
+            Ok(naive)
+        }
+    }
+}
+
+// Every pair of wires, each pair once
+fn pairs(wires: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..wires).flat_map(move |i| (i + 1..wires).map(move |j| (i, j)))
+}
+
+/// Every wire, laid out as segments, and the rules for where they meet.
+pub struct Wires {
+    segments: Vec<Vec<Segment>>,
+    rules: Rules,
+}
+
+/// A place where two wires cross.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Intersection {
+    /// The wires that cross, in the order they were given
+    pub wires: (usize, usize),
+    pub point: Point,
+    /// From the central port
+    pub manhattan: u64,
+    /// How far both wires travel to first reach this point
+    pub combined_steps: u64,
+}
+
+impl Wires {
+    /// Lay out the routes of at least two wires.
+    pub fn new(routes: &[Vec<Route>]) -> Result<Wires, String> {
+        routes
+            .iter()
+            .enumerate()
+            .map(|(i, route)| trace(route).map_err(|e| format!("Wire {}: {}", i + 1, e)))
+            .collect::<Result<_, _>>()
+            .and_then(Wires::from_segments)
+    }
+
+    fn from_segments(segments: Vec<Vec<Segment>>) -> Result<Wires, String> {
+        if segments.len() < 2 {
+            return Err(format!(
+                "Expected at least two wire routes, found {}",
+                segments.len()
+            ));
+        }
+        Ok(Wires {
+            segments,
+            rules: Rules::default(),
+        })
+    }
+
+    /// Use different rules for which places the wires meet count.
+    pub fn with_rules(self, rules: Rules) -> Wires {
+        Wires { rules, ..self }
+    }
+
+    /// The closest crossing to the origin, and the crossing reached in the
+    /// fewest combined steps. Only two wires cross at a time, so with more
+    /// than two wires every pair is checked.
+    pub fn solve(&self, algorithm: Algorithm) -> Result<(u64, u64), String> {
+        let mut crosses = vec![];
+        let segments = &self.segments;
+        for (i, j) in pairs(segments.len()) {
+            crosses.extend(find_crossings(
+                &segments[i],
+                &segments[j],
+                algorithm,
+                self.rules,
+            )?);
+        }
+        minima(&crosses)
+    }
+
+    /// Every place any two wires cross, once per pair of wires. Each pair's
+    /// intersections are listed in the order the first wire reaches them.
+    pub fn intersections(&self) -> Vec<Intersection> {
+        let segments = &self.segments;
+        pairs(segments.len())
+            .flat_map(|(i, j)| {
+                crossings(&segments[i], &segments[j], self.rules)
+                    .into_iter()
+                    .map(move |(point, combined_steps)| Intersection {
+                        wires: (i, j),
+                        point,
+                        manhattan: point.manhattan_distance(),
+                        combined_steps,
+                    })
+            })
+            .collect()
+    }
+
+    /// How often each wire crosses itself, and its tightest loop.
+    pub fn loops(&self) -> Vec<Loops> {
+        loops::find(self)
+    }
+
+    /// Draw the wires as an SVG image.
+    pub fn svg(&self) -> String {
+        svg::render(self)
+    }
+
+    /// Draw the wires as a PNG, for layouts too big for an SVG viewer.
+    pub fn png(&self, raster: &Raster) -> Result<Vec<u8>, String> {
+        raster.render(self)
+    }
+}
+
+/// Parse the wire routes, one comma separated route per line. There must be
+/// at least two wires.
+fn parse(input: &str) -> Result<Wires, String> {
+    let routes = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            parse_route(line).map_err(|e| format!("Error parsing line {}: {}", i + 1, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Wires::new(&routes)
+}
+
+/// Parse one wire's route, such as `R8,U5,L5,D3`.
+pub fn parse_route(line: &str) -> Result<Vec<Route>, RouteError> {
+    line.split(',').map(|s| s.trim().parse()).collect()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Wires;
+
+    fn parse(input: &str) -> Result<Wires, String> {
+        parse(input)
+    }
+
+    fn part1(wires: &Wires) -> Result<String, String> {
+        let (min_manhattan, _) = wires.solve(Algorithm::default())?;
+        Ok(min_manhattan.to_string())
+    }
+
+    fn part2(wires: &Wires) -> Result<String, String> {
+        let (_, min_sum_dist) = wires.solve(Algorithm::default())?;
+        Ok(min_sum_dist.to_string())
+    }
+}
+
+/// Report every intersection of each pair of wires, in the order the first
+/// wire of the pair reaches them.
+pub fn explain(input: &str, reporter: &mut dyn Reporter) -> Result<(), String> {
+    let wires = parse(input)?;
+    let intersections = wires.intersections();
+    for i in &intersections {
+        // Only name the wires when there are more than two to tell apart
+        let label = if wires.segments.len() == 2 {
+            "intersection".to_owned()
+        } else {
+            format!(
+                "intersection of wires {} and {}",
+                i.wires.0 + 1,
+                i.wires.1 + 1
+            )
+        };
+        reporter.report(
+            &label,
+            &format!(
+                "({}, {}), manhattan distance {}, combined steps {}",
+                i.point.x, i.point.y, i.manhattan, i.combined_steps
+            ),
+        );
+    }
+    reporter.report("intersections found", &intersections.len().to_string());
+    Ok(())
+}
+
+aoc_runner::register!(year: 2019, day: 3, solution: Day3, explain: explain);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    const ALGORITHMS: [Algorithm; 5] = [
+        Algorithm::Naive,
+        Algorithm::Grid,
+        Algorithm::Sweep,
+        Algorithm::Tree,
+        Algorithm::Check,
+    ];
+
+    fn solve(routes: &[Vec<Route>], algorithm: Algorithm) -> Result<(u64, u64), String> {
+        Wires::new(routes)?.solve(algorithm)
+    }
+
+    // Plain geometry, where the central port is like any other point
+    const GEOMETRY: Rules = Rules {
+        endpoints: false,
+        origin: true,
+    };
+
+    macro_rules! segments_cross {
+        {(($x1:expr, $y1:expr), ($x2:expr, $y2:expr)) <-|-> (($x3:expr, $y3:expr), ($x4:expr, $y4:expr)) @ ($c1:expr, $c2:expr)} => {
+            let segment1 = Segment(Point{x: $x1, y: $y1}, Point{x: $x2, y: $y2});
+            let segment2 = Segment(Point{x: $x3, y: $y3}, Point{x: $x4, y: $y4});
+            let cross = segment1.crosses(&segment2, GEOMETRY);
+            assert!(cross.is_some());
+            let (cross, ..) = cross.unwrap();
+            assert_eq!(cross.x, $c1);
+            assert_eq!(cross.y, $c2);
+        };
+        {(($x1:expr, $y1:expr), ($x2:expr, $y2:expr)) <---> (($x3:expr, $y3:expr), ($x4:expr, $y4:expr))} => {
+            let segment1 = Segment(Point{x: $x1, y: $y1}, Point{x: $x2, y: $y2});
+            let segment2 = Segment(Point{x: $x3, y: $y3}, Point{x: $x4, y: $y4});
+            assert!(segment1.crosses(&segment2, GEOMETRY).is_none());
+        };
+    }
+
+    #[test]
+    fn segments_cross() {
+        // two vertical | |
+        segments_cross!((( 0,  1), ( 0, -1)) <---> (( 0,  2), ( 0, -2)));
+        // two horizontal =
+        segments_cross!((( 1,  0), (-1,  0)) <---> (( 2,  0), (-2,  0)));
+        // too far left - |
+        segments_cross!(((-1,  0), ( 1,  0)) <---> ((-2,  1), (-2, -1)));
+        // too far right | -
+        segments_cross!(((-1,  0), ( 1,  0)) <---> (( 2,  1), ( 2, -1)));
+        // too far up |
+        //            -
+        segments_cross!((( 2, -1), ( 2,  1)) <---> ((-1,  0), ( 1,  0)));
+        // too far down -
+        //              |
+        segments_cross!(((-2, -1), (-2,  1)) <---> ((-1,  0), ( 1,  0)));
+        // cross +
+        segments_cross!(((-1,  0), ( 1,  0)) <-|-> (( 0, -1), ( 0,  1)) @ (0, 0));
+        // on-edge should not cross -|
+        segments_cross!(((-1,  0), ( 1,  0)) <---> (( 1, -1), ( 1,  1)));
+    }
+
+    macro_rules! assert_segments_eq {
+        ($seg:expr, {($x1:expr, $y1:expr), ($x2:expr, $y2:expr)}) => {
+            assert_eq!($seg.0.x, $x1);
+            assert_eq!($seg.0.y, $y1);
+            assert_eq!($seg.1.x, $x2);
+            assert_eq!($seg.1.y, $y2);
+        };
+    }
+
+    #[test]
+    fn runner() {
+        let mut runner = Runner::new();
+        runner.follow(Route::Up(4)).unwrap();
+        runner.follow(Route::Right(4)).unwrap();
+        runner.follow(Route::Down(4)).unwrap();
+        runner.follow(Route::Left(4)).unwrap();
+        let path = runner.finish();
+        assert_segments_eq!(path[0], {(0, 0), (0, 4)});
+        assert_segments_eq!(path[1], {(0, 4), (4, 4)});
+        assert_segments_eq!(path[2], {(4, 4), (4, 0)});
+        assert_segments_eq!(path[3], {(4, 0), (0, 0)});
+    }
+
+    #[test]
+    fn stays_on_the_grid() {
+        let mut runner = Runner {
+            cursor: Point {
+                x: MAX_COORDINATE - 5,
+                y: -MAX_COORDINATE,
+            },
+            ..Runner::default()
+        };
+        runner.follow(Route::Right(5)).unwrap();
+        assert_eq!(
+            runner.follow(Route::Right(1)),
+            Err(format!(
+                "The wire strays too far from the central port, to ({}, {})",
+                i128::from(MAX_COORDINATE) + 1,
+                -MAX_COORDINATE
+            ))
+        );
+        assert!(runner.follow(Route::Down(u32::MAX)).is_err());
+        runner.follow(Route::Up(u32::MAX)).unwrap();
+
+        let mut runner = Runner {
+            steps: MAX_STEPS - 3,
+            ..Runner::default()
+        };
+        runner.follow(Route::Up(3)).unwrap();
+        assert!(runner.follow(Route::Up(1)).is_err());
+        // Nothing changes after a step fails
+        assert_eq!(runner.finish().len(), 1);
+
+        // Crossings at the very edge of the grid are still measured exactly
+        let corner = Point {
+            x: MAX_COORDINATE,
+            y: -MAX_COORDINATE,
+        };
+        assert_eq!(corner.manhattan_distance(), 2 * MAX_COORDINATE as u64);
+        let far = Point {
+            x: -MAX_COORDINATE,
+            y: MAX_COORDINATE,
+        };
+        assert_eq!(corner.flat_distance_to(&far), 4 * MAX_COORDINATE as u64);
+        let horizontal = Segment(
+            Point {
+                x: -MAX_COORDINATE,
+                y: MAX_COORDINATE - 1,
+            },
+            Point {
+                x: MAX_COORDINATE,
+                y: MAX_COORDINATE - 1,
+            },
+        );
+        let vertical = Segment(
+            Point {
+                x: MAX_COORDINATE - 1,
+                y: MAX_COORDINATE,
+            },
+            Point {
+                x: MAX_COORDINATE - 1,
+                y: -MAX_COORDINATE,
+            },
+        );
+        assert_eq!(horizontal.length(), 2 * MAX_COORDINATE as u64);
+        assert_eq!(
+            horizontal.crosses(&vertical, Rules::default()),
+            Some((
+                Point {
+                    x: MAX_COORDINATE - 1,
+                    y: MAX_COORDINATE - 1
+                },
+                2 * MAX_COORDINATE as u64 - 1,
+                1
+            ))
+        );
+    }
+
+    macro_rules! route_vec {
+        (@route R $num:expr) => {
+            Route::Right($num)
+        };
+        (@route L $num:expr) => {
+            Route::Left($num)
+        };
+        (@route U $num:expr) => {
+            Route::Up($num)
+        };
+        (@route D $num:expr) => {
+            Route::Down($num)
+        };
+        ([$($route:ident $num:expr,)+]) => {
+            vec![$(route_vec!(@route $route $num),)+]
+        };
+    }
+
+    macro_rules! distance_of {
+        ([$($route1:ident $num1:expr),+], [$($route2:ident $num2:expr),+] = ($d1:expr, $d2:expr)) => {
+            let routes = [route_vec!([$($route1 $num1,)+]), route_vec!([$($route2 $num2,)+])];
+            for &algorithm in &ALGORITHMS {
+                assert_eq!(solve(&routes, algorithm), Ok(($d1, $d2)));
+            }
+        }
+    }
+
+    #[test]
+    fn distance_tests() {
+        distance_of!(
+            [R 75, D 30, R 83, U 83, L 12, D 49, R 71, U  7, L 72],
+            [U 62, R 66, U 55, R 34, D 71, R 55, D 58, R 83] = (159, 610));
+        distance_of!(
+            [R 98, U 47, R 26, D 63, R 33, U 87, L 62, D 20, R 33, U 53, R 51],
+            [U 98, R 91, D 20, R 16, D 67, R 40, U  7, R 15, U  6, R  7] = (135, 410));
+    }
+
+    #[test]
+    fn dense_crossings() {
+        // A pound sign: two horizontals crossing two verticals, where the
+        // second horizontal starts left of the first
+        let route1 = route_vec!([R 10, U 2, L 12, U 2, R 12,]);
+        let route2 = route_vec!([U 1, R 3, U 5, R 3, D 5,]);
+        let routes = [route1, route2];
+        assert_eq!(
+            solve(&routes, Algorithm::Check),
+            solve(&routes, Algorithm::Naive)
+        );
+        assert_eq!(solve(&routes, Algorithm::Sweep), Ok((5, 24)));
+        assert_eq!(solve(&routes, Algorithm::Tree), Ok((5, 24)));
+
+        // Segments that go nowhere, and segments that only touch
+        let routes = [
+            route_vec!([R 2, U 0, R 2, U 2, L 2, D 4,]),
+            route_vec!([U 1, R 1, U 0, R 2, D 2, R 2,]),
+        ];
+        for &algorithm in &ALGORITHMS {
+            assert_eq!(solve(&routes, algorithm), Ok((3, 8)));
+        }
+    }
+
+    // Short routes on a small grid, so the wires cross often and shrinking
+    // quickly reaches something readable
+    fn routes() -> impl Strategy<Value = Vec<Route>> {
+        let step = (0..4, 0..12u32).prop_map(|(direction, length)| match direction {
+            0 => Route::Up(length),
+            1 => Route::Down(length),
+            2 => Route::Left(length),
+            _ => Route::Right(length),
+        });
+        prop::collection::vec(step, 1..24)
+    }
+
+    proptest! {
+        #[test]
+        fn algorithms_agree(
+            route1 in routes(),
+            route2 in routes(),
+            endpoints in any::<bool>(),
+            origin in any::<bool>(),
+        ) {
+            let routes = [route1, route2];
+            let rules = Rules { endpoints, origin };
+            let wires = Wires::new(&routes).unwrap().with_rules(rules);
+            // Walking the grid is obviously right, so everything else is
+            // measured against it
+            let grid = wires.solve(Algorithm::Grid);
+            prop_assert_eq!(&wires.solve(Algorithm::Naive), &grid);
+            prop_assert_eq!(&wires.solve(Algorithm::Sweep), &grid);
+            prop_assert_eq!(&wires.solve(Algorithm::Tree), &grid);
+
+            // Beyond the minima, every backend finds the same points
+            let segments = [trace(&routes[0]).unwrap(), trace(&routes[1]).unwrap()];
+            let points = |algorithm| {
+                find_crossings(&segments[0], &segments[1], algorithm, rules)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(p, steps)| (p.x, p.y, steps))
+                    .collect::<BTreeSet<_>>()
+            };
+            prop_assert_eq!(points(Algorithm::Sweep), points(Algorithm::Naive));
+            prop_assert_eq!(points(Algorithm::Tree), points(Algorithm::Naive));
+        }
+    }
+
+    #[test]
+    fn parses_routes() {
+        assert_eq!(
+            parse_route("R8, U5,L5,D0"),
+            Ok(vec![
+                Route::Right(8),
+                Route::Up(5),
+                Route::Left(5),
+                Route::Down(0)
+            ])
+        );
+        assert_eq!(parse_route("R8,"), Err(RouteError::Empty));
+        assert_eq!(parse_route("X8"), Err(RouteError::Direction('X')));
+        assert_eq!(parse_route("é8"), Err(RouteError::Direction('é')));
+        assert_eq!(
+            "U-1".parse::<Route>().unwrap_err().to_string(),
+            "Bad route length: invalid digit found in string"
+        );
+        assert_eq!(
+            parse("R8,U5\nU7,Q6").err(),
+            Some("Error parsing line 2: Unknown direction 'Q', expected U, D, L or R".to_owned())
+        );
+    }
+
+    #[test]
+    fn overlapping_wires() {
+        let along = Segment(Point { x: 0, y: 0 }, Point { x: 10, y: 0 });
+        let back = Segment(Point { x: 7, y: 0 }, Point { x: 3, y: 0 });
+        assert_eq!(
+            along.overlaps(&back, Rules::default()),
+            vec![(Point { x: 4, y: 0 }, 4, 3), (Point { x: 6, y: 0 }, 6, 1),]
+        );
+        assert!(along.crosses(&back, Rules::default()).is_none());
+        // Meeting end to end is only touching
+        let next = Segment(Point { x: 10, y: 0 }, Point { x: 12, y: 0 });
+        assert!(along.overlaps(&next, Rules::default()).is_empty());
+        // The central port is skipped, but the points either side of it count
+        let through = Segment(Point { x: -5, y: 0 }, Point { x: 5, y: 0 });
+        assert_eq!(
+            through
+                .overlaps(
+                    &Segment(Point { x: 2, y: 0 }, Point { x: -2, y: 0 }),
+                    Rules::default(),
+                )
+                .into_iter()
+                .map(|(p, ..)| p.x)
+                .collect::<Vec<_>>(),
+            vec![-1, 1]
+        );
+
+        // The second wire comes down onto the first, then runs along it
+        let routes = [route_vec!([R 10,]), route_vec!([U 2, R 3, D 2, R 4,])];
+        for &algorithm in &ALGORITHMS {
+            assert_eq!(solve(&routes, algorithm), Ok((4, 12)));
+        }
+        // And the other way, where every shared point takes as many steps
+        let routes = [route_vec!([R 10,]), route_vec!([U 2, R 8, D 2, L 5,])];
+        for &algorithm in &ALGORITHMS {
+            assert_eq!(solve(&routes, algorithm), Ok((4, 20)));
+        }
+    }
+
+    #[test]
+    fn touching_wires() {
+        // The second wire ends right on the first
+        let wires = || Wires::new(&[route_vec!([R 5, U 5,]), route_vec!([U 3, R 5,])]).unwrap();
+        assert_eq!(
+            wires().solve(Algorithm::Check),
+            Err("The wires never cross".to_owned())
+        );
+        let endpoints = Rules {
+            endpoints: true,
+            origin: false,
+        };
+        for &algorithm in &ALGORITHMS {
+            assert_eq!(wires().with_rules(endpoints).solve(algorithm), Ok((8, 16)));
+        }
+        // Every wire touches every other at the central port
+        let origin = Rules {
+            origin: true,
+            ..endpoints
+        };
+        assert_eq!(
+            wires().with_rules(origin).solve(Algorithm::Check),
+            Ok((0, 0))
+        );
+
+        // Wires that cross right at the central port
+        let horizontal = Segment(Point { x: -2, y: 0 }, Point { x: 2, y: 0 });
+        let vertical = Segment(Point { x: 0, y: 2 }, Point { x: 0, y: -2 });
+        assert!(horizontal.crosses(&vertical, Rules::default()).is_none());
+        assert_eq!(
+            horizontal.crosses(
+                &vertical,
+                Rules {
+                    endpoints: false,
+                    origin: true,
+                }
+            ),
+            Some((Point { x: 0, y: 0 }, 2, 2))
+        );
+    }
+
+    #[test]
+    fn loops_back_through_crossing() {
+        // Both wires pass (0, 5) twice. The first wire gets there in 5 steps
+        // the first time, when the second wire has taken 13, and the second
+        // wire gets there in 5 steps when the first has taken 19.
+        let routes = [
+            route_vec!([U 10, L 2, D 5, R 4,]),
+            route_vec!([U 8, L 1, D 3, R 3,]),
+        ];
+        let segments = [trace(&routes[0]).unwrap(), trace(&routes[1]).unwrap()];
+        for &algorithm in &ALGORITHMS {
+            let crosses =
+                find_crossings(&segments[0], &segments[1], algorithm, Rules::default()).unwrap();
+            assert!(crosses.contains(&(Point { x: 0, y: 5 }, 10)));
+        }
+    }
+
+    #[test]
+    fn parses_algorithms() {
+        assert_eq!("sweep".parse(), Ok(Algorithm::Sweep));
+        assert_eq!("naive".parse(), Ok(Algorithm::Naive));
+        assert_eq!("tree".parse(), Ok(Algorithm::Tree));
+        assert_eq!("grid".parse(), Ok(Algorithm::Grid));
+        assert!("fast".parse::<Algorithm>().is_err());
+        assert_eq!(
+            solve(
+                &[vec![Route::Up(1)], vec![Route::Down(1)]],
+                Algorithm::Sweep
+            ),
+            Err("The wires never cross".to_owned())
+        );
+    }
+
+    #[test]
+    fn many_wires() {
+        // The third wire only crosses the first, but reaches that crossing
+        // sooner than the first two reach each other
+        let wires = parse("R8,U5,L5,D3\nU7,R6,D4,L4\n\nD1,R10,U3,L3\n").unwrap();
+        for &algorithm in &ALGORITHMS {
+            assert_eq!(wires.solve(algorithm), Ok((6, 26)));
+        }
+        assert!(parse("R8,U5,L5,D3\n").is_err());
+        assert_eq!(
+            wires.intersections()[2],
+            Intersection {
+                wires: (0, 2),
+                point: Point { x: 8, y: 2 },
+                manhattan: 10,
+                combined_steps: 26,
+            }
+        );
+
+        let mut steps = vec![];
+        explain("R8,U5,L5,D3\nU7,R6,D4,L4\nD1,R10,U3,L3", &mut steps).unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(
+            steps[2],
+            (
+                "intersection of wires 1 and 3".to_owned(),
+                "(8, 2), manhattan distance 10, combined steps 26".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn explains_intersections() {
+        let mut steps = vec![];
+        explain("R8,U5,L5,D3\nU7,R6,D4,L4", &mut steps).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    "intersection".to_owned(),
+                    "(6, 5), manhattan distance 11, combined steps 30".to_owned()
+                ),
+                (
+                    "intersection".to_owned(),
+                    "(3, 3), manhattan distance 6, combined steps 40".to_owned()
+                ),
+                ("intersections found".to_owned(), "2".to_owned()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Point, Wires};
+use std::collections::HashSet;
+
+/// A place a wire comes back to, and the steps it takes to get there each
+/// time.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SelfCrossing {
+    pub point: Point,
+    pub first: u64,
+    pub second: u64,
+}
+
+impl SelfCrossing {
+    /// How many steps the wire takes to loop back around.
+    pub fn length(&self) -> u64 {
+        self.second - self.first
+    }
+}
+
+/// How often a wire crosses itself. Only the first time a wire reaches a
+/// crossing counts towards the signal delay, so every step of a loop is
+/// wasted.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Loops {
+    /// The places the wire crosses itself. Where it runs back along itself,
+    /// only the ends of that stretch and its closest point to the central
+    /// port count, as for crossings between wires.
+    pub crossings: usize,
+    /// The crossing where the wire loops back soonest
+    pub tightest: Option<SelfCrossing>,
+}
+
+/// Every wire's loops, in the order the wires were given.
+pub fn find(wires: &Wires) -> Vec<Loops> {
+    wires
+        .segments
+        .iter()
+        .map(|segments| {
+            let mut points = HashSet::new();
+            let mut tightest: Option<SelfCrossing> = None;
+            let mut first = 0;
+            for (i, s1) in segments.iter().enumerate() {
+                let mut second = first + s1.length();
+                for (j, s2) in segments.iter().enumerate().skip(i + 1) {
+                    let overlaps = s1.overlaps(s2, wires.rules);
+                    for (point, d1, d2) in s1.crosses(s2, wires.rules).into_iter().chain(overlaps) {
+                        // A segment always touches the next one where the
+                        // wire turns
+                        if j == i + 1 && point == s1.1 {
+                            continue;
+                        }
+                        points.insert(point);
+                        let crossing = SelfCrossing {
+                            point,
+                            first: first + d1,
+                            second: second + d2,
+                        };
+                        if tightest.is_none_or(|t| crossing.length() < t.length()) {
+                            tightest = Some(crossing);
+                        }
+                    }
+                    second += s2.length();
+                }
+                first += s1.length();
+            }
+            Loops {
+                crossings: points.len(),
+                tightest,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Rules;
+
+    #[test]
+    fn finds_loops() {
+        // The first wire crosses itself at (2, 0) and runs back along itself
+        // from (6, 1) to (6, 3). The second never does.
+        let wires = crate::parse("R6,U5,L4,D7,R4,U2,L0,U4\nU7,R6,D4,L4").unwrap();
+        let loops = find(&wires);
+        assert_eq!(
+            loops[0],
+            Loops {
+                crossings: 3,
+                tightest: Some(SelfCrossing {
+                    point: Point { x: 2, y: 0 },
+                    first: 2,
+                    second: 20,
+                }),
+            }
+        );
+        assert_eq!(loops[0].tightest.unwrap().length(), 18);
+        assert_eq!(loops[1], Loops::default());
+
+        // Turning back on itself is a loop, but turning never is, even when
+        // touching counts
+        let wires = crate::parse("R5,L3\nU1,R1").unwrap();
+        let loops = find(&wires);
+        assert_eq!(loops[0].crossings, 2);
+        assert_eq!(loops[0].tightest.unwrap().length(), 2);
+        let touching = Rules {
+            endpoints: true,
+            origin: false,
+        };
+        let loops = find(&wires.with_rules(touching));
+        assert_eq!(loops[1], Loops::default());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{Point, Wires};
+use colors::Rgb;
+use image::RgbImage;
+use std::io::Cursor;
+
+// Without a scale, the image is shrunk until its longest side fits in this
+const FIT: u64 = 1000;
+// Anything larger than this a side is more than most viewers will open
+const MAX_SIDE: u64 = 16384;
+
+/// How to draw the wires into a raster image.
+#[derive(Clone, Debug)]
+pub struct Raster {
+    /// How many grid points across each pixel covers, or `None` to fit the
+    /// wires into a thousand pixels
+    pub scale: Option<u64>,
+    pub background: Rgb,
+    /// Wires past the last color reuse them from the start
+    pub wires: Vec<Rgb>,
+    pub intersections: Rgb,
+    pub origin: Rgb,
+}
+
+impl Default for Raster {
+    fn default() -> Self {
+        // The same palette as the SVG
+        let wires = ["1f77b4", "ff7f0e", "2ca02c", "9467bd", "8c564b", "e377c2"];
+        Self {
+            scale: None,
+            background: Rgb([255, 255, 255]),
+            wires: wires.iter().map(|c| c.parse().unwrap()).collect(),
+            intersections: Rgb([214, 39, 40]),
+            origin: Rgb([0, 0, 0]),
+        }
+    }
+}
+
+impl Raster {
+    /// Draw every wire, with a dot on the origin and every place any two
+    /// wires cross, as a PNG.
+    pub fn render(&self, wires: &Wires) -> Result<Vec<u8>, String> {
+        if self.wires.is_empty() {
+            return Err("Need at least one color to draw the wires in".to_string());
+        }
+        let corners = wires.segments.iter().flatten().flat_map(|s| vec![s.0, s.1]);
+        let min_x = corners.clone().map(|p| p.x).min().unwrap_or(0);
+        let max_x = corners.clone().map(|p| p.x).max().unwrap_or(0);
+        let min_y = corners.clone().map(|p| p.y).min().unwrap_or(0);
+        let max_y = corners.map(|p| p.y).max().unwrap_or(0);
+
+        // Coordinates stay within MAX_COORDINATE, so these spans can't overflow
+        let span = (max_x - min_x).max(max_y - min_y) as u64 + 1;
+        let scale = match self.scale {
+            Some(scale) => {
+                colors::check_scale(scale)?;
+                scale
+            }
+            None => span.div_ceil(FIT),
+        };
+        // Leave room for the dots on the edge
+        let margin = 2;
+        let side = |span: i64| (span as u64 / scale) + 1 + 2 * margin;
+        let (width, height) = (side(max_x - min_x), side(max_y - min_y));
+        if width.max(height) > MAX_SIDE {
+            return Err(format!(
+                "A {}x{} image is too large, try a scale of at least {}",
+                width,
+                height,
+                span / (MAX_SIDE - 1 - 2 * margin) + 1
+            ));
+        }
+
+        // Flip y so that up is up
+        let pixel = |p: &Point| {
+            (
+                ((p.x - min_x) as u64 / scale + margin) as u32,
+                ((max_y - p.y) as u64 / scale + margin) as u32,
+            )
+        };
+        let mut image =
+            RgbImage::from_pixel(width as u32, height as u32, image::Rgb(self.background.0));
+        for (wire, color) in wires.segments.iter().zip(self.wires.iter().cycle()) {
+            for segment in wire {
+                let (x1, y1) = pixel(&segment.0);
+                let (x2, y2) = pixel(&segment.1);
+                for x in x1.min(x2)..=x1.max(x2) {
+                    for y in y1.min(y2)..=y1.max(y2) {
+                        image.put_pixel(x, y, image::Rgb(color.0));
+                    }
+                }
+            }
+        }
+
+        let mut dot = |p: &Point, color: Rgb| {
+            let (x, y) = pixel(p);
+            for x in x - 1..=x + 1 {
+                for y in y - 1..=y + 1 {
+                    image.put_pixel(x, y, image::Rgb(color.0));
+                }
+            }
+        };
+        for i in wires.intersections() {
+            dot(&i.point, self.intersections);
+        }
+        dot(&Point::default(), self.origin);
+
+        let mut png = vec![];
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .map_err(|e| format!("Failed to encode the PNG: {}", e))?;
+        Ok(png)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rasterizes_wires() {
+        let wires = crate::parse("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        let raster = Raster {
+            scale: Some(1),
+            ..Raster::default()
+        };
+        let png = raster.render(&wires).unwrap();
+        let image = image::load_from_memory(&png).unwrap().to_rgb8();
+        // Eight wide and seven high, with a margin of two all around
+        assert_eq!(image.dimensions(), (13, 12));
+        let at = |x: i64, y: i64| image.get_pixel((x + 2) as u32, (9 - y) as u32).0;
+        assert_eq!(at(5, 0), raster.wires[0].0);
+        assert_eq!(at(0, 5), raster.wires[1].0);
+        assert_eq!(at(3, 3), raster.intersections.0);
+        assert_eq!(at(6, 5), raster.intersections.0);
+        assert_eq!(at(0, 0), raster.origin.0);
+        assert_eq!(at(5, 2), raster.background.0);
+
+        // Halving the scale halves the image, less the margins
+        let half = Raster {
+            scale: Some(2),
+            ..raster.clone()
+        };
+        let png = half.render(&wires).unwrap();
+        let image = image::load_from_memory(&png).unwrap();
+        assert_eq!((image.width(), image.height()), (9, 8));
+
+        let none = Raster {
+            wires: vec![],
+            ..raster
+        };
+        assert!(none.render(&wires).is_err());
+        let huge = crate::parse("R100000\nU100000").unwrap();
+        assert!(half
+            .render(&huge)
+            .unwrap_err()
+            .contains("scale of at least 7"));
+    }
+}
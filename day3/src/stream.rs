@@ -0,0 +1,170 @@
+use crate::{Route, Runner, Wires};
+use std::{io::BufRead, mem};
+
+/// Reads the steps of every wire's route one at a time, so a huge route
+/// never has to be held in memory as a single line. Each step comes with
+/// the line it was on, counting from 1.
+pub struct RouteReader<R> {
+    reader: R,
+    line: usize,
+    token: Vec<u8>,
+    // Whether the current line has had a step or a comma yet, which tells a
+    // blank line apart from a missing step
+    started: bool,
+    done: bool,
+}
+
+impl<R: BufRead> RouteReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 1,
+            token: vec![],
+            started: false,
+            done: false,
+        }
+    }
+
+    // Parse the token read so far. Blank lines have no steps, but an empty
+    // token anywhere else is an error.
+    fn finish_token(&mut self, end_of_line: bool) -> Option<Result<(usize, Route), String>> {
+        let token = mem::take(&mut self.token);
+        let line = self.line;
+        let started = mem::replace(&mut self.started, !end_of_line);
+        if end_of_line {
+            self.line += 1;
+        }
+
+        let token = String::from_utf8_lossy(&token);
+        let token = token.trim();
+        if token.is_empty() && end_of_line && !started {
+            return None;
+        }
+        Some(
+            token
+                .parse()
+                .map(|route| (line, route))
+                .map_err(|e| format!("Error parsing line {}: {}", line, e)),
+        )
+    }
+}
+
+impl<R: BufRead> Iterator for RouteReader<R> {
+    type Item = Result<(usize, Route), String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(format!("Failed to read line {}: {}", self.line, e)));
+                }
+            };
+
+            // The end of the input ends the last line, even without a
+            // line break
+            if buf.is_empty() {
+                self.done = true;
+                return self.finish_token(true);
+            }
+
+            match buf.iter().position(|&b| b == b',' || b == b'\n') {
+                Some(i) => {
+                    let end_of_line = buf[i] == b'\n';
+                    self.token.extend_from_slice(&buf[..i]);
+                    self.reader.consume(i + 1);
+                    if let Some(step) = self.finish_token(end_of_line) {
+                        return Some(step);
+                    }
+                }
+                None => {
+                    let len = buf.len();
+                    self.token.extend_from_slice(buf);
+                    self.reader.consume(len);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Read and lay out every wire, one step at a time.
+pub fn read_wires(reader: impl BufRead) -> Result<Wires, String> {
+    let mut wires = vec![];
+    let mut runner = Runner::new();
+    let mut wire_line = None;
+    for step in RouteReader::new(reader) {
+        let (line, route) = step?;
+        if wire_line.is_some_and(|l| l != line) {
+            wires.push(mem::take(&mut runner).finish());
+        }
+        wire_line = Some(line);
+        runner
+            .follow(route)
+            .map_err(|e| format!("Wire {}: {}", wires.len() + 1, e))?;
+    }
+    if wire_line.is_some() {
+        wires.push(runner.finish());
+    }
+    Wires::from_segments(wires)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Algorithm;
+    use std::io::BufReader;
+
+    // A reader that only hands over a few bytes at a time, so steps are
+    // split between reads
+    fn read(input: &str) -> RouteReader<BufReader<&[u8]>> {
+        RouteReader::new(BufReader::with_capacity(3, input.as_bytes()))
+    }
+
+    #[test]
+    fn reads_steps() {
+        let steps = read("R75,D30 , R83\r\n\n U62,R66").collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            steps,
+            Ok(vec![
+                (1, Route::Right(75)),
+                (1, Route::Down(30)),
+                (1, Route::Right(83)),
+                (3, Route::Up(62)),
+                (3, Route::Right(66)),
+            ])
+        );
+        assert_eq!(read("").count(), 0);
+        assert_eq!(read("\n\n").count(), 0);
+
+        let errors = |input| read(input).filter_map(Result::err).collect::<Vec<_>>();
+        assert_eq!(
+            errors("R8,U5,\nU7"),
+            vec!["Error parsing line 1: Empty route".to_owned()]
+        );
+        assert_eq!(
+            errors("R8\nU7,,L2"),
+            vec!["Error parsing line 2: Empty route".to_owned()]
+        );
+        assert_eq!(
+            errors("R8\nU7,X2"),
+            vec!["Error parsing line 2: Unknown direction 'X', expected U, D, L or R".to_owned()]
+        );
+    }
+
+    #[test]
+    fn reads_wires() {
+        let input = "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\n\
+                     U98,R91,D20,R16,D67,R40,U7,R15,U6,R7\n";
+        let wires = read_wires(BufReader::with_capacity(5, input.as_bytes())).unwrap();
+        assert_eq!(wires.solve(Algorithm::Check), Ok((135, 410)));
+        assert_eq!(
+            wires.intersections(),
+            crate::parse(input).unwrap().intersections()
+        );
+
+        assert!(read_wires("R8,U5\n\n".as_bytes()).is_err());
+        assert!(read_wires("R8,U5\nU7,Q1".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{Point, Segment, Wires};
+
+// Wires past the last color reuse the palette from the start
+const COLORS: [&str; 6] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#9467bd", "#8c564b", "#e377c2",
+];
+
+// The corners of a wire, starting at the origin
+fn corners(segments: &[Segment]) -> Vec<Point> {
+    let mut points = vec![Point { x: 0, y: 0 }];
+    points.extend(segments.iter().map(|s| s.1));
+    points
+}
+
+/// Draw every wire, marking every place any two of them cross. The crossing
+/// closest to the origin is circled in red, and the one reached in the
+/// fewest combined steps in green. Wires that never cross are still drawn,
+/// just without the circles.
+pub fn render(wires: &Wires) -> String {
+    let crosses = wires.intersections();
+    let closest = crosses.iter().min_by_key(|i| i.manhattan);
+    let quickest = crosses.iter().min_by_key(|i| i.combined_steps);
+
+    let segments = &wires.segments;
+    let wires = segments.iter().map(|s| corners(s)).collect::<Vec<_>>();
+    let all = wires.iter().flatten();
+    let min_x = all.clone().map(|p| p.x).min().unwrap_or(0);
+    let max_x = all.clone().map(|p| p.x).max().unwrap_or(0);
+    let min_y = all.clone().map(|p| p.y).min().unwrap_or(0);
+    let max_y = all.map(|p| p.y).max().unwrap_or(0);
+
+    // Leave a margin so markers on the edge aren't cut off, and flip y so
+    // that up is up
+    let size = (max_x - min_x).max(max_y - min_y).max(1);
+    let stroke = size as f64 / 500.0;
+    let margin = (size / 20).max(1);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        min_x - margin,
+        -max_y - margin,
+        max_x - min_x + 2 * margin,
+        max_y - min_y + 2 * margin
+    );
+
+    for (wire, color) in wires.iter().zip(COLORS.iter().cycle()) {
+        let points = wire
+            .iter()
+            .map(|p| format!("{},{}", p.x, -p.y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        svg += &format!(
+            "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+            points, color, stroke
+        );
+    }
+
+    let circle = |p: &Point, radius: f64, style: &str| {
+        format!(
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>\n",
+            p.x,
+            -p.y,
+            radius * stroke,
+            style
+        )
+    };
+    svg += &circle(&Point { x: 0, y: 0 }, 4.0, "fill=\"black\"");
+    for i in &crosses {
+        svg += &circle(&i.point, 3.0, "fill=\"gray\"");
+    }
+    let ring = |color| {
+        format!(
+            "fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"",
+            color,
+            stroke * 2.0
+        )
+    };
+    if let (Some(closest), Some(quickest)) = (closest, quickest) {
+        svg += &circle(&closest.point, 8.0, &ring("red"));
+        svg += &circle(&quickest.point, 11.0, &ring("green"));
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn draws_wires() {
+        let wires = crate::parse("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        let svg = render(&wires);
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -8 10 9\">")
+        );
+        assert!(svg.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        // The origin, both crossings and the two highlights
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert!(svg.contains("cx=\"3\" cy=\"-3\" r=\"0.128\" fill=\"none\" stroke=\"red\""));
+        assert!(svg.contains("cx=\"6\" cy=\"-5\" r=\"0.176\" fill=\"none\" stroke=\"green\""));
+
+        let three = crate::parse("R8,U5,L5,D3\nU7,R6,D4,L4\nD1,R10,U3,L3").unwrap();
+        let svg = render(&three);
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains("stroke=\"#2ca02c\""));
+        // The third wire's crossing is the quickest
+        assert!(svg.contains("cx=\"8\" cy=\"-2\" r=\"0.22\" fill=\"none\" stroke=\"green\""));
+
+        // Parallel wires never cross, but are drawn all the same
+        let apart = crate::parse("U5\nR1,U5").unwrap();
+        let svg = render(&apart);
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains("points=\"0,0 0,-5\""));
+        assert!(svg.contains("points=\"0,0 1,0 1,-5\""));
+        // Only the origin is marked
+        assert_eq!(svg.matches("<circle").count(), 1);
+    }
+}
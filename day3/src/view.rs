@@ -0,0 +1,272 @@
+use crate::{Intersection, Point, Segment, Wires, MAX_COORDINATE};
+use std::{
+    io::{self, BufRead, Write},
+    iter,
+};
+
+const WIDTH: i64 = 79;
+const HEIGHT: i64 = 21;
+// Zoomed out this far, the window is as wide as the whole grid wires can
+// reach. Going any further would overflow working out what's on screen.
+const MAX_SCALE: i64 = 2 * MAX_COORDINATE / WIDTH;
+// Wires past the last mark reuse them from the start
+const MARKS: &[u8] = b"123456789";
+
+/// A window onto the wires, centred on a cursor that can be moved around,
+/// zoomed out, and jumped between intersections.
+pub struct Viewer<'a> {
+    wires: &'a Wires,
+    /// Closest to the central port first
+    intersections: Vec<Intersection>,
+    cursor: Point,
+    /// How many grid points across each character covers
+    scale: i64,
+    selected: Option<usize>,
+}
+
+impl<'a> Viewer<'a> {
+    pub fn new(wires: &'a Wires) -> Self {
+        let mut intersections = wires.intersections();
+        intersections.sort_by_key(|i| (i.manhattan, i.combined_steps));
+        Self {
+            wires,
+            intersections,
+            cursor: Point::default(),
+            scale: 1,
+            selected: None,
+        }
+    }
+
+    // The column and row a point falls in, if it's on screen at all
+    fn cell(&self, p: &Point) -> Option<(usize, usize)> {
+        let left = self.cursor.x - WIDTH / 2 * self.scale;
+        let top = self.cursor.y + HEIGHT / 2 * self.scale;
+        let col = (p.x - left).div_euclid(self.scale);
+        let row = (top - p.y).div_euclid(self.scale);
+        if (0..WIDTH).contains(&col) && (0..HEIGHT).contains(&row) {
+            Some((col as usize, row as usize))
+        } else {
+            None
+        }
+    }
+
+    // Mark every character a segment passes through, only walking the part
+    // of it that's on screen
+    fn draw(&self, screen: &mut [Vec<u8>], segment: &Segment, mark: u8) {
+        let Segment(from, to) = segment;
+        let reach = (WIDTH.max(HEIGHT) / 2 + 1) * self.scale;
+        let clip = |a: i64, b: i64, centre: i64| {
+            (a.min(b).max(centre - reach), a.max(b).min(centre + reach))
+        };
+        let (x_low, x_high) = clip(from.x, to.x, self.cursor.x);
+        let (y_low, y_high) = clip(from.y, to.y, self.cursor.y);
+        // A step of a whole character at a time can jump past the far end,
+        // so that's always drawn too
+        let along = |low: i64, high: i64| {
+            (low..=high)
+                .step_by(self.scale as usize)
+                .chain(iter::once(high).filter(move |_| low <= high))
+        };
+        for x in along(x_low, x_high) {
+            for y in along(y_low, y_high) {
+                if let Some((col, row)) = self.cell(&Point { x, y }) {
+                    let cell = &mut screen[row][col];
+                    *cell = match *cell {
+                        b' ' => mark,
+                        c if c == mark => mark,
+                        _ => b'#',
+                    };
+                }
+            }
+        }
+    }
+
+    /// The window, followed by what's under the cursor.
+    pub fn render(&self) -> String {
+        let mut screen = vec![vec![b' '; WIDTH as usize]; HEIGHT as usize];
+        for (i, wire) in self.wires.segments.iter().enumerate() {
+            let mark = MARKS[i % MARKS.len()];
+            for segment in wire {
+                self.draw(&mut screen, segment, mark);
+            }
+        }
+        let mut put = |p: &Point, c: u8| {
+            if let Some((col, row)) = self.cell(p) {
+                screen[row][col] = c;
+            }
+        };
+        for i in &self.intersections {
+            put(&i.point, b'X');
+        }
+        put(&Point::default(), b'o');
+        put(&self.cursor, b'@');
+
+        let mut view = String::new();
+        for row in screen {
+            view += String::from_utf8_lossy(&row).trim_end();
+            view.push('\n');
+        }
+        view += &format!(
+            "({}, {}), {} to a character, {} intersections\n",
+            self.cursor.x,
+            self.cursor.y,
+            self.scale,
+            self.intersections.len()
+        );
+        for i in self.under_cursor() {
+            view += &format!(
+                "Wires {} and {} meet at ({}, {}): manhattan distance {}, combined steps {}\n",
+                i.wires.0 + 1,
+                i.wires.1 + 1,
+                i.point.x,
+                i.point.y,
+                i.manhattan,
+                i.combined_steps
+            );
+        }
+        view
+    }
+
+    // The intersections in the same character as the cursor
+    fn under_cursor(&self) -> impl Iterator<Item = &Intersection> {
+        let cursor = self.cell(&self.cursor);
+        self.intersections
+            .iter()
+            .filter(move |i| self.cell(&i.point) == cursor)
+    }
+
+    // Move the cursor, but no further out than any wire can go, which keeps
+    // the window's edges from overflowing
+    fn nudge(&mut self, dx: i64, dy: i64) {
+        let clamp = |c: i64| c.clamp(-MAX_COORDINATE, MAX_COORDINATE);
+        self.cursor.x = clamp(self.cursor.x + dx);
+        self.cursor.y = clamp(self.cursor.y + dy);
+    }
+
+    /// Follow a command, returning false once it's time to stop.
+    pub fn command(&mut self, command: &str) -> bool {
+        let step = self.scale;
+        match command {
+            "w" => self.nudge(0, step),
+            "s" => self.nudge(0, -step),
+            "a" => self.nudge(-step, 0),
+            "d" => self.nudge(step, 0),
+            "W" => self.nudge(0, step * HEIGHT / 2),
+            "S" => self.nudge(0, -step * HEIGHT / 2),
+            "A" => self.nudge(-step * WIDTH / 2, 0),
+            "D" => self.nudge(step * WIDTH / 2, 0),
+            "-" => self.scale = self.scale.saturating_mul(2).min(MAX_SCALE),
+            "+" => self.scale = (self.scale / 2).max(1),
+            "n" | "p" if !self.intersections.is_empty() => {
+                let len = self.intersections.len();
+                let next = match (self.selected, command) {
+                    (None, _) => 0,
+                    (Some(i), "n") => (i + 1) % len,
+                    (Some(i), _) => (i + len - 1) % len,
+                };
+                self.selected = Some(next);
+                self.cursor = self.intersections[next].point;
+            }
+            "o" => self.cursor = Point::default(),
+            "q" => return false,
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Look around the wires from the terminal until asked to stop.
+pub fn explore(wires: &Wires) {
+    let mut viewer = Viewer::new(wires);
+    loop {
+        print!(
+            "{}Move (wasd, WASD for a page), zoom (+/-), intersections (n/p), origin (o), quit (q): ",
+            viewer.render()
+        );
+        io::stdout().flush().expect("Failed to flush stdout");
+        let mut buffer = String::new();
+        let read = io::stdin()
+            .lock()
+            .read_line(&mut buffer)
+            .expect("failed to read from stdin");
+        if read == 0 || !viewer.command(buffer.trim()) {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn views_wires() {
+        let wires = crate::parse("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        let mut viewer = Viewer::new(&wires);
+        let view = viewer.render();
+        let rows = view.lines().collect::<Vec<_>>();
+        // The cursor starts on the central port, in the middle of the window
+        assert_eq!(rows[3], format!("{}2222222", " ".repeat(39)));
+        assert_eq!(rows[5], format!("{}2  111X11", " ".repeat(39)));
+        assert_eq!(rows[7], format!("{}2 2X222 1", " ".repeat(39)));
+        assert_eq!(rows[10], format!("{}@11111111", " ".repeat(39)));
+        assert_eq!(rows[21], "(0, 0), 1 to a character, 2 intersections");
+        assert_eq!(rows.len(), 22);
+
+        // Jump to the closest intersection
+        assert!(viewer.command("n"));
+        let view = viewer.render();
+        assert!(view.contains("(3, 3), 1 to a character"));
+        assert!(view
+            .ends_with("Wires 1 and 2 meet at (3, 3): manhattan distance 6, combined steps 40\n"));
+        viewer.command("n");
+        viewer.command("n");
+        assert_eq!(viewer.cursor, Point { x: 3, y: 3 });
+        viewer.command("p");
+        assert_eq!(viewer.cursor, Point { x: 6, y: 5 });
+
+        // Zoomed out, whole blocks of the grid share a character
+        viewer.command("o");
+        viewer.command("-");
+        viewer.command("-");
+        let view = viewer.render();
+        assert!(view.contains("(0, 0), 4 to a character"));
+        assert_eq!(
+            view.lines().nth(8).unwrap(),
+            format!("{}#X1", " ".repeat(39))
+        );
+        assert_eq!(
+            view.lines().nth(9).unwrap(),
+            format!("{}X21", " ".repeat(39))
+        );
+        assert!(!viewer.command("q"));
+    }
+
+    #[test]
+    fn stops_at_the_edges() {
+        let wires = crate::parse("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+        let mut viewer = Viewer::new(&wires);
+        for _ in 0..100 {
+            viewer.command("-");
+        }
+        assert_eq!(viewer.scale, MAX_SCALE);
+        for _ in 0..100 {
+            viewer.command("D");
+            viewer.command("S");
+        }
+        assert_eq!(
+            viewer.cursor,
+            Point {
+                x: MAX_COORDINATE,
+                y: -MAX_COORDINATE
+            }
+        );
+        assert!(viewer
+            .render()
+            .contains(&format!("{} to a character", MAX_SCALE)));
+        for _ in 0..100 {
+            viewer.command("+");
+        }
+        assert_eq!(viewer.scale, 1);
+    }
+}
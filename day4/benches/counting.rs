@@ -0,0 +1,40 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day4::{count_with_runs, PasswordIter, RunLength, Validator};
+use std::ops::RangeInclusive;
+
+const RUNS: RunLength = RunLength::Exactly(2);
+
+// Check every number in the range
+fn brute_force(range: RangeInclusive<u32>) -> usize {
+    let validator = Validator::with_runs(RUNS);
+    range.filter(|&n| validator.is_valid(n)).count()
+}
+
+// Only check the numbers whose digits never decrease
+fn skipping(range: RangeInclusive<u32>) -> usize {
+    PasswordIter::new(range, Validator::with_runs(RUNS)).count()
+}
+
+fn counting(c: &mut Criterion) {
+    let mut group = c.benchmark_group("counting");
+    // A puzzle input's range, and every password up to nine digits long.
+    // Checking every number up to a billion takes far too long to time.
+    let ranges = [("puzzle", 123_257..=647_015), ("huge", 0..=999_999_999)];
+    for (name, range) in ranges {
+        if name == "puzzle" {
+            group.bench_with_input(BenchmarkId::new("brute force", name), &range, |b, r| {
+                b.iter(|| brute_force(r.clone()))
+            });
+        }
+        group.bench_with_input(BenchmarkId::new("skipping", name), &range, |b, r| {
+            b.iter(|| skipping(r.clone()))
+        });
+        group.bench_with_input(BenchmarkId::new("digit dp", name), &range, |b, r| {
+            b.iter(|| count_with_runs(r, RUNS))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, counting);
+criterion_main!(benches);
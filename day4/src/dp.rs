@@ -0,0 +1,112 @@
+use crate::{digits, RunLength};
+use std::{collections::HashMap, ops::RangeInclusive};
+
+/// How many numbers in the range have digits that never decrease and a
+/// group of matching digits of the given length, the same as
+/// `Validator::with_runs` accepts. Rather than checking every candidate,
+/// this counts them a digit at a time.
+pub fn count_with_runs(range: &RangeInclusive<u32>, runs: RunLength) -> u64 {
+    let (start, end) = (*range.start(), *range.end());
+    if start > end {
+        return 0;
+    }
+    let below = start.checked_sub(1).map_or(0, |n| up_to(n, runs));
+    up_to(end, runs) - below
+}
+
+// Where a count has got to: the next digit to pick, the last one picked
+// if any have been, how long its group is so far, and whether an earlier
+// group already had the right length
+type State = (usize, Option<u8>, usize, bool);
+
+struct Counter<'a> {
+    limit: &'a [u8],
+    runs: RunLength,
+    // Counts for states no longer held back by the limit, which are the
+    // same wherever they were reached from
+    memo: HashMap<State, u64>,
+}
+
+impl Counter<'_> {
+    fn fits(&self, run: usize) -> bool {
+        run >= 2
+            && match self.runs {
+                RunLength::AtLeast(n) => run >= n,
+                RunLength::Exactly(n) => run == n,
+                RunLength::AtMost(n) => run <= n,
+            }
+    }
+
+    // How many ways the rest of the digits can be picked. Until the first
+    // non-zero digit the number is shorter than the limit.
+    fn count(&mut self, state: State, tight: bool) -> u64 {
+        let (pos, last, run, matched) = state;
+        if pos == self.limit.len() {
+            return u64::from(last.is_some() && (matched || self.fits(run)));
+        }
+        if !tight {
+            if let Some(&count) = self.memo.get(&state) {
+                return count;
+            }
+        }
+
+        let high = if tight { self.limit[pos] } else { 9 };
+        let mut total = 0;
+        for d in last.unwrap_or(0)..=high {
+            let next = match last {
+                None if d == 0 => (pos + 1, None, 0, false),
+                None => (pos + 1, Some(d), 1, false),
+                Some(l) if l == d => (pos + 1, last, run + 1, matched),
+                Some(_) => (pos + 1, Some(d), 1, matched || self.fits(run)),
+            };
+            total += self.count(next, tight && d == high);
+        }
+
+        if !tight {
+            self.memo.insert(state, total);
+        }
+        total
+    }
+}
+
+// How many numbers up to n pass
+fn up_to(n: u32, runs: RunLength) -> u64 {
+    let limit = digits(n);
+    let mut counter = Counter {
+        limit: &limit,
+        runs,
+        memo: HashMap::new(),
+    };
+    counter.count((0, None, 0, false), true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PasswordIter, Validator};
+
+    #[test]
+    fn counts_like_checking() {
+        let runs = [
+            RunLength::AtLeast(2),
+            RunLength::Exactly(2),
+            RunLength::Exactly(3),
+            RunLength::AtMost(3),
+        ];
+        for &range in &[(0, 1000), (10, 11), (111_110, 111_122), (123_257, 647_015)] {
+            let range = range.0..=range.1;
+            for &run in &runs {
+                let checked = PasswordIter::new(range.clone(), Validator::with_runs(run)).count();
+                assert_eq!(count_with_runs(&range, run), checked as u64);
+            }
+        }
+        assert_eq!(
+            count_with_runs(&(0..=u32::MAX), RunLength::AtLeast(2)),
+            88863
+        );
+        assert_eq!(
+            count_with_runs(&RangeInclusive::new(5, 4), RunLength::AtLeast(2)),
+            0
+        );
+    }
+}
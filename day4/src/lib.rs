@@ -0,0 +1,181 @@
+mod dp;
+mod rules;
+
+pub use dp::count_with_runs;
+pub use rules::{
+    digits, next_non_decreasing, HasDouble, HasExactDouble, NonDecreasing, Rule, RunLength,
+    Validator,
+};
+
+use aoc_runner::Solution;
+use rayon::prelude::*;
+use std::{
+    io::{self, Write},
+    ops::RangeInclusive,
+};
+
+#[test]
+fn counts_in_chunks() {
+    // Chunks that don't divide the range evenly still cover all of it
+    let range = 100_000..=100_000 + 3 * CHUNK_SIZE + 7;
+    assert_eq!(
+        count_in_chunks(&range, |r| r.filter(|n| n % 3 == 0)),
+        range.filter(|n| n % 3 == 0).count()
+    );
+    assert_eq!(count_in_chunks(&RangeInclusive::new(10, 9), |r| r), 0);
+    assert_eq!(Day4::part1(&(111_110..=111_122)), Ok("10".to_owned()));
+}
+
+#[test]
+fn writes_passwords() {
+    let mut out = vec![];
+    let count = write_passwords(111_119..=111_122, Validator::part1(), &mut out).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(String::from_utf8(out).unwrap(), "111119\n111122\n");
+}
+
+#[test]
+fn parses_ranges() {
+    assert_eq!(parse("123257-647015\n"), Ok(123257..=647015));
+    assert_eq!(parse("100..=200"), Ok(100..=200));
+    assert!(parse("100..200").is_err());
+    assert!(parse("1-2-3").is_err());
+}
+
+#[test]
+fn valid_passwords() {
+    let part1 = Validator::part1();
+    assert!(part1.is_valid(111111));
+    assert!(!part1.is_valid(647011));
+    assert!(!part1.is_valid(223450));
+    assert!(!part1.is_valid(123789));
+
+    let part2 = Validator::part2();
+    assert!(part2.is_valid(112233));
+    assert!(!part2.is_valid(123444));
+    assert!(part2.is_valid(111122));
+}
+
+#[test]
+fn iterates_passwords() {
+    let passwords = PasswordIter::new(111_110..=111_122, Validator::part2());
+    assert_eq!(passwords.collect::<Vec<_>>(), vec![111_122]);
+    // Without a rule that skips ahead, every candidate is checked
+    let odd = PasswordIter::new(u32::MAX - 4..=u32::MAX, |digits: &[u8]| {
+        digits.last().is_some_and(|d| d % 2 == 1)
+    });
+    assert_eq!(
+        odd.collect::<Vec<_>>(),
+        vec![u32::MAX - 4, u32::MAX - 2, u32::MAX]
+    );
+}
+
+/// Every password in a range that satisfies a rule, in order.
+pub struct PasswordIter<R: Rule> {
+    // A u64, so that it can step past the end of any range of u32s
+    num: u64,
+    end: u32,
+    rule: R,
+}
+
+impl<R: Rule> PasswordIter<R> {
+    pub fn new(range: RangeInclusive<u32>, rule: R) -> Self {
+        Self {
+            num: u64::from(*range.start()),
+            end: *range.end(),
+            rule,
+        }
+    }
+}
+
+impl<R: Rule> Iterator for PasswordIter<R> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.num <= u64::from(self.end) {
+            // The rule may know that nothing before some later number can
+            // pass, such as the next one whose digits never decrease
+            let num = self
+                .rule
+                .next_candidate(self.num as u32)
+                .filter(|&n| n <= self.end)?;
+            self.num = u64::from(num) + 1;
+            if self.rule.check(&digits(num)) {
+                return Some(num);
+            }
+        }
+        None
+    }
+}
+
+/// Write every password in the range that satisfies the rule, one per
+/// line, returning how many there were.
+pub fn write_passwords(
+    range: RangeInclusive<u32>,
+    rule: impl Rule,
+    mut sink: impl Write,
+) -> io::Result<usize> {
+    let mut count = 0;
+    for password in PasswordIter::new(range, rule) {
+        writeln!(sink, "{}", password)?;
+        count += 1;
+    }
+    sink.flush()?;
+    Ok(count)
+}
+
+/// How many candidates each thread checks at a time.
+const CHUNK_SIZE: u32 = 1 << 14;
+
+// Split the range into chunks, and count the passwords in each in parallel
+fn count_in_chunks<I: Iterator>(
+    range: &RangeInclusive<u32>,
+    counter: impl Fn(RangeInclusive<u32>) -> I + Sync,
+) -> usize {
+    let (start, end) = (*range.start(), *range.end());
+    if start > end {
+        return 0;
+    }
+    (0..=(end - start) / CHUNK_SIZE)
+        .into_par_iter()
+        .map(|i| {
+            let low = start + i * CHUNK_SIZE;
+            counter(low..=end.min(low.saturating_add(CHUNK_SIZE - 1))).count()
+        })
+        .sum()
+}
+
+/// Parse the puzzle input range, e.g. `123257-647015`, or the same range
+/// written as `123257..=647015`.
+pub fn parse(input: &str) -> Result<RangeInclusive<u32>, String> {
+    let input = input.trim();
+    let (start, end) = input
+        .split_once("..=")
+        .or_else(|| input.split_once('-'))
+        .ok_or_else(|| format!("Expected a range like 123-456 or 123..=456, got {}", input))?;
+    let bound = |s: &str| {
+        s.parse::<u32>()
+            .map_err(|e| format!("Error parsing {}: {}", s, e))
+    };
+    Ok(bound(start)?..=bound(end)?)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = RangeInclusive<u32>;
+
+    fn parse(input: &str) -> Result<RangeInclusive<u32>, String> {
+        parse(input)
+    }
+
+    fn part1(range: &RangeInclusive<u32>) -> Result<String, String> {
+        Ok(count_in_chunks(range, |r| PasswordIter::new(r, Validator::part1())).to_string())
+    }
+
+    fn part2(range: &RangeInclusive<u32>) -> Result<String, String> {
+        Ok(count_in_chunks(range, |r| PasswordIter::new(r, Validator::part2())).to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 4, solution: Day4);
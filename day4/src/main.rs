@@ -1,149 +1,74 @@
-use std::ops::RangeInclusive;
-
-trait ValidPassword {
-    fn is_valid(&self) -> bool;
-    fn is_valid2(&self) -> bool;
-}
-
-impl ValidPassword for u32 {
-    fn is_valid(&self) -> bool {
-        let mut n = *self;
-        let mut a = n % 10;
-
-        let mut b = n / 10 % 10;
-        if a == 0 || b == 0 {
-            return false;
-        }
-
-        let mut double = false;
-        while n > 0 {
-            if a < b {
-                return false;
+use aoc_runner::Solution;
+use day4::{Day4, Validator};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter},
+};
+
+const USAGE: &str = "Usage: day4 [--range LO..=HI]... [--list 1|2 [--output PATH]]";
+
+// Counts the passwords in the puzzle input's range, or in each range given
+// with `--range`. `--list PART` writes every password for that part instead,
+// one per line, to stdout or the `--output` file.
+fn main() -> Result<(), String> {
+    let mut ranges = vec![];
+    let mut list = None;
+    let mut output = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--range" => {
+                let range = args
+                    .next()
+                    .ok_or_else(|| format!("--range needs a range like 123..=456\n{}", USAGE))?;
+                ranges.push(day4::parse(&range)?);
             }
-
-            if a == b && a != 0 {
-                double = true;
+            "--list" => {
+                list = match args.next().as_deref() {
+                    Some("1") => Some(Validator::part1 as fn() -> Validator),
+                    Some("2") => Some(Validator::part2),
+                    _ => return Err(format!("--list needs a part, 1 or 2\n{}", USAGE)),
+                }
             }
-
-            n /= 10;
-            a = n % 10;
-            b = n / 10 % 10;
-        }
-
-        double
-    }
-
-    fn is_valid2(&self) -> bool {
-        let mut bucket = [0; 9];
-        let mut n = *self;
-        let mut a = n % 10;
-
-        let mut b = n / 10 % 10;
-        if a == 0 || b == 0 {
-            return false;
-        }
-
-        while n > 0 {
-            if a < b {
-                return false;
-            }
-
-            if a == b && a != 0 {
-                bucket[(a - 1) as usize] += 1;
+            "--output" => {
+                output = Some(
+                    args.next()
+                        .ok_or_else(|| format!("--output needs a path\n{}", USAGE))?,
+                )
             }
-
-            n /= 10;
-            a = n % 10;
-            b = n / 10 % 10;
+            _ => return Err(format!("Unexpected argument {}\n{}", arg, USAGE)),
         }
-
-        bucket.iter().any(|c| *c == 1)
     }
-}
-
-#[test]
-fn valid_passwords() {
-    assert!(111111.is_valid());
-    assert!(!647011.is_valid());
-    assert!(!223450.is_valid());
-    assert!(!123789.is_valid());
 
-    assert!(112233.is_valid2());
-    assert!(!123444.is_valid2());
-    assert!(111122.is_valid2());
-}
-
-struct PasswordCounter {
-    range: RangeInclusive<u32>,
-    num: u32,
-}
-
-impl PasswordCounter {
-    fn new(range: RangeInclusive<u32>) -> Self {
-        Self {
-            num: *range.start(), // cheating but meh
-            range,
-        }
+    if ranges.is_empty() {
+        let input = fs::read_to_string(day4::DAY.input_path()).map_err(|e| format!("{}", e))?;
+        ranges.push(Day4::parse(&input)?);
     }
-}
 
-impl Iterator for PasswordCounter {
-    type Item = u32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        while !self.num.is_valid() && self.num <= *self.range.end() {
-            self.num += 1;
-        }
-
-        let num = self.num;
-        self.num += 1;
-
-        if num > *self.range.end() {
-            None
-        } else {
-            Some(num)
+    if let Some(validator) = list {
+        let mut sink: Box<dyn io::Write> = match &output {
+            Some(path) => Box::new(BufWriter::new(
+                File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?,
+            )),
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        };
+        for range in ranges {
+            day4::write_passwords(range, validator(), &mut sink)
+                .map_err(|e| format!("Failed to write passwords: {}", e))?;
         }
+        return Ok(());
     }
-}
-
-struct PasswordCounter2 {
-    range: RangeInclusive<u32>,
-    num: u32,
-}
-
-impl PasswordCounter2 {
-    fn new(range: RangeInclusive<u32>) -> Self {
-        Self {
-            num: *range.start(), // cheating but meh
-            range,
-        }
+    if output.is_some() {
+        return Err(format!("--output needs --list\n{}", USAGE));
     }
-}
 
-impl Iterator for PasswordCounter2 {
-    type Item = u32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        while !self.num.is_valid2() && self.num <= *self.range.end() {
-            self.num += 1;
-        }
-
-        let num = self.num;
-        self.num += 1;
-
-        if num > *self.range.end() {
-            None
-        } else {
-            Some(num)
+    for range in &ranges {
+        if ranges.len() > 1 {
+            println!("{}..={}:", range.start(), range.end());
         }
+        println!("Hello, world! {}", Day4::part1(range)?);
+        println!("Hello, world! {}", Day4::part2(range)?);
     }
-}
-fn main() {
-    let valid_range = 123_257..=647_015;
-    let counter = PasswordCounter::new(valid_range.clone());
-    let total_valid: u32 = counter.map(|_| 1).sum();
-    println!("Hello, world! {}", total_valid);
-    let counter = PasswordCounter2::new(valid_range);
-    let total_valid: u32 = counter.map(|_| 1).sum();
-    println!("Hello, world! {}", total_valid);
+    Ok(())
 }
@@ -0,0 +1,203 @@
+use std::convert::TryFrom;
+
+/// Something a password's digits must satisfy. Any `Fn(&[u8]) -> bool` is a
+/// rule too, for one-off variants.
+pub trait Rule {
+    /// Check the password's digits, most significant first.
+    fn check(&self, digits: &[u8]) -> bool;
+
+    /// The first number from `n` on that could pass, or `None` if no u32
+    /// can. Rules that can rule out whole stretches of numbers at once let
+    /// searches skip them.
+    fn next_candidate(&self, n: u32) -> Option<u32> {
+        Some(n)
+    }
+}
+
+impl<F: Fn(&[u8]) -> bool> Rule for F {
+    fn check(&self, digits: &[u8]) -> bool {
+        self(digits)
+    }
+}
+
+/// Going from left to right, the digits never decrease.
+pub struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+    fn check(&self, digits: &[u8]) -> bool {
+        digits.windows(2).all(|w| w[0] <= w[1])
+    }
+
+    fn next_candidate(&self, n: u32) -> Option<u32> {
+        next_non_decreasing(n)
+    }
+}
+
+/// Two adjacent digits are the same.
+pub struct HasDouble;
+
+impl Rule for HasDouble {
+    fn check(&self, digits: &[u8]) -> bool {
+        RunLength::AtLeast(2).check(digits)
+    }
+}
+
+/// Two adjacent digits are the same, and not part of a larger group of
+/// matching digits.
+pub struct HasExactDouble;
+
+impl Rule for HasExactDouble {
+    fn check(&self, digits: &[u8]) -> bool {
+        RunLength::Exactly(2).check(digits)
+    }
+}
+
+/// Some group of matching adjacent digits has a length in the given range.
+/// A lone digit isn't a group, so groups are always at least two long.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RunLength {
+    AtLeast(usize),
+    Exactly(usize),
+    AtMost(usize),
+}
+
+impl Rule for RunLength {
+    fn check(&self, digits: &[u8]) -> bool {
+        runs(digits).filter(|&run| run >= 2).any(|run| match *self {
+            RunLength::AtLeast(n) => run >= n,
+            RunLength::Exactly(n) => run == n,
+            RunLength::AtMost(n) => run <= n,
+        })
+    }
+}
+
+// How many times each digit repeats before the next one
+fn runs(digits: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    let mut rest = digits;
+    std::iter::from_fn(move || {
+        let first = *rest.first()?;
+        let run = rest.iter().take_while(|&&d| d == first).count();
+        rest = &rest[run..];
+        Some(run)
+    })
+}
+
+/// The digits of a number, most significant first.
+pub fn digits(mut n: u32) -> Vec<u8> {
+    let mut digits = vec![];
+    loop {
+        digits.push((n % 10) as u8);
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+/// The smallest number at least `n` whose digits never decrease, such as
+/// 133333 for 130000, or `None` if that doesn't fit in a u32.
+pub fn next_non_decreasing(n: u32) -> Option<u32> {
+    let mut digits = digits(n);
+    // Past the first drop, every digit has to rise to the one before it
+    if let Some(drop) = digits.windows(2).position(|w| w[0] > w[1]) {
+        let high = digits[drop];
+        for d in &mut digits[drop + 1..] {
+            *d = high;
+        }
+    }
+    let next = digits.iter().fold(0u64, |n, &d| n * 10 + u64::from(d));
+    u32::try_from(next).ok()
+}
+
+/// A password is valid when it satisfies every one of a set of rules.
+#[derive(Default)]
+pub struct Validator {
+    rules: Vec<Box<dyn Rule + Send + Sync>>,
+}
+
+impl Validator {
+    /// A validator with no rules, which accepts anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Passwords whose digits never decrease, with a group of matching
+    /// digits of the given length.
+    pub fn with_runs(runs: RunLength) -> Self {
+        Self::new().with(NonDecreasing).with(runs)
+    }
+
+    /// The rules for the first part of the puzzle.
+    pub fn part1() -> Self {
+        Self::with_runs(RunLength::AtLeast(2))
+    }
+
+    /// The rules for the second part of the puzzle.
+    pub fn part2() -> Self {
+        Self::with_runs(RunLength::Exactly(2))
+    }
+
+    /// Also require a password to satisfy the given rule.
+    pub fn with(mut self, rule: impl Rule + Send + Sync + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn is_valid(&self, password: u32) -> bool {
+        self.check(&digits(password))
+    }
+}
+
+impl Rule for Validator {
+    fn check(&self, digits: &[u8]) -> bool {
+        self.rules.iter().all(|rule| rule.check(digits))
+    }
+
+    // Skip ahead until no rule can skip any further
+    fn next_candidate(&self, mut n: u32) -> Option<u32> {
+        loop {
+            let next = self
+                .rules
+                .iter()
+                .try_fold(n, |n, rule| rule.next_candidate(n))?;
+            if next == n {
+                return Some(n);
+            }
+            n = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn composes_rules() {
+        assert_eq!(digits(0), vec![0]);
+        assert_eq!(digits(123450), vec![1, 2, 3, 4, 5, 0]);
+        assert_eq!(runs(&[1, 1, 2, 3, 3, 3]).collect::<Vec<_>>(), vec![2, 1, 3]);
+        assert_eq!(next_non_decreasing(130000), Some(133333));
+        assert_eq!(next_non_decreasing(123789), Some(123789));
+        assert_eq!(next_non_decreasing(199990), Some(199999));
+        assert_eq!(next_non_decreasing(u32::MAX), None);
+
+        assert!(Validator::new().is_valid(987654));
+        let triples = Validator::with_runs(RunLength::Exactly(3));
+        assert!(triples.is_valid(111233));
+        assert!(!triples.is_valid(111123));
+        assert!(!triples.is_valid(112233));
+        let long = Validator::with_runs(RunLength::AtLeast(4));
+        assert!(long.is_valid(111123));
+        assert!(!long.is_valid(111223));
+        let short = Validator::with_runs(RunLength::AtMost(3));
+        assert!(short.is_valid(111122));
+        assert!(!short.is_valid(111123));
+        assert!(!short.is_valid(123456));
+        let no_nines = Validator::part2().with(|digits: &[u8]| !digits.contains(&9));
+        assert!(no_nines.is_valid(112233));
+        assert!(!no_nines.is_valid(112299));
+    }
+}
@@ -0,0 +1,543 @@
+use aoc_runner::Solution;
+use int_code_emulator::{parse_str, MockIo, Program};
+
+/// The system ID the first part of the puzzle runs the diagnostic with,
+/// for the ship's air conditioner.
+pub const AIR_CONDITIONER: i128 = 1;
+/// The system ID for the second part, the thermal radiator controller.
+pub const THERMAL_RADIATOR: i128 = 5;
+
+/// What the diagnostic program leaves behind once it has run.
+pub struct Run {
+    /// Every value it output, the last of which is the diagnostic code
+    pub outputs: Vec<i128>,
+    /// Its memory when it exited
+    pub mem: Vec<i128>,
+}
+
+impl Run {
+    /// The diagnostic code, once every test before it has passed by
+    /// outputting 0. Fails with the first test that didn't.
+    pub fn verify(&self) -> Result<i128, String> {
+        let (code, tests) = self
+            .outputs
+            .split_last()
+            .ok_or_else(|| "Program produced no output".to_owned())?;
+        match tests.iter().position(|&t| t != 0) {
+            Some(i) => Err(format!("Test {} failed, outputting {}", i, tests[i])),
+            None => Ok(*code),
+        }
+    }
+}
+
+/// A memory cell a program changed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Change {
+    pub address: usize,
+    pub before: i128,
+    pub after: i128,
+}
+
+/// The cells that differ between two snapshots of memory. Memory grows as a
+/// program writes past its end, and cells past the end of either snapshot
+/// read as 0.
+pub fn diff(before: &[i128], after: &[i128]) -> Vec<Change> {
+    let cell = |mem: &[i128], address| mem.get(address).copied().unwrap_or(0);
+    (0..before.len().max(after.len()))
+        .map(|address| Change {
+            address,
+            before: cell(before, address),
+            after: cell(after, address),
+        })
+        .filter(|change| change.before != change.after)
+        .collect()
+}
+
+/// Run the diagnostic program, feeding it the given system ID rather than
+/// asking for one.
+pub fn run(mem: &[i128], system_id: i128) -> Result<Run, String> {
+    let io = MockIo::with_input(&[&format!("{}\n", system_id)]);
+    let mut mem = mem.to_vec();
+    let program = Program::new(&mut mem, &io);
+    program.run();
+    let outputs = io
+        .into_output()
+        .iter()
+        .map(|o| o.parse().map_err(|e| format!("Bad output {}: {}", o, e)))
+        .collect::<Result<_, _>>()?;
+    Ok(Run { outputs, mem })
+}
+
+/// Run the diagnostic program with the given system ID and return
+/// the diagnostic code, which is the last value it outputs.
+pub fn diagnostic_code(mem: &[i128], system_id: i128) -> Result<i128, String> {
+    run(mem, system_id)?
+        .outputs
+        .pop()
+        .ok_or_else(|| "Program produced no output".to_owned())
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        diagnostic_code(mem, AIR_CONDITIONER).map(|code| code.to_string())
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        diagnostic_code(mem, THERMAL_RADIATOR).map(|code| code.to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 5, solution: Day5);
+
+pub fn parse(input: &str) -> Vec<i128> {
+    parse_str(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! validate_program {
+        ($mem:expr, $expected:expr) => {
+            let io = MockIo::new();
+            let mem = $mem;
+            let program = Program::new(mem, &io);
+            let expected = $expected;
+            program.run();
+            assert_eq!(
+                mem.len(),
+                expected.len(),
+                "mem and expected mem are not the same length:\n{:?}\n{:?}",
+                &mem[..],
+                &expected[..]
+            );
+            for (i, (l, r)) in mem.iter().zip(expected.iter()).enumerate() {
+                assert_eq!(l, r, "mem mismatch at idx {}", i);
+            }
+        };
+    }
+
+    #[test]
+    fn test_run() {
+        // Add value at address 0 to itself and store
+        // it in address 0
+        #[rustfmt::skip]
+        validate_program!(
+            &mut vec![1, 0, 0, 0, 99],
+                &[2, 0, 0, 0, 99]);
+
+        // Multiply value at address 3 with value at
+        // address 0 and store it in address 3
+        #[rustfmt::skip]
+        validate_program!(
+            &mut vec![2, 3, 0, 3, 99],
+                &[2, 3, 0, 6, 99]);
+
+        // Multiply value at address 4 with value itself
+        // and store it in address 5
+        #[rustfmt::skip]
+        validate_program!(
+            &mut vec![2, 4, 4, 5, 99, 0],
+                &[2, 4, 4, 5, 99, 9801]);
+
+        // Add value at address 1 to itself and store it
+        // in address 4 (create mul opcode)
+        // Multiply value at address 5 with value at
+        // address 6 and store it in address 0
+        #[rustfmt::skip]
+        validate_program!(
+            &mut vec![ 1, 1, 1, 4, 99, 5, 6, 0, 99],
+                &[30, 1, 1, 4,  2, 5, 6, 0, 99]);
+    }
+
+    macro_rules! validate_program_with_io {
+        ($mem:expr, $expected:expr, $input:expr, $output:expr $(,)?) => {
+            let io = MockIo::with_input($input);
+            let mem = $mem;
+            let expected = $expected;
+            let program = Program::new(mem, &io);
+            program.run();
+            assert_eq!(
+                mem.len(),
+                expected.len(),
+                "mem and expected mem are not the same length:\n{:?}\n{:?}",
+                &mem[..],
+                &expected[..]
+            );
+            for (i, (l, r)) in mem.iter().zip(expected.iter()).enumerate() {
+                assert_eq!(l, r, "mem mismatch at idx {}", i);
+            }
+            let output: &[&str] = $output;
+            let actual = io.into_output();
+            assert_eq!(
+                actual.len(),
+                output.len(),
+                "output and expected output are not the same length:\n{:?}\n{:?}",
+                &actual[..],
+                &output[..]
+            );
+            for (i, (l, r)) in actual.iter().zip(output.iter()).enumerate() {
+                assert_eq!(l, r, "output mismatch at idx {}", i);
+            }
+        };
+    }
+
+    // Position mode tests
+    #[test]
+    fn position_op_equal_marks_true_on_equal() {
+        // Read input of "8" and store it in address 9
+        // Since value at address 9 is equal to value at
+        // address 10 (8 == 8), store 1 in address 9
+        // Output value at address 9 (1)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8],
+                &[3, 9, 8, 9, 10, 9, 4, 9, 99,  1, 8],
+            &["8\n"],
+            &["1"],
+        );
+    }
+
+    #[test]
+    fn position_op_equal_marks_false_on_not_equal() {
+        // Read input of "7" and store it in address 9
+        // Since value at address 9 is not equal to value
+        // at address 10 (7 != 8), store 0 in address 9
+        // Output value at address 9 (0)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8],
+                &[3, 9, 8, 9, 10, 9, 4, 9, 99,  0, 8],
+            &["7\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn position_op_less_marks_true_on_less() {
+        // Read input of "7" and store it in address 9
+        // Since value at address 9 is less than the
+        // value at address 10 (7 < 8), store 1 in
+        // address 9. Output value at address 9 (1)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8],
+                &[3, 9, 7, 9, 10, 9, 4, 9, 99,  1, 8],
+            &["7\n"],
+            &["1"],
+        );
+    }
+
+    #[test]
+    fn position_op_less_marks_false_on_not_less() {
+        // Read input of "8" and store it in address 9
+        // Since value at address 9 is not less than
+        // the value at address 10 (8 !< 8), store 0
+        // in address 9. Output value at address 9 (0)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8],
+                &[3, 9, 7, 9, 10, 9, 4, 9, 99,  0, 8],
+            &["8\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn position_op_jtr_executes_jump_if_val_true() {
+        // Read "1" from input and store it in address 12
+        // Jump to the value at address 15 (address 9) because the value
+        // at address 12 is not equal to 0 (1). Print the value at
+        // address 14 (1).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 12, 5, 12, 15, 2, 13, 14, 14, 4, 14, 99, -1, 0, 1, 9],
+                &[3, 12, 5, 12, 15, 2, 13, 14, 14, 4, 14, 99,  1, 0, 1, 9],
+            &["1\n"],
+            &["1"],
+        );
+
+        // Same as above, but ensure we don't only consider 1 to be "true".
+        // Read "-1" from input and store it in address 12
+        // Jump to the value at address 15 (address 9) because the value
+        // at address 12 is not equal to 0 (-1). Print the value at
+        // address 14 (1).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 12, 5, 12, 15, 2, 13, 14, 14, 4, 14, 99, -1, 0, 1, 9],
+                &[3, 12, 5, 12, 15, 2, 13, 14, 14, 4, 14, 99, -1, 0, 1, 9],
+            &["-1\n"],
+            &["1"],
+        );
+    }
+
+    #[test]
+    fn position_op_jtr_does_not_execute_jump_if_val_false() {
+        // Read input "0" into address 12
+        // Do not jump to the address specified at address 15 (9)
+        // because address 12 is 0. Output the value in address
+        // 13 (0).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 12, 5, 12, 15, 2, 13, 14, 14, 4, 14, 99, -1, 0, 1, 9],
+                &[3, 12, 5, 12, 15, 2, 13, 14, 14, 4, 14, 99,  0, 0, 0, 9],
+            &["0\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn position_op_jfl_executes_jump_if_val_false() {
+        // Read input "0" into address 12. Jump to the address specified
+        // at address 15 (9) because address 12 is 0. Output the value in
+        // address 13 (0).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99,  0, 0, 1, 9],
+            &["0\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn position_op_jfl_does_not_execute_jump_if_val_true() {
+        // Read input "1" into address 12. Do not jump to the address
+        // specified at address 15 (9) because address 12 is not 0.
+        // Add the values at address 13 and 14 and store them at address
+        // 13 (0 + 1 => 1). Output the value in address 13 (1).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99,  1, 1, 1, 9],
+            &["1\n"],
+            &["1"],
+        );
+    }
+
+    // immediate mode tests
+
+    #[test]
+    fn immediate_op_equal_marks_true_on_equal() {
+        // Read input of "8" and store it in address 3
+        // Since the first param is equal to the second
+        // param (8 == 8), store 1 in address 3
+        // Output value at address 3 (1)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1108, -1, 8, 3, 4, 3, 99],
+                &[3, 3, 1108,  1, 8, 3, 4, 3, 99],
+            &["8\n"],
+            &["1"],
+        );
+    }
+
+    #[test]
+    fn immediate_op_equal_marks_false_on_not_equal() {
+        // Read input of "7" and store it in address 3
+        // Since the first param is equal to the second
+        // param (7 != 8), store 0 in address 3
+        // Output value at address 3 (0)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1108, -1, 8, 3, 4, 3, 99],
+                &[3, 3, 1108,  0, 8, 3, 4, 3, 99],
+            &["7\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn immediate_op_less_marks_true_on_less() {
+        // Read input of "7" and store it in address 3
+        // Since the first param is less than the
+        // second param (7 < 8), store 1 in address 3
+        // Output value at address 3 (1)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1107, -1, 8, 3, 4, 3, 99],
+                &[3, 3, 1107,  1, 8, 3, 4, 3, 99],
+            &["7\n"],
+            &["1"],
+        );
+    }
+
+    #[test]
+    fn immediate_op_less_marks_false_on_not_less() {
+        // Read input of "8" and store it in address 3
+        // Since the first param is not less than the
+        // second param (8 !< 8), store 0 in address 3
+        // Output value at address 3 (0)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1107, -1, 8, 3, 4, 3, 99],
+                &[3, 3, 1107,  0, 8, 3, 4, 3, 99],
+            &["8\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn immediate_op_jtr_executes_jump_if_val_true() {
+        // Read input of "1" and store it in address 3
+        // Jump to address 9 because the param is 1
+        // Output the value at address 12 (1)
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
+                &[3, 3, 1105,  1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
+            &["1\n"],
+            &["1"],
+        );
+    }
+
+    #[test]
+    fn immediate_op_jtr_does_not_execute_jump_if_val_false() {
+        // Read input "0" into address 3. Do not jump to address 9
+        // because the first param is 0. Add 0 with itself and
+        // store it in address 12. Output the value in address 12
+        // (0).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
+                &[3, 3, 1105,  0, 9, 1101, 0, 0, 12, 4, 12, 99, 0],
+            &["0\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn immediate_op_jfl_executes_jump_if_val_false() {
+        // Read input "0" into address 3. Jump to address 9 because
+        // the first param is 0. Output the value in address 12 (0).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1106, -1, 9, 1101, 0, 1, 12, 4, 12, 99, 0],
+                &[3, 3, 1106,  0, 9, 1101, 0, 1, 12, 4, 12, 99, 0],
+            &["0\n"],
+            &["0"],
+        );
+    }
+
+    #[test]
+    fn immediate_op_jfl_does_not_execute_jump_if_val_true() {
+        // Read input "1" into address 3. Do not jump to address 9
+        // because the first param is not 0. Add 0 to 1 and store
+        // the result in address 12 (1). Output the value in
+        // address 12 (1).
+        #[rustfmt::skip]
+        validate_program_with_io!(
+            &mut vec![3, 3, 1106, -1, 9, 1101, 0, 1, 12, 4, 12, 99, 0],
+                &[3, 3, 1106,  1, 9, 1101, 0, 1, 12, 4, 12, 99, 1],
+            &["1\n"],
+            &["1"],
+        );
+    }
+
+    #[test]
+    fn runs_with_system_id() {
+        // Outputs 1 when the system ID is 8, and 0 otherwise
+        let mem = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        let run = run(&mem, 8).unwrap();
+        assert_eq!(run.outputs, vec![1]);
+        assert_eq!(run.mem[9], 1);
+        assert_eq!(diagnostic_code(&mem, 7), Ok(0));
+        assert!(diagnostic_code(&[3, 0, 99], 1).is_err());
+
+        let run = |outputs: Vec<i128>| Run {
+            outputs,
+            mem: vec![],
+        };
+        assert_eq!(run(vec![0, 0, 42]).verify(), Ok(42));
+        assert_eq!(run(vec![42]).verify(), Ok(42));
+        assert_eq!(
+            run(vec![0, 3, 0, 42]).verify(),
+            Err("Test 1 failed, outputting 3".to_owned())
+        );
+        assert!(run(vec![]).verify().is_err());
+    }
+
+    #[test]
+    fn diffs_memory() {
+        let before = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        let after = super::run(&before, 8).unwrap().mem;
+        assert_eq!(
+            diff(&before, &after),
+            vec![Change {
+                address: 9,
+                before: -1,
+                after: 1,
+            }]
+        );
+        assert_eq!(
+            diff(&[1, 2], &[1, 2, 0, 4]),
+            vec![Change {
+                address: 3,
+                before: 0,
+                after: 4,
+            }]
+        );
+    }
+
+    #[test]
+    fn big_test_lower_prints_999() {
+        validate_program_with_io!(
+            &mut vec![
+                3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36,
+                98, 0, 0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000,
+                1, 20, 4, 20, 1105, 1, 46, 98, 99,
+            ],
+            &[
+                3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36,
+                98, 0, 7, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000,
+                1, 20, 4, 20, 1105, 1, 46, 98, 99,
+            ],
+            &["7\n"],
+            &["999"],
+        );
+    }
+
+    #[test]
+    fn big_test_lower_prints_1000() {
+        validate_program_with_io!(
+            &mut vec![
+                3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36,
+                98, 0, 0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000,
+                1, 20, 4, 20, 1105, 1, 46, 98, 99,
+            ],
+            &[
+                3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36,
+                98, 1000, 8, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101,
+                1000, 1, 20, 4, 20, 1105, 1, 46, 98, 99,
+            ],
+            &["8\n"],
+            &["1000"],
+        );
+    }
+
+    #[test]
+    fn big_test_lower_prints_1001() {
+        validate_program_with_io!(
+            &mut vec![
+                3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36,
+                98, 0, 0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000,
+                1, 20, 4, 20, 1105, 1, 46, 98, 99,
+            ],
+            &[
+                3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36,
+                98, 1001, 9, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101,
+                1000, 1, 20, 4, 20, 1105, 1, 46, 98, 99,
+            ],
+            &["9\n"],
+            &["1001"],
+        );
+    }
+}
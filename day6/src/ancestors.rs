@@ -0,0 +1,131 @@
+use crate::OrbitMap;
+
+/// Every body's level, and its ancestors at each power of two up, so that
+/// the deepest body two others share can be found in a logarithmic number
+/// of jumps instead of walking up one orbit at a time.
+#[derive(Debug)]
+pub(crate) struct Ancestors {
+    levels: Vec<usize>,
+    // `up[k][node]` is the body `2^k` orbits up from `node`, or the root
+    // if that's further up than the tree goes
+    up: Vec<Vec<usize>>,
+}
+
+impl Ancestors {
+    pub(crate) fn new(map: &OrbitMap) -> Self {
+        // Parents always come before their children going down from roots
+        let mut levels = vec![0; map.nodes.len()];
+        let mut stack = map.roots.clone();
+        while let Some(node) = stack.pop() {
+            for &child in &map.nodes[node].children {
+                levels[child] = levels[node] + 1;
+                stack.push(child);
+            }
+        }
+
+        let parents = map
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| node.parent.unwrap_or(i))
+            .collect::<Vec<_>>();
+        let deepest = levels.iter().copied().max().unwrap_or(0);
+        let mut up = vec![parents];
+        while 1 << up.len() <= deepest {
+            let last = up.last().unwrap();
+            let next = last.iter().map(|&node| last[node]).collect();
+            up.push(next);
+        }
+        Self { levels, up }
+    }
+
+    /// How many bodies the given one orbits, directly or indirectly.
+    pub(crate) fn level(&self, node: usize) -> usize {
+        self.levels[node]
+    }
+
+    /// The deepest body that both given bodies orbit, or are, if they're
+    /// in the same tree.
+    pub(crate) fn lca(&self, mut a: usize, mut b: usize) -> Option<usize> {
+        if self.levels[a] < self.levels[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        // Bring the deeper one up to the other's level
+        let mut rise = self.levels[a] - self.levels[b];
+        let mut k = 0;
+        while rise > 0 {
+            if rise & 1 == 1 {
+                a = self.up[k][a];
+            }
+            rise >>= 1;
+            k += 1;
+        }
+        if a == b {
+            return Some(a);
+        }
+
+        // Then jump both up as far as they can go without meeting, which
+        // leaves them just under the body they share
+        for up in self.up.iter().rev() {
+            if up[a] != up[b] {
+                a = up[a];
+                b = up[b];
+            }
+        }
+        // Different roots never meet
+        let parent = self.up[0][a];
+        if parent == a || parent != self.up[0][b] {
+            return None;
+        }
+        Some(parent)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Walk up from both bodies one orbit at a time to check against
+    fn slow_lca(map: &OrbitMap, a: usize, b: usize) -> Option<usize> {
+        let path = |mut node: usize| {
+            let mut path = vec![node];
+            while let Some(parent) = map.nodes[node].parent {
+                path.push(parent);
+                node = parent;
+            }
+            path
+        };
+        let path_a = path(a);
+        path(b).into_iter().find(|node| path_a.contains(node))
+    }
+
+    // Each body orbits a body before it, or nothing, which is always a
+    // forest
+    fn forests() -> impl Strategy<Value = Vec<Option<usize>>> {
+        (1usize..150).prop_flat_map(|n| {
+            (0..n)
+                .map(|i| proptest::option::weighted(0.95, 0..i.max(1)))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_walking_up(parents in forests()) {
+            let pairs = parents
+                .iter()
+                .enumerate()
+                .filter_map(|(i, parent)| parent.filter(|_| i > 0).map(|p| (p, i)))
+                .map(|(p, i)| (format!("B{}", p), format!("B{}", i)))
+                .collect();
+            let map = OrbitMap::new(pairs).unwrap();
+            let ancestors = Ancestors::new(&map);
+            for a in 0..map.nodes.len() {
+                for b in 0..map.nodes.len() {
+                    prop_assert_eq!(ancestors.lca(a, b), slow_lca(&map, a, b));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{find_cycle, NodeData, OrbitMap};
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    sync::OnceLock,
+};
+
+// A map as it's written out, before it's been checked
+#[derive(Deserialize)]
+pub(crate) struct Bodies {
+    bodies: Vec<NodeData>,
+}
+
+impl TryFrom<Bodies> for OrbitMap {
+    type Error = String;
+
+    // Anything could have been written to the file, so make sure the links
+    // all agree with each other before trusting them
+    fn try_from(Bodies { bodies: nodes }: Bodies) -> Result<Self, String> {
+        let mut index = HashMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            if index.insert(node.name.clone(), i).is_some() {
+                return Err(format!("{} is listed more than once", node.name));
+            }
+        }
+
+        let body = |i: usize| {
+            nodes
+                .get(i)
+                .ok_or_else(|| format!("There's no body {} of {}", i, nodes.len()))
+        };
+        for (i, node) in nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                if !body(parent)?.children.contains(&i) {
+                    return Err(format!(
+                        "{} orbits {}, which doesn't list it",
+                        node.name, nodes[parent].name
+                    ));
+                }
+            }
+            let mut listed = HashSet::new();
+            for &child in &node.children {
+                if body(child)?.parent != Some(i) {
+                    return Err(format!(
+                        "{} lists {}, which doesn't orbit it",
+                        node.name, nodes[child].name
+                    ));
+                }
+                // Counting a body twice would count its orbits twice too
+                if !listed.insert(child) {
+                    return Err(format!(
+                        "{} lists {} more than once",
+                        node.name, nodes[child].name
+                    ));
+                }
+            }
+        }
+
+        let roots = (0..nodes.len())
+            .filter(|&i| nodes[i].parent.is_none())
+            .collect::<Vec<_>>();
+        if let Some(cycle) = find_cycle(&nodes, &roots) {
+            return Err(format!("Orbits loop back around: {}", cycle.join(")")));
+        }
+        Ok(Self {
+            nodes,
+            index,
+            roots,
+            ancestors: OnceLock::new(),
+        })
+    }
+}
+
+impl OrbitMap {
+    /// Write the map as JSON, so it can be loaded again without rebuilding
+    /// it from the orbit pairs.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| format!("Failed to write the map: {}", e))
+    }
+
+    /// Load a map written by `to_json`.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Failed to load the map: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let map = OrbitMap::parse("COM)B\nB)C\nB)YOU\nC)SAN\nX)Y\n".as_bytes()).unwrap();
+        let json = map.to_json().unwrap();
+        assert!(json.starts_with(r#"{"bodies":[{"name":"COM","parent":null,"children":[1]}"#));
+        let loaded = OrbitMap::from_json(&json).unwrap();
+        assert_eq!(loaded.checksum(), map.checksum());
+        assert_eq!(loaded.min_transfers(), Some(1));
+        assert_eq!(loaded.roots().collect::<Vec<_>>(), vec!["COM", "X"]);
+        assert_eq!(loaded.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn checks_loaded_maps() {
+        let load = |bodies: &str| OrbitMap::from_json(&format!(r#"{{"bodies":[{}]}}"#, bodies));
+        let a = r#"{"name":"A","parent":null,"children":[1]}"#;
+        assert!(load(&format!(r#"{},{{"name":"B","parent":0,"children":[]}}"#, a)).is_ok());
+
+        let err = load(&format!(r#"{},{{"name":"A","parent":0,"children":[]}}"#, a)).unwrap_err();
+        assert!(err.contains("A is listed more than once"), "{}", err);
+        let err = load(a).unwrap_err();
+        assert!(err.contains("There's no body 1 of 1"), "{}", err);
+        let err = load(&format!(
+            r#"{},{{"name":"B","parent":null,"children":[]}}"#,
+            a
+        ))
+        .unwrap_err();
+        assert!(err.contains("A lists B, which doesn't orbit it"), "{}", err);
+        let err = load(&format!(
+            r#"{{"name":"A","parent":null,"children":[1,1]}},{}"#,
+            r#"{"name":"B","parent":0,"children":[]}"#
+        ))
+        .unwrap_err();
+        assert!(err.contains("A lists B more than once"), "{}", err);
+        let err = load(r#"{"name":"A","parent":0,"children":[0]}"#).unwrap_err();
+        assert!(err.contains("Orbits loop back around: A)A"), "{}", err);
+        assert!(load(r#"{"name":"A"}"#).is_err());
+    }
+}
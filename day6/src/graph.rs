@@ -0,0 +1,141 @@
+use petgraph::{
+    algo::dijkstra,
+    graph::{NodeIndex, UnGraph},
+    Graph,
+};
+use std::collections::HashMap;
+
+/// The orbit map as a petgraph graph, with an edge from every body to each
+/// body orbiting it. It answers the same questions as `OrbitMap`, leaning on
+/// petgraph's algorithms instead.
+#[derive(Debug)]
+pub struct OrbitGraph {
+    graph: Graph<String, ()>,
+    // The same orbits without their direction, and without the names, for
+    // finding transfers, which can go either way along an orbit. Its node
+    // indices match `graph`'s.
+    undirected: UnGraph<(), ()>,
+    index: HashMap<String, NodeIndex>,
+    roots: Vec<NodeIndex>,
+}
+
+impl OrbitGraph {
+    /// Assemble the orbit pairs, where each pair is the body being orbited
+    /// followed by the one orbiting it.
+    pub fn new(pairs: Vec<(String, String)>) -> Self {
+        let mut graph = Graph::new();
+        let mut index = HashMap::new();
+        for (center, body) in pairs {
+            let mut id = |name: String| {
+                *index
+                    .entry(name)
+                    .or_insert_with_key(|name| graph.add_node(name.clone()))
+            };
+            let (center, body) = (id(center), id(body));
+            graph.add_edge(center, body, ());
+        }
+
+        let roots = graph.externals(petgraph::Incoming).collect();
+        let undirected = graph.map(|_, _| (), |_, _| ()).into_edge_type();
+        Self {
+            graph,
+            undirected,
+            index,
+            roots,
+        }
+    }
+
+    /// The sum of all direct and indirect orbits, which is every body's
+    /// distance from its root.
+    pub fn checksum(&self) -> usize {
+        self.roots
+            .iter()
+            .flat_map(|&root| dijkstra(&self.graph, root, None, |_| 1usize).into_values())
+            .sum()
+    }
+
+    /// The orbital transfers needed to get from the body `from` orbits to
+    /// the one `to` orbits, or `None` if either is missing or there's no
+    /// way between them, as between different roots' trees.
+    pub fn transfers(&self, from: &str, to: &str) -> Option<usize> {
+        let (from, to) = (self.center(from)?, self.center(to)?);
+        let distances = dijkstra(&self.undirected, from, Some(to), |_| 1usize);
+        distances.get(&to).copied()
+    }
+
+    // The body the given one orbits
+    fn center(&self, body: &str) -> Option<NodeIndex> {
+        let body = *self.index.get(body)?;
+        self.graph
+            .neighbors_directed(body, petgraph::Incoming)
+            .next()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse, OrbitMap};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/transfers.txt");
+
+    // Both backends should agree on everything they're asked
+    fn assert_agree(pairs: Vec<(String, String)>) {
+        let map = OrbitMap::new(pairs.clone()).unwrap();
+        let graph = OrbitGraph::new(pairs);
+        assert_eq!(graph.checksum(), map.checksum());
+        assert_eq!(graph.transfers("YOU", "SAN"), map.min_transfers());
+    }
+
+    #[test]
+    fn matches_map_on_example() {
+        let graph = OrbitGraph::new(parse(EXAMPLE).unwrap());
+        assert_eq!(graph.checksum(), 54);
+        assert_eq!(graph.transfers("YOU", "SAN"), Some(4));
+        assert_eq!(graph.transfers("YOU", "NOBODY"), None);
+        assert_eq!(graph.transfers("YOU", "COM"), None);
+        assert_eq!(graph.transfers("YOU", "L"), Some(0));
+        assert_eq!(graph.transfers("YOU", "J"), Some(2));
+        assert_agree(parse(EXAMPLE).unwrap());
+
+        let forest = "COM)B\nB)YOU\nX)Y\nY)SAN\n";
+        let graph = OrbitGraph::new(parse(forest).unwrap());
+        assert_eq!(graph.checksum(), 6);
+        assert_eq!(graph.transfers("YOU", "SAN"), None);
+        assert_agree(parse(forest).unwrap());
+    }
+
+    // Each body after the first orbits some body before it, which is
+    // always a tree with COM at the root
+    fn orbits() -> impl Strategy<Value = Vec<(String, String)>> {
+        (2usize..200)
+            .prop_flat_map(|n| {
+                let parents = (1..n).map(|i| 0..i).collect::<Vec<_>>();
+                (parents, 1..n, 1..n)
+            })
+            .prop_filter("YOU and SAN are different bodies", |(_, you, san)| {
+                you != san
+            })
+            .prop_map(|(parents, you, san)| {
+                let name = |i: usize| match i {
+                    0 => "COM".to_string(),
+                    i if i == you => "YOU".to_string(),
+                    i if i == san => "SAN".to_string(),
+                    i => format!("B{}", i),
+                };
+                parents
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, parent)| (name(parent), name(i + 1)))
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_map(pairs in orbits()) {
+            assert_agree(pairs);
+        }
+    }
+}
@@ -0,0 +1,531 @@
+use aoc_runner::{Reporter, Solution};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::BufRead,
+    sync::{Arc, OnceLock},
+};
+
+mod ancestors;
+mod cache;
+#[cfg(feature = "petgraph")]
+pub mod graph;
+mod outline;
+
+pub use outline::Outline;
+
+// A body in the arena, pointing at the bodies around it by index
+#[derive(Debug, Serialize, Deserialize)]
+struct NodeData {
+    name: Arc<str>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+/// Every body in the orbit map, kept in one arena and linked by index.
+/// Serializes as just the arena, as `{"bodies": [{"name", "parent",
+/// "children"}]}`, and is checked over again when deserialized.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(try_from = "cache::Bodies")]
+pub struct OrbitMap {
+    #[serde(rename = "bodies")]
+    nodes: Vec<NodeData>,
+    #[serde(skip)]
+    index: HashMap<Arc<str>, usize>,
+    // The bodies that don't orbit anything, each at the center of its own
+    // tree of orbits
+    #[serde(skip)]
+    roots: Vec<usize>,
+    // Built on the first query between two bodies, for quick answers to any
+    // that follow
+    #[serde(skip)]
+    ancestors: OnceLock<ancestors::Ancestors>,
+}
+
+impl OrbitMap {
+    /// Read the orbit pairs, such as `COM)B`, one to a line. Blank lines are
+    /// skipped.
+    pub fn parse(mut reader: impl BufRead) -> Result<Self, String> {
+        let mut builder = Builder::default();
+        // Reuse the one line, so that only new names are ever allocated
+        let mut line = String::new();
+        for i in 1.. {
+            line.clear();
+            let read = reader
+                .read_line(&mut line)
+                .map_err(|e| format!("Failed to read line {}: {}", i, e))?;
+            if read == 0 {
+                break;
+            }
+            if let Some((center, body)) = parse_line(i, &line)? {
+                builder.add(center, body)?;
+            }
+        }
+        builder.finish()
+    }
+
+    /// Assemble the orbit pairs into trees in one pass, where each
+    /// pair is the body being orbited followed by the one orbiting it. Fails
+    /// when a body orbits two others, or when orbits loop back on
+    /// themselves.
+    pub fn new(pairs: Vec<(String, String)>) -> Result<Self, String> {
+        let mut builder = Builder::default();
+        for (center, body) in &pairs {
+            builder.add(center, body)?;
+        }
+        builder.finish()
+    }
+
+    /// Whether the body is anywhere in the map.
+    pub fn contains(&self, body: &str) -> bool {
+        self.index.contains_key(body)
+    }
+
+    /// The bodies that don't orbit anything. The puzzle has only the one,
+    /// COM, but other maps may have several.
+    pub fn roots(&self) -> impl Iterator<Item = &str> {
+        self.roots
+            .iter()
+            .map(move |&root| self.nodes[root].name.as_ref())
+    }
+
+    /// Calculate the sum of all direct and indirect orbits
+    /// of all bodies in the map.
+    pub fn checksum(&self) -> usize {
+        self.checksums().map(|(_, checksum)| checksum).sum()
+    }
+
+    /// The sum of all direct and indirect orbits in each root's tree, along
+    /// with the root's name.
+    pub fn checksums(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.roots.iter().map(move |&root| {
+            // Walk the tree with our own stack, since a long enough chain of
+            // orbits would overflow the real one
+            let mut total = 0;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, depth)) = stack.pop() {
+                total += depth;
+                stack.extend(self.nodes[node].children.iter().map(|&c| (c, depth + 1)));
+            }
+            (self.nodes[root].name.as_ref(), total)
+        })
+    }
+
+    /// Return a path from the root to the requested body
+    /// if it exists. For example,
+    /// ```text
+    ///                    D -> E -> F
+    ///                   /
+    /// COM -> A -> B -> C-> G -> H
+    ///                       \
+    ///                        I -> J -> K
+    /// ```
+    /// Calling `map.path("J")` should return:
+    ///  `Some(vec!["COM", "A", "B", "C", "G", "I", "J"])`
+    fn path(&self, to: &str) -> Option<Vec<&str>> {
+        let mut path = vec![];
+        let mut node = Some(*self.index.get(to)?);
+        while let Some(i) = node {
+            path.push(self.nodes[i].name.as_ref());
+            node = self.nodes[i].parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    fn ancestors(&self) -> &ancestors::Ancestors {
+        self.ancestors
+            .get_or_init(|| ancestors::Ancestors::new(self))
+    }
+
+    // The deepest body that both given bodies orbit, or are, if they're in
+    // the same tree
+    fn lca(&self, a: usize, b: usize) -> Option<usize> {
+        self.ancestors().lca(a, b)
+    }
+
+    // How many orbits lie between two bodies in the same tree
+    fn hops(&self, a: usize, b: usize) -> Option<usize> {
+        let ancestors = self.ancestors();
+        let lca = ancestors.lca(a, b)?;
+        Some(ancestors.level(a) + ancestors.level(b) - 2 * ancestors.level(lca))
+    }
+
+    /// How many orbits lie between two bodies, going up to the deepest body
+    /// they both orbit and back down, or `None` if either is missing or
+    /// they're in different trees.
+    pub fn distance(&self, a: &str, b: &str) -> Option<usize> {
+        self.hops(*self.index.get(a)?, *self.index.get(b)?)
+    }
+
+    /// The orbital transfers needed to get from the body `a` orbits to the
+    /// one `b` orbits, or `None` if either is missing, is a root, which
+    /// doesn't orbit anything, or they're in different trees.
+    pub fn transfers_between(&self, a: &str, b: &str) -> Option<usize> {
+        let a = self.nodes[*self.index.get(a)?].parent?;
+        let b = self.nodes[*self.index.get(b)?].parent?;
+        self.hops(a, b)
+    }
+
+    /// The orbital transfers needed to get from the body YOU orbits to the
+    /// one SAN orbits.
+    pub fn min_transfers(&self) -> Option<usize> {
+        self.transfers_between("YOU", "SAN")
+    }
+
+    /// Draw the map as a tree, one body to a line.
+    pub fn outline(&self, outline: &Outline) -> Result<String, String> {
+        outline.render(self)
+    }
+}
+
+// Assembles the arena a pair at a time. Each name is allocated once, and
+// shared between its body and the index.
+#[derive(Default)]
+struct Builder {
+    nodes: Vec<NodeData>,
+    index: HashMap<Arc<str>, usize>,
+}
+
+impl Builder {
+    // Find a body, or add it if it hasn't been seen yet
+    fn id(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.index.get(name) {
+            return id;
+        }
+        let name: Arc<str> = name.into();
+        self.nodes.push(NodeData {
+            name: name.clone(),
+            parent: None,
+            children: vec![],
+        });
+        self.index.insert(name, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    fn add(&mut self, center: &str, body: &str) -> Result<(), String> {
+        let center = self.id(center);
+        let body = self.id(body);
+        let nodes = &mut self.nodes;
+        match nodes[body].parent {
+            Some(parent) if parent == center => {
+                return Err(format!(
+                    "{} orbits {} more than once",
+                    nodes[body].name, nodes[center].name
+                ))
+            }
+            Some(parent) => {
+                return Err(format!(
+                    "{} orbits both {} and {}",
+                    nodes[body].name, nodes[parent].name, nodes[center].name
+                ))
+            }
+            None => {}
+        }
+        nodes[body].parent = Some(center);
+        nodes[center].children.push(body);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<OrbitMap, String> {
+        let Builder { nodes, index } = self;
+        let roots = (0..nodes.len())
+            .filter(|&i| nodes[i].parent.is_none())
+            .collect::<Vec<_>>();
+        if let Some(cycle) = find_cycle(&nodes, &roots) {
+            return Err(format!("Orbits loop back around: {}", cycle.join(")")));
+        }
+        Ok(OrbitMap {
+            nodes,
+            index,
+            roots,
+            ancestors: OnceLock::new(),
+        })
+    }
+}
+
+// Since every body orbits at most one other, anything that can't be reached
+// from a root orbits a cycle, or is part of one. Returns the names around
+// the first cycle, each orbiting the one before and ending where it began.
+fn find_cycle<'a>(nodes: &'a [NodeData], roots: &[usize]) -> Option<Vec<&'a str>> {
+    let mut reached = vec![false; nodes.len()];
+    let mut stack = roots.to_vec();
+    while let Some(node) = stack.pop() {
+        reached[node] = true;
+        stack.extend(&nodes[node].children);
+    }
+
+    // Walking up from an unreached body has to come back around
+    let mut node = reached.iter().position(|&r| !r)?;
+    let mut seen = vec![false; nodes.len()];
+    while !seen[node] {
+        seen[node] = true;
+        node = nodes[node].parent?;
+    }
+    let start = node;
+    let mut cycle = vec![nodes[start].name.as_ref()];
+    while let Some(parent) = nodes[node].parent {
+        cycle.push(nodes[parent].name.as_ref());
+        node = parent;
+        if node == start {
+            break;
+        }
+    }
+    cycle.reverse();
+    Some(cycle)
+}
+
+// Parse one line of input, which may be blank
+fn parse_line(line: usize, value: &str) -> Result<Option<(&str, &str)>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    match value.split_once(')') {
+        Some((center, body)) if !body.contains(')') => {
+            let (center, body) = (center.trim(), body.trim());
+            if !center.is_empty() && !body.is_empty() {
+                return Ok(Some((center, body)));
+            }
+        }
+        _ => {}
+    }
+    Err(format!(
+        "Expected one body orbiting another, like COM)B, on line {}: {:?}",
+        line, value
+    ))
+}
+
+/// Parse the orbit pairs, such as `COM)B`, one to a line. Blank lines are
+/// skipped.
+pub fn parse(input: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = vec![];
+    for (i, line) in input.lines().enumerate() {
+        if let Some((center, body)) = parse_line(i + 1, line)? {
+            pairs.push((center.to_owned(), body.to_owned()));
+        }
+    }
+    Ok(pairs)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    // Assembling the orbit map is most of the work, so both parts share it
+    type Input = OrbitMap;
+
+    fn parse(input: &str) -> Result<OrbitMap, String> {
+        OrbitMap::parse(input.as_bytes())
+    }
+
+    fn part1(map: &OrbitMap) -> Result<String, String> {
+        Ok(map.checksum().to_string())
+    }
+
+    fn part2(map: &OrbitMap) -> Result<String, String> {
+        map.min_transfers()
+            .map(|transfers| transfers.to_string())
+            .ok_or_else(|| "No way to get from YOU to SAN".to_string())
+    }
+}
+
+/// Report the paths from the root to YOU and SAN, and where they part ways.
+pub fn explain(input: &str, reporter: &mut dyn Reporter) -> Result<(), String> {
+    let map = OrbitMap::parse(input.as_bytes())?;
+    let path_to_you = map.path("YOU").ok_or("No path to YOU")?;
+    let path_to_san = map.path("SAN").ok_or("No path to SAN")?;
+    reporter.report("path to YOU", &path_to_you.join(" -> "));
+    reporter.report("path to SAN", &path_to_san.join(" -> "));
+
+    if let Some(shared) = map.lca(map.index["YOU"], map.index["SAN"]) {
+        reporter.report("last shared body", &map.nodes[shared].name);
+    }
+    Ok(())
+}
+
+aoc_runner::register!(year: 2019, day: 6, solution: Day6, explain: explain);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Pairs from `A)B` style shorthand, for building small trees
+    fn pairs(input: &[(&str, &str)]) -> Vec<(String, String)> {
+        input
+            .iter()
+            .map(|&(a, b)| (a.to_owned(), b.to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn builds_map_in_any_order() {
+        // Children can be listed before the bodies they orbit are placed
+        let map = OrbitMap::new(pairs(&[("C", "D"), ("A", "B"), ("B", "C"), ("B", "E")])).unwrap();
+        assert_eq!(map.roots().collect::<Vec<_>>(), vec!["A"]);
+        assert_eq!(map.path("D"), Some(vec!["A", "B", "C", "D"]));
+        assert_eq!(map.path("E"), Some(vec!["A", "B", "E"]));
+        assert_eq!(map.path("A"), Some(vec!["A"]));
+        assert_eq!(map.path("F"), None);
+        let b = map.index["B"];
+        assert_eq!(map.nodes[b].children.len(), 2);
+
+        let (d, e) = (map.index["D"], map.index["E"]);
+        assert_eq!(map.lca(d, e), Some(b));
+        assert_eq!(map.lca(d, b), Some(b));
+        assert_eq!(map.lca(e, e), Some(e));
+        assert_eq!(map.hops(d, e), Some(3));
+        assert_eq!(map.hops(map.index["A"], d), Some(3));
+
+        assert_eq!(map.distance("D", "E"), Some(3));
+        assert_eq!(map.distance("A", "A"), Some(0));
+        assert_eq!(map.distance("D", "F"), None);
+        assert_eq!(map.transfers_between("D", "E"), Some(1));
+        assert_eq!(map.transfers_between("C", "E"), Some(0));
+        assert_eq!(map.transfers_between("A", "E"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_maps() {
+        let err = OrbitMap::new(pairs(&[("A", "B"), ("C", "B")])).unwrap_err();
+        assert_eq!(err, "B orbits both A and C");
+        let err = OrbitMap::new(pairs(&[("A", "B"), ("A", "B")])).unwrap_err();
+        assert_eq!(err, "B orbits A more than once");
+
+        // Without any root at all
+        let err = OrbitMap::new(pairs(&[("A", "B"), ("B", "C"), ("C", "A")])).unwrap_err();
+        assert_eq!(err, "Orbits loop back around: A)B)C)A");
+        // A loop off to the side of a proper tree, with a body hanging off it
+        let looped = pairs(&[("COM", "A"), ("B", "C"), ("D", "E"), ("C", "D"), ("D", "B")]);
+        let err = OrbitMap::new(looped).unwrap_err();
+        assert_eq!(err, "Orbits loop back around: B)C)D)B");
+        let err = OrbitMap::new(pairs(&[("COM", "A"), ("B", "B")])).unwrap_err();
+        assert_eq!(err, "Orbits loop back around: B)B");
+    }
+
+    #[test]
+    fn handles_forests() {
+        let map = OrbitMap::new(pairs(&[
+            ("COM", "A"),
+            ("A", "YOU"),
+            ("ROOT", "B"),
+            ("B", "C"),
+            ("C", "SAN"),
+            ("A", "D"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            map.checksums().collect::<Vec<_>>(),
+            vec![("COM", 5), ("ROOT", 6)]
+        );
+        assert_eq!(map.checksum(), 11);
+        assert_eq!(map.min_transfers(), None);
+        assert_eq!(map.distance("YOU", "SAN"), None);
+        assert_eq!(map.transfers_between("YOU", "D"), Some(0));
+        assert_eq!(map.distance("ROOT", "SAN"), Some(3));
+        assert!(map.contains("ROOT"));
+        assert!(!map.contains("Z"));
+
+        let empty = OrbitMap::new(vec![]).unwrap();
+        assert_eq!(empty.checksum(), 0);
+        assert_eq!(empty.roots().count(), 0);
+    }
+
+    #[test]
+    fn checksum_test() {
+        let map = OrbitMap::new(pairs(&[
+            ("COM", "B"),
+            ("B", "C"),
+            ("C", "D"),
+            ("D", "E"),
+            ("E", "F"),
+            ("B", "G"),
+            ("G", "H"),
+            ("D", "I"),
+            ("E", "J"),
+            ("J", "K"),
+            ("K", "L"),
+        ]))
+        .unwrap();
+
+        assert_eq!(map.checksum(), 42);
+    }
+
+    #[test]
+    fn parses_pairs() {
+        assert_eq!(
+            parse("COM)B\r\n\n B)C \n").unwrap(),
+            pairs(&[("COM", "B"), ("B", "C")])
+        );
+        let err = parse("COM)B\nB-C\n").unwrap_err();
+        assert!(err.contains("line 2"), "{}", err);
+        assert!(parse("COM)B)C").unwrap_err().contains("line 1"));
+        assert!(parse("COM)").is_err());
+    }
+
+    #[test]
+    fn handles_deep_chains() {
+        // Deep enough to overflow the stack if anything recursed per orbit
+        let n = 100_000;
+        let mut chain = vec![("COM".to_string(), "B1".to_string())];
+        chain.extend((1..n).map(|i| (format!("B{}", i), format!("B{}", i + 1))));
+        chain.push(("B1".to_string(), "YOU".to_string()));
+        chain.push((format!("B{}", n), "SAN".to_string()));
+        let map = OrbitMap::new(chain).unwrap();
+
+        // The chain itself, then YOU at depth 2 and SAN at the very bottom
+        assert_eq!(map.checksum(), n * (n + 1) / 2 + 2 + n + 1);
+        assert_eq!(map.path("SAN").unwrap().len(), n + 2);
+        assert_eq!(map.min_transfers(), Some(n - 1));
+    }
+
+    #[test]
+    fn explains_paths() {
+        let input = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN";
+        let mut steps = vec![];
+        explain(input, &mut steps).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                (
+                    "path to YOU".to_owned(),
+                    "COM -> B -> C -> D -> E -> J -> K -> YOU".to_owned()
+                ),
+                (
+                    "path to SAN".to_owned(),
+                    "COM -> B -> C -> D -> I -> SAN".to_owned()
+                ),
+                ("last shared body".to_owned(), "D".to_owned()),
+            ]
+        );
+    }
+
+    // The puzzle's examples, and a map with no way from YOU to SAN
+    const CHECKSUM: &str = include_str!("../fixtures/checksum.txt");
+    const TRANSFERS: &str = include_str!("../fixtures/transfers.txt");
+    const NO_PATH: &str = include_str!("../fixtures/no_path.txt");
+
+    #[test]
+    fn solves_fixtures() {
+        let map = Day6::parse(CHECKSUM).unwrap();
+        assert_eq!(Day6::part1(&map), Ok("42".to_string()));
+        assert!(Day6::part2(&map).is_err());
+
+        let map = Day6::parse(TRANSFERS).unwrap();
+        assert_eq!(Day6::part1(&map), Ok("54".to_string()));
+        assert_eq!(Day6::part2(&map), Ok("4".to_string()));
+        assert_eq!(map.transfers_between("SAN", "YOU"), Some(4));
+        assert_eq!(map.distance("YOU", "SAN"), Some(6));
+
+        let map = Day6::parse(NO_PATH).unwrap();
+        assert_eq!(Day6::part1(&map), Ok("9".to_string()));
+        assert_eq!(
+            Day6::part2(&map),
+            Err("No way to get from YOU to SAN".to_string())
+        );
+        assert_eq!(map.min_transfers(), None);
+        let mut steps = vec![];
+        explain(NO_PATH, &mut steps).unwrap();
+        assert_eq!(steps.len(), 2);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::OrbitMap;
+
+/// How to draw the orbit map as a tree of box-drawing characters, with
+/// each body under the one it orbits.
+#[derive(Clone, Debug, Default)]
+pub struct Outline {
+    /// How many levels of orbits to draw below the top body, or `None` for
+    /// all of them. Cut off bodies note how many more orbit them.
+    pub depth: Option<usize>,
+    /// Only draw the bodies orbiting this one, under the way down to it
+    /// from the root
+    pub focus: Option<String>,
+}
+
+impl Outline {
+    /// Draw the map, one body to a line, with each root's tree in turn.
+    /// With a focus, the first line is the way down to it from its root, as
+    /// in `COM)B)C`.
+    pub fn render(&self, map: &OrbitMap) -> Result<String, String> {
+        let tops = match &self.focus {
+            Some(body) => vec![*map
+                .index
+                .get(body.as_str())
+                .ok_or_else(|| format!("{} isn't in the map", body))?],
+            None => map.roots.clone(),
+        };
+
+        let mut out = String::new();
+        for top in tops {
+            self.render_tree(map, top, &mut out);
+        }
+        Ok(out)
+    }
+
+    // Draw the bodies orbiting the top one, under the way down to it
+    fn render_tree(&self, map: &OrbitMap, top: usize, out: &mut String) {
+        out.push_str(&map.path(&map.nodes[top].name).unwrap().join(")"));
+        self.cut_off(map, top, 0, out);
+        out.push('\n');
+
+        // Each body waits with the prefix for its line, whether it's the
+        // last of its siblings, and how far below the top body it is
+        let mut stack = vec![];
+        let push_children = |stack: &mut Vec<_>, node: usize, prefix: &str, level: usize| {
+            if self.depth.is_none_or(|depth| level < depth) {
+                let children = &map.nodes[node].children;
+                for (i, &child) in children.iter().enumerate().rev() {
+                    let last = i + 1 == children.len();
+                    stack.push((child, prefix.to_string(), last, level + 1));
+                }
+            }
+        };
+        push_children(&mut stack, top, "", 0);
+        while let Some((node, prefix, last, level)) = stack.pop() {
+            let (connector, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            out.push_str(&prefix);
+            out.push_str(connector);
+            out.push_str(&map.nodes[node].name);
+            self.cut_off(map, node, level, out);
+            out.push('\n');
+            push_children(&mut stack, node, &(prefix + indent), level);
+        }
+    }
+
+    // Note how many bodies orbit one that's too deep to draw them under
+    fn cut_off(&self, map: &OrbitMap, node: usize, level: usize, out: &mut String) {
+        if self.depth.is_some_and(|depth| level >= depth) {
+            let hidden = descendants(map, node);
+            if hidden > 0 {
+                out.push_str(&format!(" ({} more)", hidden));
+            }
+        }
+    }
+}
+
+// How many bodies orbit the given one, directly or indirectly
+fn descendants(map: &OrbitMap, node: usize) -> usize {
+    let mut count = 0;
+    let mut stack = map.nodes[node].children.clone();
+    while let Some(node) = stack.pop() {
+        count += 1;
+        stack.extend(&map.nodes[node].children);
+    }
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/checksum.txt");
+
+    #[test]
+    fn draws_outlines() {
+        let map = OrbitMap::parse(EXAMPLE.as_bytes()).unwrap();
+        let full = map.outline(&Outline::default()).unwrap();
+        assert_eq!(
+            full,
+            "\
+COM
+└── B
+    ├── C
+    │   └── D
+    │       ├── E
+    │       │   ├── F
+    │       │   └── J
+    │       │       └── K
+    │       │           └── L
+    │       └── I
+    └── G
+        └── H
+"
+        );
+
+        let shallow = Outline {
+            depth: Some(2),
+            ..Outline::default()
+        };
+        assert_eq!(
+            map.outline(&shallow).unwrap(),
+            "\
+COM
+└── B
+    ├── C (7 more)
+    └── G (1 more)
+"
+        );
+
+        let focused = Outline {
+            depth: Some(1),
+            focus: Some("E".to_string()),
+        };
+        assert_eq!(
+            map.outline(&focused).unwrap(),
+            "\
+COM)B)C)D)E
+├── F
+└── J (2 more)
+"
+        );
+
+        let missing = Outline {
+            focus: Some("X".to_string()),
+            ..Outline::default()
+        };
+        assert!(map.outline(&missing).is_err());
+        let nothing = Outline {
+            depth: Some(0),
+            ..Outline::default()
+        };
+        assert_eq!(map.outline(&nothing).unwrap(), "COM (11 more)\n");
+
+        let forest = OrbitMap::parse("COM)B\nX)Y\nB)C\n".as_bytes()).unwrap();
+        assert_eq!(
+            forest.outline(&Outline::default()).unwrap(),
+            "COM\n└── B\n    └── C\nX\n└── Y\n"
+        );
+    }
+}
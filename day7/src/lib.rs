@@ -0,0 +1,263 @@
+use aoc_runner::Solution;
+use int_code_emulator::{parse_str, MockIo, PipedIo, Program};
+use std::sync::mpsc;
+
+struct Amplifiers<'a> {
+    init_mem: &'a [i128],
+    phase_settings: &'a [usize],
+    config: Configuration,
+}
+
+impl<'a> Amplifiers<'a> {
+    fn new(init_mem: &'a [i128], phase_settings: &'a [usize], config: Configuration) -> Self {
+        Self {
+            init_mem,
+            phase_settings,
+            config,
+        }
+    }
+
+    fn run(self) -> isize {
+        let mut output = None;
+        match self.config {
+            Configuration::Simple => {
+                for phase_setting in self.phase_settings.iter() {
+                    let io = MockIo::with_input(&[
+                        &format!("{}\n", phase_setting),
+                        &format!("{}\n", output.unwrap_or_else(|| "0".to_owned())),
+                    ]);
+
+                    let mut mem = self.init_mem.to_vec();
+                    let program = Program::new(&mut mem, &io);
+                    program.run();
+                    output = io.into_output().into_iter().next();
+                }
+            }
+            Configuration::Looped => {
+                let (mut txs, rxs): (Vec<_>, Vec<_>) = (0..self.phase_settings.len())
+                    .map(|i| {
+                        let (tx, rx) = mpsc::channel();
+                        let i = format!("{}", i);
+                        ((i.clone(), tx), (i, rx))
+                    })
+                    .unzip();
+                // tx -> rx
+                // 0 -> 1
+                // 1 -> 2
+                // 2 -> 3
+                // 3 -> 4
+                // 4 -> 0
+                let first_tx = txs.remove(0);
+                txs.push(first_tx);
+                let mut pipes = txs
+                    .into_iter()
+                    .zip(rxs)
+                    .map(|(tx, rx)| PipedIo::new(rx, tx))
+                    .collect::<Vec<_>>();
+                for (pipe, phase_setting) in pipes.iter_mut().zip(self.phase_settings) {
+                    pipe.init_input(format!("{}\n", phase_setting));
+                }
+                pipes.iter_mut().next().unwrap().init_input("0\n");
+                let threads = pipes
+                    .into_iter()
+                    .map(|io| {
+                        let mut mem = self.init_mem.to_vec();
+                        std::thread::spawn(move || {
+                            let program = Program::new(&mut mem, &io);
+                            program.run();
+                            io.close()
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                for thread in threads {
+                    let rx = thread.join().ok();
+                    let results = rx.map(|rx| rx.iter().collect::<Vec<_>>());
+                    if let Some(mut res) = results {
+                        if let Some(last) = res.pop() {
+                            output = Some(last);
+                        }
+                    }
+                }
+            }
+        }
+
+        output
+            .expect("Expected to have an output!")
+            .parse()
+            .expect("Could not parse output!")
+    }
+}
+
+pub fn maximize_amplifiers(init_mem: &[i128], config: Configuration) -> isize {
+    fn max_amp_util(
+        init_mem: &[i128],
+        config: Configuration,
+        phase_settings: &mut [usize],
+        size: usize,
+    ) -> isize {
+        if size == 1 {
+            let amplifiers = Amplifiers::new(init_mem, phase_settings, config);
+            return amplifiers.run();
+        }
+
+        let mut max = 0;
+        for i in 0..size {
+            let res = max_amp_util(init_mem, config, phase_settings, size - 1);
+            if res > max {
+                max = res;
+            }
+
+            if size % 2 == 1 {
+                phase_settings.swap(0, size - 1);
+            } else {
+                phase_settings.swap(i, size - 1);
+            }
+        }
+
+        max
+    }
+
+    let range = match config {
+        Configuration::Simple => 0..5,
+        Configuration::Looped => 5..10,
+    };
+    let mut possible_phase_settings: Vec<_> = range.into_iter().collect();
+    let length = possible_phase_settings.len();
+    max_amp_util(init_mem, config, &mut possible_phase_settings, length)
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Configuration {
+    Simple,
+    Looped,
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse_str(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        Ok(maximize_amplifiers(mem, Configuration::Simple).to_string())
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        Ok(maximize_amplifiers(mem, Configuration::Looped).to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 7, solution: Day7);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn amplifiers_case_1_simple() {
+        let mem = vec![
+            3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+        ];
+        let amplifiers = Amplifiers::new(&mem, &[4, 3, 2, 1, 0], Configuration::Simple);
+        let res = amplifiers.run();
+        assert_eq!(res, 43_210);
+    }
+
+    #[test]
+    fn amplifiers_case_2_simple() {
+        let mem = vec![
+            3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23,
+            99, 0, 0,
+        ];
+        let amplifiers = Amplifiers::new(&mem, &[0, 1, 2, 3, 4], Configuration::Simple);
+        let res = amplifiers.run();
+        assert_eq!(res, 54_321);
+    }
+
+    #[test]
+    fn amplifiers_case_3_simple() {
+        let mem = vec![
+            3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1,
+            33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
+        ];
+        let amplifiers = Amplifiers::new(&mem, &[1, 0, 4, 3, 2], Configuration::Simple);
+        let res = amplifiers.run();
+        assert_eq!(res, 65_210);
+    }
+
+    #[test]
+    fn amplifiers_case_1_looped() {
+        let mem = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let amplifiers = Amplifiers::new(&mem, &[9, 8, 7, 6, 5], Configuration::Looped);
+        let res = amplifiers.run();
+        assert_eq!(res, 139_629_729);
+    }
+
+    #[test]
+    fn amplifiers_case_2_looped() {
+        let mem = vec![
+            3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001, 54,
+            -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53, 55, 53, 4,
+            53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10,
+        ];
+        let amplifiers = Amplifiers::new(&mem, &[9, 7, 8, 5, 6], Configuration::Looped);
+        let res = amplifiers.run();
+        assert_eq!(res, 18_216);
+    }
+
+    #[test]
+    fn max_thrust_case_1() {
+        let mem = vec![
+            3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+        ];
+        let res = maximize_amplifiers(&mem, Configuration::Simple);
+        assert_eq!(res, 43210);
+    }
+
+    #[test]
+    fn max_thrust_case_2() {
+        let mem = vec![
+            3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23,
+            99, 0, 0,
+        ];
+        let res = maximize_amplifiers(&mem, Configuration::Simple);
+        assert_eq!(res, 54_321);
+    }
+
+    #[test]
+    fn max_thrust_case_3() {
+        let mem = vec![
+            3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1,
+            33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
+        ];
+        let res = maximize_amplifiers(&mem, Configuration::Simple);
+        assert_eq!(res, 65_210);
+    }
+
+    #[test]
+    fn max_thrust_case_1_looped() {
+        let mem = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let res = maximize_amplifiers(&mem, Configuration::Looped);
+        assert_eq!(res, 139_629_729);
+    }
+
+    #[test]
+    fn max_thrust_case_2_looped() {
+        let mem = vec![
+            3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001, 54,
+            -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53, 55, 53, 4,
+            53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10,
+        ];
+        let res = maximize_amplifiers(&mem, Configuration::Looped);
+        assert_eq!(res, 18_216);
+    }
+}
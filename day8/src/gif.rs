@@ -0,0 +1,173 @@
+use crate::{Image, Palette};
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, Rgba, RgbaImage,
+};
+use std::convert::TryFrom;
+
+/// How to animate the layers being stacked up, starting from the back and
+/// adding one more layer in front each frame.
+#[derive(Clone, Debug)]
+pub struct Animation {
+    /// How many pixels across each of the image's pixels is drawn
+    pub scale: u32,
+    /// How long each frame shows for, in milliseconds. The last frame, with
+    /// every layer in place, shows for ten times as long.
+    pub delay: u32,
+    /// The colors to draw each digit in, and which digits are transparent
+    pub palette: Palette,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            scale: 10,
+            delay: 100,
+            palette: Palette::default(),
+        }
+    }
+}
+
+impl Animation {
+    // Pixels that are still transparent show as grey, between black and
+    // white
+    fn color(&self, pixel: u8) -> Rgba<u8> {
+        let [r, g, b] = self.palette.color(pixel).map_or([128; 3], |c| c.0);
+        Rgba([r, g, b, 255])
+    }
+
+    /// Draw every step of stacking up the layers as a looping GIF.
+    pub fn render(&self, image: &Image) -> Result<Vec<u8>, String> {
+        colors::check_scale(self.scale.into())?;
+        let scaled = |side: usize| {
+            u32::try_from(side)
+                .ok()
+                .and_then(|side| side.checked_mul(self.scale))
+        };
+        let (width, height) = match (scaled(image.width()), scaled(image.height())) {
+            (Some(width), Some(height)) => (width, height),
+            _ => {
+                return Err(format!(
+                    "A {}x{} image is too big to draw at a scale of {}",
+                    image.width(),
+                    image.height(),
+                    self.scale
+                ))
+            }
+        };
+        let last_delay = self
+            .delay
+            .checked_mul(10)
+            .ok_or_else(|| format!("A delay of {}ms is too long", self.delay))?;
+
+        // Keep one composite going from the back, painting each new layer in
+        // front over it, rather than stacking every frame up from scratch.
+        // Parsing makes sure there's always at least one layer.
+        let layers = image.layers();
+        let mut composite = layers.last().unwrap().pixels().to_vec();
+        let frames = (0..layers.len()).rev().map(|first| {
+            for (pixel, &digit) in composite.iter_mut().zip(layers[first].pixels()) {
+                if !self.palette.is_transparent(digit) {
+                    *pixel = digit;
+                }
+            }
+            let pixels = RgbaImage::from_fn(width, height, |x, y| {
+                let (x, y) = ((x / self.scale) as usize, (y / self.scale) as usize);
+                self.color(composite[y * image.width() + x])
+            });
+            let delay = if first == 0 { last_delay } else { self.delay };
+            Frame::from_parts(pixels, 0, 0, Delay::from_numer_denom_ms(delay, 1))
+        });
+
+        let mut gif = vec![];
+        let mut encoder = GifEncoder::new(&mut gif);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .and_then(|_| encoder.encode_frames(frames))
+            .map_err(|e| format!("Failed to encode the GIF: {}", e))?;
+        drop(encoder);
+        Ok(gif)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use image::{codecs::gif::GifDecoder, AnimationDecoder};
+
+    #[test]
+    fn animates_layers() {
+        let image = Image::parse("0222112222120000", 2, 2).unwrap();
+        let animation = Animation {
+            scale: 3,
+            ..Animation::default()
+        };
+        let gif = animation.render(&image).unwrap();
+        let frames = GifDecoder::new(&gif[..])
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        // One frame for each layer added in front, ending on the message
+        assert_eq!(frames.len(), 4);
+        let pixels = |frame: &Frame| {
+            let buffer = frame.buffer();
+            assert_eq!(buffer.dimensions(), (6, 6));
+            [(0, 0), (3, 0), (0, 3), (3, 3)]
+                .iter()
+                .map(|&(x, y)| buffer.get_pixel(x, y).0[0])
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pixels(&frames[0]), vec![0, 0, 0, 0]);
+        assert_eq!(pixels(&frames[1]), vec![0, 0, 255, 0]);
+        assert_eq!(pixels(&frames[2]), vec![255, 255, 255, 0]);
+        assert_eq!(pixels(&frames[3]), vec![0, 255, 255, 0]);
+        assert_eq!(frames[0].delay(), Delay::from_numer_denom_ms(100, 1));
+        assert_eq!(frames[3].delay(), Delay::from_numer_denom_ms(1000, 1));
+
+        // Anything still transparent is grey
+        let gif = animation
+            .render(&Image::parse("21", 2, 1).unwrap())
+            .unwrap();
+        let frame = GifDecoder::new(&gif[..])
+            .unwrap()
+            .into_frames()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(frame.buffer().get_pixel(0, 0).0, [128, 128, 128, 255]);
+
+        // Unless the palette gives it a color
+        let red = Animation {
+            palette: "2=ff0000".parse().unwrap(),
+            ..animation.clone()
+        };
+        let gif = red.render(&Image::parse("21", 2, 1).unwrap()).unwrap();
+        let frame = GifDecoder::new(&gif[..])
+            .unwrap()
+            .into_frames()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(frame.buffer().get_pixel(0, 0).0, [255, 0, 0, 255]);
+
+        let flat = Animation {
+            scale: 0,
+            ..animation.clone()
+        };
+        assert!(flat.render(&image).is_err());
+        let huge = Animation {
+            scale: u32::MAX,
+            ..animation.clone()
+        };
+        assert_eq!(
+            huge.render(&image),
+            Err("A 2x2 image is too big to draw at a scale of 4294967295".to_owned())
+        );
+        let slow = Animation {
+            delay: u32::MAX,
+            ..animation
+        };
+        assert!(slow.render(&image).unwrap_err().contains("is too long"));
+    }
+}
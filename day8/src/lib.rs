@@ -0,0 +1,293 @@
+use aoc_runner::Solution;
+use rayon::prelude::*;
+
+mod gif;
+mod ocr;
+mod palette;
+mod terminal;
+
+pub use colors::Rgb;
+pub use gif::Animation;
+pub use ocr::recognize;
+pub use palette::Palette;
+pub use terminal::Style;
+
+/// The size of the puzzle's image.
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+/// What each digit in the puzzle's images means.
+pub const BLACK: u8 = 0;
+pub const WHITE: u8 = 1;
+pub const TRANSPARENT: u8 = 2;
+
+/// One layer of the image, with its digits stored a row at a time.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Layer {
+    width: usize,
+    pixels: Vec<u8>,
+}
+
+impl Layer {
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.pixels.chunks(self.width)
+    }
+
+    /// Row `y`, counting from the top.
+    pub fn row(&self, y: usize) -> &[u8] {
+        &self.pixels[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The digit in column `x` of row `y`, counting from the top left.
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        self.row(y)[x]
+    }
+
+    /// Every digit in the layer, a row at a time.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// How many of the layer's digits are `digit`.
+    pub fn count(&self, digit: u8) -> usize {
+        self.pixels.iter().filter(|&&i| i == digit).count()
+    }
+
+    /// How many of each digit the layer has, counted in one pass.
+    pub fn histogram(&self) -> [usize; 10] {
+        let mut counts = [0; 10];
+        for &digit in &self.pixels {
+            counts[digit as usize] += 1;
+        }
+        counts
+    }
+}
+
+/// An image in the Space Image Format, as a stack of layers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    layers: Vec<Layer>,
+}
+
+impl Image {
+    /// Split the digits into layers of `height` rows, each `width` pixels
+    /// wide. Trailing whitespace is ignored, but anything else that isn't a
+    /// digit is an error, as is any layer left unfinished.
+    pub fn parse(input: &str, width: usize, height: usize) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err(format!("Can't have a {}x{} image", width, height));
+        }
+        let input = input.trim_end();
+        let digits = input.as_bytes();
+        if let Some(i) = digits.iter().position(|b| !b.is_ascii_digit()) {
+            // Show the whole character, even if it's more than one byte
+            let c = input[i..].chars().next().unwrap();
+            return Err(format!("Expected a digit at byte {}, found {:?}", i, c));
+        }
+        let layer_size = width
+            .checked_mul(height)
+            .ok_or_else(|| format!("Can't have a {}x{} image", width, height))?;
+        if digits.is_empty() || !digits.len().is_multiple_of(layer_size) {
+            return Err(format!(
+                "Expected whole {}x{} layers of {} digits each, found {} digits",
+                width,
+                height,
+                layer_size,
+                digits.len()
+            ));
+        }
+
+        let layers = digits
+            .chunks(layer_size)
+            .map(|chunk| Layer {
+                width,
+                pixels: chunk.iter().map(|b| b - b'0').collect(),
+            })
+            .collect();
+        Ok(Self {
+            width,
+            height,
+            layers,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The layers, from the front of the stack to the back.
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Find the layer with the fewest `fewest_of` digits, and multiply its
+    /// number of `multiply_a` digits by its number of `multiply_b` digits.
+    /// The puzzle's checksum is `checksum(BLACK, WHITE, TRANSPARENT)`.
+    pub fn checksum(&self, fewest_of: u8, multiply_a: u8, multiply_b: u8) -> usize {
+        // Each layer is counted once, and on its own, so large images can
+        // count them all at once. Parsing makes sure there's always at least
+        // one layer.
+        let counts = self
+            .layers
+            .par_iter()
+            .map(Layer::histogram)
+            .min_by_key(|counts| counts[fewest_of as usize])
+            .unwrap();
+        counts[multiply_a as usize] * counts[multiply_b as usize]
+    }
+
+    /// Stack the layers on top of each other, keeping the first pixel in
+    /// each place that isn't transparent. Places that are transparent all
+    /// the way down stay transparent.
+    pub fn composite(&self) -> Layer {
+        self.composite_with(&Palette::default())
+    }
+
+    /// Stack the layers on top of each other, keeping the first pixel in
+    /// each place whose digit isn't transparent in the palette. Places that
+    /// are transparent all the way down keep the back layer's digit.
+    pub fn composite_with(&self, palette: &Palette) -> Layer {
+        // Parsing makes sure there's always at least one layer. Going from
+        // the back to the front, each opaque digit covers what's behind it.
+        let (back, rest) = self.layers.split_last().unwrap();
+        let mut pixels = back.pixels.clone();
+        for layer in rest.iter().rev() {
+            for (pixel, &digit) in pixels.iter_mut().zip(&layer.pixels) {
+                if !palette.is_transparent(digit) {
+                    *pixel = digit;
+                }
+            }
+        }
+        Layer {
+            width: self.width,
+            pixels,
+        }
+    }
+
+    /// Stack the layers on top of each other, and render the visible
+    /// pixels.
+    pub fn render(&self) -> String {
+        self.draw(Style::Digits)
+    }
+
+    /// Stack the layers on top of each other, and draw the visible pixels
+    /// in the given style.
+    pub fn draw(&self, style: Style) -> String {
+        self.draw_with(style, &Palette::default())
+    }
+
+    /// Stack the layers on top of each other using the palette's idea of
+    /// which digits are transparent, and draw the visible pixels in the
+    /// given style, with light colors lit up.
+    pub fn draw_with(&self, style: Style, palette: &Palette) -> String {
+        style.draw_with(&self.composite_with(palette), palette)
+    }
+
+    /// Animate stacking up the layers, as a GIF.
+    pub fn gif(&self, animation: &Animation) -> Result<Vec<u8>, String> {
+        animation.render(self)
+    }
+
+    /// Stack the layers on top of each other, and read the letters the
+    /// white pixels spell out.
+    pub fn message(&self) -> Result<String, String> {
+        let lit = self
+            .composite()
+            .rows()
+            .map(|row| row.iter().map(|&pixel| pixel == WHITE).collect())
+            .collect::<Vec<_>>();
+        recognize(&lit)
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Image;
+
+    fn parse(input: &str) -> Result<Image, String> {
+        Image::parse(input, WIDTH, HEIGHT)
+    }
+
+    fn part1(image: &Image) -> Result<String, String> {
+        Ok(image.checksum(BLACK, WHITE, TRANSPARENT).to_string())
+    }
+
+    fn part2(image: &Image) -> Result<String, String> {
+        image.message()
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 8, solution: Day8);
+
+#[test]
+fn parse_test() {
+    let image = Image::parse("123456789012\n", 3, 2).unwrap();
+    let layers = image.layers();
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].rows().count(), 2);
+    assert_eq!(layers[0].row(0), &[1, 2, 3]);
+    assert_eq!(layers[0].row(1), &[4, 5, 6]);
+    assert_eq!(layers[1].rows().count(), 2);
+    assert_eq!(layers[1].row(0), &[7, 8, 9]);
+    assert_eq!(layers[1].row(1), &[0, 1, 2]);
+    assert_eq!(layers[1].pixel(2, 0), 9);
+    assert_eq!(layers[1].pixels(), &[7, 8, 9, 0, 1, 2]);
+    assert_eq!(layers[1].histogram(), [1, 1, 1, 0, 0, 0, 0, 1, 1, 1]);
+    assert_eq!(image.checksum(0, 1, 2), 1);
+    // Any digits can be counted. The second layer has the fewest 3s, and
+    // the first the fewest 7s, with no 9s.
+    assert_eq!(image.checksum(3, 7, 8), 1);
+    assert_eq!(image.checksum(7, 5, 5), 1);
+    assert_eq!(image.checksum(7, 1, 9), 0);
+
+    // Ties go to the layer in front, however the counting is split up
+    let tied = "0111".repeat(500) + &"0122".repeat(500);
+    assert_eq!(Image::parse(&tied, 2, 2).unwrap().checksum(0, 1, 2), 0);
+}
+
+#[test]
+fn composite_test() {
+    let image = Image::parse("0222112222120000", 2, 2).unwrap();
+    let composite = image.composite();
+    assert_eq!(composite.rows().collect::<Vec<_>>(), vec![&[0, 1], &[1, 0]]);
+    assert_eq!(composite.pixel(1, 0), WHITE);
+    assert_eq!(composite.count(BLACK), 2);
+    assert_eq!(image.render(), " 1\n1 \n");
+
+    // Nothing shows through where every layer is transparent
+    let image = Image::parse("2212", 2, 1).unwrap();
+    assert_eq!(image.composite().rows().collect::<Vec<_>>(), vec![&[1, 2]]);
+    assert_eq!(image.render(), "12\n");
+
+    // Any digit can be transparent, and the ones behind it show through
+    let image = Image::parse("5313", 2, 1).unwrap();
+    let palette = "5=none,3=none".parse().unwrap();
+    let composite = image.composite_with(&palette);
+    assert_eq!(composite.pixels(), &[1, 3]);
+    assert_eq!(image.composite().pixels(), &[5, 3]);
+    assert_eq!(image.draw_with(Style::Digits, &palette), "13\n");
+}
+
+#[test]
+fn parse_errors() {
+    let err = Image::parse("1234 5678", 2, 2).unwrap_err();
+    assert_eq!(err, "Expected a digit at byte 4, found ' '");
+    let err = Image::parse("12é4x", 2, 2).unwrap_err();
+    assert_eq!(err, "Expected a digit at byte 2, found 'é'");
+    let err = Image::parse("123456", 2, 2).unwrap_err();
+    assert_eq!(
+        err,
+        "Expected whole 2x2 layers of 4 digits each, found 6 digits"
+    );
+    assert!(Image::parse("\n", 2, 2).is_err());
+    assert!(Image::parse("1234", 0, 2).is_err());
+    let err = Image::parse("1234", usize::MAX, 2).unwrap_err();
+    assert_eq!(err, format!("Can't have a {}x2 image", usize::MAX));
+}
@@ -1,90 +1,97 @@
-use std::{
-    fs::File,
-    io::Read,
-};
+use day8::{Animation, Image, Palette, Style, BLACK, HEIGHT, TRANSPARENT, WHITE, WIDTH};
+use std::{env, fs, path::PathBuf};
 
-type Row = Vec<u8>;
+const USAGE: &str = "Usage: day8 [--width N] [--height N] [--input PATH] \
+                     [--style digits|blocks|half|braille] [--palette D=COLOR,...] [--gif PATH [--scale N] [--delay MS]]";
 
-struct Layer {
-    rows: Vec<Row>,
-}
-
-fn parse(input: &str, width: usize, height: usize) -> Vec<Layer> {
-    input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as u8)
-        .collect::<Vec<_>>()
-        .chunks(width)
-        .map(|chunk| chunk.into_iter().map(|&u| u).collect::<Vec<_>>())
-        .collect::<Vec<Vec<_>>>()
-        .chunks(height)
-        .map(|chunk| Layer {
-            rows: chunk.into_iter().map(|v| v.clone()).collect(),
-        })
-        .collect()
-}
-
-fn main() {
-    let width = 25;
-    let height = 6;
-    let mut buffer = String::new();
-    let mut file = File::open("day8/input.txt").expect("Failed to open file");
-    file.read_to_string(&mut buffer)
-        .expect("Failed to read from file");
-    let layers = parse(buffer.trim(), width, height);
-    let layer = layers
-        .iter()
-        .map(|l| {
-            (
-                l.rows
-                    .iter()
-                    .map(|r| r.iter().filter(|&&i| i == 0).count())
-                    .sum::<usize>(),
-                l,
-            )
-        })
-        .min_by_key(|&(c, _)| c)
-        .map(|(_, l)| l)
-        .expect("Should have had something...");
-    let l1s = layer.rows.iter().map(|r| r.iter().filter(|&&i| i == 1).count()).sum::<usize>();
-    let l2s = layer.rows.iter().map(|r| r.iter().filter(|&&i| i == 2).count()).sum::<usize>();
-    println!("Hello, world! {}", l1s * l2s);
-
-    let mut final_layer: Vec<Vec<Option<u8>>> = vec![vec![None; width]; height];
-    for layer in &layers {
-        for (r, row) in layer.rows.iter().enumerate() {
-            for (c, col) in row.iter().enumerate() {
-                if *col == 2 {
-                    continue;
+// Decodes the puzzle input as a 25x6 image by default. Other images, such
+// as the puzzle's examples, can be given with `--input` along with their
+// `--width` and `--height`. `--gif` also animates the layers stacking up,
+// with each pixel `--scale` pixels across and `--delay` milliseconds between
+// frames. `--style` draws the image with block or braille characters
+// instead of digits. `--palette` changes the color each digit is drawn in,
+// or makes it transparent with `none`.
+fn main() -> Result<(), String> {
+    let mut width = WIDTH;
+    let mut height = HEIGHT;
+    let mut path = None;
+    let mut gif = None;
+    let mut animation = Animation::default();
+    let mut style = Style::Digits;
+    let mut palette = Palette::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--width" | "--height" => {
+                let n = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a number of pixels\n{}", arg, USAGE))?;
+                let n = n
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("Bad {} {}", &arg[2..], n))?;
+                if arg == "--width" {
+                    width = n;
+                } else {
+                    height = n;
                 }
-
-                if let pixel @ None = &mut final_layer[r][c] {
-                    *pixel = Some(*col);
+            }
+            "--input" => {
+                let p = args
+                    .next()
+                    .ok_or_else(|| format!("--input needs a path\n{}", USAGE))?;
+                path = Some(PathBuf::from(p));
+            }
+            "--style" => {
+                style = args
+                    .next()
+                    .ok_or_else(|| format!("--style needs a style\n{}", USAGE))?
+                    .parse()?;
+            }
+            "--palette" => {
+                palette = args
+                    .next()
+                    .ok_or_else(|| format!("--palette needs colors for some digits\n{}", USAGE))?
+                    .parse()?;
+            }
+            "--gif" => {
+                let p = args
+                    .next()
+                    .ok_or_else(|| format!("--gif needs a path\n{}", USAGE))?;
+                gif = Some(PathBuf::from(p));
+            }
+            "--scale" | "--delay" => {
+                let n = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a number\n{}", arg, USAGE))?;
+                let n = n
+                    .parse::<u32>()
+                    .map_err(|e| format!("Bad {} {}: {}", &arg[2..], n, e))?;
+                if arg == "--scale" {
+                    animation.scale = n;
+                } else {
+                    animation.delay = n;
                 }
             }
+            _ => return Err(format!("Unexpected argument {}\n{}", arg, USAGE)),
         }
     }
-    for row in final_layer {
-        for col in row {
-            print!("{}", match col {
-                Some(v) if v == 1 => format!("{}", v),
-                Some(v) if v == 0 => " ".to_owned(),
-                None => "2".to_owned(),
-                _ => "3".to_owned(),
-            })
-        }
-        println!();
-    }
-}
 
-#[test]
-fn parse_test() {
-    let layers = parse("123456789012", 3, 2);
-    assert_eq!(layers.len(), 2);
-    assert_eq!(layers[0].rows.len(), 2);
-    assert_eq!(&layers[0].rows[0], &[1, 2, 3]);
-    assert_eq!(&layers[0].rows[1], &[4, 5, 6]);
-    assert_eq!(layers[1].rows.len(), 2);
-    assert_eq!(&layers[1].rows[0], &[7, 8, 9]);
-    assert_eq!(&layers[1].rows[1], &[0, 1, 2]);
+    let path = path.unwrap_or_else(|| day8::DAY.input_path());
+    let input = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let image = Image::parse(&input, width, height)?;
+    println!("Checksum: {}", image.checksum(BLACK, WHITE, TRANSPARENT));
+    print!("{}", image.draw_with(style, &palette));
+    // Only images of whole letters spell anything out
+    if let Ok(letters) = image.message() {
+        println!("Message: {}", letters);
+    }
+    if let Some(gif) = gif {
+        animation.palette = palette;
+        fs::write(&gif, image.gif(&animation)?)
+            .map_err(|e| format!("Failed to write {}: {}", gif.display(), e))?;
+    }
+    Ok(())
 }
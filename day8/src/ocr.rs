@@ -0,0 +1,108 @@
+// The letters the puzzles spell out, six pixels high. Most are four wide
+// with a blank column after them, but Y takes up all five.
+const GLYPHS: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const GLYPH_HEIGHT: usize = 6;
+// Each letter's width, including the gap before the next
+const GLYPH_WIDTH: usize = 5;
+
+/// Read the letters spelled out by the lit pixels, given a row at a time.
+pub fn recognize(lit: &[Vec<bool>]) -> Result<String, String> {
+    if lit.len() != GLYPH_HEIGHT {
+        return Err(format!(
+            "Letters are {} pixels high, but the image is {}",
+            GLYPH_HEIGHT,
+            lit.len()
+        ));
+    }
+
+    let width = lit.iter().map(Vec::len).max().unwrap_or(0);
+    let pixel = |x: usize, y: usize| lit[y].get(x).copied().unwrap_or(false);
+    // The last letter may not have a gap after it
+    (0..width)
+        .step_by(GLYPH_WIDTH)
+        .map(|left| {
+            let cell = (0..GLYPH_HEIGHT)
+                .map(|y| {
+                    (left..left + GLYPH_WIDTH)
+                        .map(|x| if pixel(x, y) { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            GLYPHS
+                .iter()
+                .find(|(_, glyph)| {
+                    glyph.iter().zip(&cell).all(|(row, cell)| {
+                        cell.starts_with(row) && !cell[row.len()..].contains('#')
+                    })
+                })
+                .map(|&(letter, _)| letter)
+                .ok_or_else(|| {
+                    format!(
+                        "Couldn't read the letter at column {}:\n{}",
+                        left,
+                        cell.join("\n")
+                    )
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Turn a picture drawn with `#` into lit pixels
+    fn lit(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn reads_letters() {
+        let image = lit(&[
+            "#..#.#...##..#.####.####.",
+            "#.#..#...##..#.#....#....",
+            "##....#.#.####.###..###..",
+            "#.#....#..#..#.#....#....",
+            "#.#....#..#..#.#....#....",
+            "#..#...#..#..#.#....####.",
+        ]);
+        assert_eq!(recognize(&image), Ok("KYHFE".to_string()));
+
+        // Every letter reads as itself
+        for &(letter, glyph) in GLYPHS {
+            assert_eq!(recognize(&lit(&glyph)), Ok(letter.to_string()));
+        }
+
+        let smudged = lit(&["####", "#...", "###.", "#..#", "#...", "####"]);
+        let err = recognize(&smudged).unwrap_err();
+        assert!(
+            err.starts_with("Couldn't read the letter at column 0"),
+            "{}",
+            err
+        );
+        assert!(recognize(&smudged[..5]).is_err());
+        assert_eq!(recognize(&lit(&[""; 6])), Ok(String::new()));
+    }
+}
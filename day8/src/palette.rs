@@ -0,0 +1,88 @@
+use crate::{BLACK, TRANSPARENT, WHITE};
+use colors::Rgb;
+use std::str::FromStr;
+
+/// What each digit in an image means: the color it's drawn in, or `None`
+/// for digits that let the layers behind show through.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Palette(pub [Option<Rgb>; 10]);
+
+impl Default for Palette {
+    /// The puzzle's black, white and transparent, with the digits it never
+    /// uses in colors of their own.
+    fn default() -> Self {
+        let others = [
+            "1f77b4", "ff7f0e", "2ca02c", "d62728", "9467bd", "8c564b", "e377c2",
+        ];
+        let mut colors = [None; 10];
+        for (digit, color) in (3..).zip(others.iter()) {
+            colors[digit] = Some(color.parse().unwrap());
+        }
+        colors[BLACK as usize] = Some(Rgb([0, 0, 0]));
+        colors[WHITE as usize] = Some(Rgb([255, 255, 255]));
+        colors[TRANSPARENT as usize] = None;
+        Palette(colors)
+    }
+}
+
+impl Palette {
+    /// The color `digit` is drawn in, or `None` if it's transparent.
+    pub fn color(&self, digit: u8) -> Option<Rgb> {
+        self.0[digit as usize]
+    }
+
+    pub fn is_transparent(&self, digit: u8) -> bool {
+        self.color(digit).is_none()
+    }
+}
+
+/// Changes to the default palette, written like `2=none,3=ff0000`, with a
+/// color or `none` for each digit that changes.
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut palette = Palette::default();
+        for entry in s.split(',') {
+            let (digit, color) = entry.split_once('=').ok_or_else(|| {
+                format!(
+                    "Expected a digit and its color, like 3=ff0000, found {}",
+                    entry
+                )
+            })?;
+            let digit = digit
+                .parse::<u8>()
+                .ok()
+                .filter(|&d| d < 10)
+                .ok_or_else(|| format!("Expected a digit from 0 to 9, found {}", digit))?;
+            palette.0[digit as usize] = match color {
+                "none" => None,
+                color => Some(color.parse()?),
+            };
+        }
+        Ok(palette)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_palettes() {
+        let palette = Palette::default();
+        assert_eq!(palette.color(BLACK), Some(Rgb([0, 0, 0])));
+        assert_eq!(palette.color(WHITE), Some(Rgb([255, 255, 255])));
+        assert!(palette.is_transparent(TRANSPARENT));
+        assert_eq!(palette.color(6), Some(Rgb([0xd6, 0x27, 0x28])));
+
+        let swapped = "2=#808080,5=none".parse::<Palette>().unwrap();
+        assert_eq!(swapped.color(2), Some(Rgb([128, 128, 128])));
+        assert!(swapped.is_transparent(5));
+        assert_eq!(swapped.color(WHITE), palette.color(WHITE));
+
+        assert!("2".parse::<Palette>().is_err());
+        assert!("10=ffffff".parse::<Palette>().is_err());
+        assert!("3=red".parse::<Palette>().is_err());
+    }
+}
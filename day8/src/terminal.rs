@@ -0,0 +1,135 @@
+use crate::{Layer, Palette};
+use std::str::FromStr;
+
+/// How to draw a layer in the terminal. Terminal cells are about twice as
+/// tall as they're wide, so each style squares its pixels up differently.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Style {
+    /// A digit for each white pixel, and a space for each black one, as
+    /// the puzzle was first solved. Other digits show as themselves, unless
+    /// they're dark.
+    Digits,
+    /// Two full blocks for each white pixel, and shading for any that are
+    /// still transparent
+    Blocks,
+    /// Half blocks, fitting two rows of pixels into each line
+    HalfBlocks,
+    /// Braille dots, fitting two columns and four rows of pixels into each
+    /// character
+    Braille,
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "digits" => Ok(Style::Digits),
+            "blocks" => Ok(Style::Blocks),
+            "half" => Ok(Style::HalfBlocks),
+            "braille" => Ok(Style::Braille),
+            _ => Err(format!(
+                "Expected digits, blocks, half or braille, found {}",
+                s
+            )),
+        }
+    }
+}
+
+impl Style {
+    /// Draw the layer, one line of text at a time.
+    pub fn draw(self, layer: &Layer) -> String {
+        self.draw_with(layer, &Palette::default())
+    }
+
+    /// Draw the layer, one line of text at a time, lighting up the digits
+    /// the palette gives light colors.
+    pub fn draw_with(self, layer: &Layer, palette: &Palette) -> String {
+        let rows = layer.rows().collect::<Vec<_>>();
+        let width = rows.first().map_or(0, |row| row.len());
+        let light = |digit: u8| palette.color(digit).is_some_and(|c| c.is_light());
+        // Past the edges counts as dark
+        let lit = |x: usize, y: usize| rows.get(y).is_some_and(|row| light(row[x]));
+
+        let mut out = String::new();
+        match self {
+            Style::Digits | Style::Blocks => {
+                for row in &rows {
+                    for &pixel in *row {
+                        let transparent = palette.is_transparent(pixel);
+                        match self {
+                            Style::Digits if transparent || light(pixel) => {
+                                out.push(char::from(b'0' + pixel))
+                            }
+                            Style::Digits => out.push(' '),
+                            _ if transparent => out.push_str("░░"),
+                            _ if light(pixel) => out.push_str("██"),
+                            _ => out.push_str("  "),
+                        }
+                    }
+                    out.push('\n');
+                }
+            }
+            Style::HalfBlocks => {
+                for y in (0..rows.len()).step_by(2) {
+                    for x in 0..width {
+                        out.push(match (lit(x, y), lit(x, y + 1)) {
+                            (true, true) => '█',
+                            (true, false) => '▀',
+                            (false, true) => '▄',
+                            (false, false) => ' ',
+                        });
+                    }
+                    out.push('\n');
+                }
+            }
+            Style::Braille => {
+                // The bit for each dot in a braille character, by column
+                // and then row
+                const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+                for y in (0..rows.len()).step_by(4) {
+                    for x in (0..width).step_by(2) {
+                        let mut bits = 0;
+                        for (dx, column) in DOTS.iter().enumerate() {
+                            for (dy, bit) in column.iter().enumerate() {
+                                if x + dx < width && lit(x + dx, y + dy) {
+                                    bits |= bit;
+                                }
+                            }
+                        }
+                        out.push(std::char::from_u32(0x2800 + bits).unwrap());
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Image;
+
+    #[test]
+    fn draws_styles() {
+        // A 3x3 image with a transparent corner: white on the diagonal
+        let layer = Image::parse("100010002", 3, 3).unwrap().composite();
+        assert_eq!(Style::Digits.draw(&layer), "1  \n 1 \n  2\n");
+        assert_eq!(Style::Blocks.draw(&layer), "██    \n  ██  \n    ░░\n");
+        assert_eq!(Style::HalfBlocks.draw(&layer), "▀▄ \n   \n");
+        // Dots 1 and 5 in the first character, and none from the second,
+        // whose pixel is transparent
+        assert_eq!(Style::Braille.draw(&layer), "\u{2811}\u{2800}\n");
+
+        // Other palettes light up other digits
+        let layer = Image::parse("1357", 4, 1).unwrap().composite();
+        let palette = "1=000000,3=none,5=ffff00".parse().unwrap();
+        assert_eq!(Style::Digits.draw_with(&layer, &palette), " 35 \n");
+        assert_eq!(Style::Blocks.draw_with(&layer, &palette), "  ░░██  \n");
+
+        assert_eq!("half".parse(), Ok(Style::HalfBlocks));
+        assert!("ascii".parse::<Style>().is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use aoc_runner::Solution;
+use int_code_emulator::{parse_str, MockIo, Program};
+
+/// The mode the first part of the puzzle runs BOOST in, which tests the
+/// emulator and outputs a keycode.
+pub const TEST_MODE: i128 = 1;
+/// The mode for the second part, which outputs the distress signal's
+/// coordinates.
+pub const SENSOR_BOOST: i128 = 2;
+
+// Run the BOOST program in the given mode and collect everything it
+// outputs
+fn run(mem: &[i128], mode: i128) -> Result<Vec<i128>, String> {
+    let io = MockIo::with_input(&[&format!("{}\n", mode)]);
+    let mut mem = mem.to_vec();
+    let program = Program::new(&mut mem, &io);
+    // Even BOOST's large number tests fit in an i64
+    program.run_fast();
+    io.into_output()
+        .iter()
+        .map(|o| o.parse().map_err(|e| format!("Bad output {}: {}", o, e)))
+        .collect()
+}
+
+/// Run the BOOST program in the given mode and return the last value
+/// it outputs.
+pub fn boost(mem: &[i128], mode: i128) -> Result<i128, String> {
+    run(mem, mode)?
+        .pop()
+        .ok_or_else(|| "Program produced no output".to_owned())
+}
+
+/// Run BOOST in test mode and return the keycode, as long as it's the only
+/// thing it outputs. Otherwise BOOST has found opcodes the emulator runs
+/// wrong, and its outputs are reported as those opcodes instead.
+pub fn self_test(mem: &[i128]) -> Result<i128, String> {
+    match run(mem, TEST_MODE)?.as_slice() {
+        [] => Err("Program produced no output".to_owned()),
+        [keycode] => Ok(*keycode),
+        outputs => {
+            let opcodes = outputs.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+            Err(format!(
+                "BOOST found malfunctioning opcodes: {}",
+                opcodes.join(", ")
+            ))
+        }
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i128>, String> {
+        Ok(parse(input))
+    }
+
+    fn part1(mem: &Vec<i128>) -> Result<String, String> {
+        boost(mem, TEST_MODE).map(|keycode| keycode.to_string())
+    }
+
+    fn part2(mem: &Vec<i128>) -> Result<String, String> {
+        boost(mem, SENSOR_BOOST).map(|coordinates| coordinates.to_string())
+    }
+}
+
+aoc_runner::register!(year: 2019, day: 9, solution: Day9);
+
+pub fn parse(input: &str) -> Vec<i128> {
+    parse_str(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The instructions themselves are tested in int_code_emulator
+    #[test]
+    fn boost_reports_last_output() {
+        // Read the mode, then output it and a large number after it
+        let mem = parse("3,9,4,9,104,1125899906842624,99,0,0,0");
+        assert_eq!(boost(&mem, SENSOR_BOOST), Ok(1125899906842624));
+        assert!(boost(&[99], TEST_MODE).is_err());
+    }
+
+    #[test]
+    fn self_test_reports_opcodes() {
+        // Only the keycode
+        let mem = parse("3,7,104,1234,99,0,0,0");
+        assert_eq!(self_test(&mem), Ok(1234));
+        // Two broken opcodes instead
+        let mem = parse("3,9,104,203,104,9,99,0,0,0");
+        assert_eq!(
+            self_test(&mem),
+            Err("BOOST found malfunctioning opcodes: 203, 9".to_owned())
+        );
+        assert!(self_test(&[3, 3, 99, 0]).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Getting about a grid one step at a time, for the robots that paint,
+//! explore and follow scaffolding. Positions are `(x, y)`, with y growing
+//! downward like the rows of a picture, so up is towards smaller y.
+
+use std::collections::HashMap;
+
+pub type Pos = (isize, isize);
+
+/// Which way something on the grid is facing.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// Which way to turn, a quarter turn at a time.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn(self, turn: Turn) -> Self {
+        match (self, turn) {
+            (Direction::Up, Turn::Left) | (Direction::Down, Turn::Right) => Direction::Left,
+            (Direction::Up, Turn::Right) | (Direction::Down, Turn::Left) => Direction::Right,
+            (Direction::Left, Turn::Right) | (Direction::Right, Turn::Left) => Direction::Up,
+            (Direction::Right, Turn::Right) | (Direction::Left, Turn::Left) => Direction::Down,
+        }
+    }
+
+    /// The position one step from `pos` this way.
+    pub fn next(self, (x, y): Pos) -> Pos {
+        match self {
+            Direction::Up => (x, y - 1),
+            Direction::Right => (x + 1, y),
+            Direction::Down => (x, y + 1),
+            Direction::Left => (x - 1, y),
+        }
+    }
+}
+
+/// A robot that turns and moves about the grid, remembering something about
+/// each position it leaves a mark on.
+#[derive(Clone, Debug)]
+pub struct Robot<T> {
+    pub pos: Pos,
+    pub facing: Direction,
+    visited: HashMap<Pos, T>,
+}
+
+impl<T> Robot<T> {
+    pub fn new(pos: Pos, facing: Direction) -> Self {
+        Self {
+            pos,
+            facing,
+            visited: HashMap::new(),
+        }
+    }
+
+    pub fn turn(&mut self, turn: Turn) {
+        self.facing = self.facing.turn(turn);
+    }
+
+    /// The position one step ahead of the robot.
+    pub fn ahead(&self) -> Pos {
+        self.facing.next(self.pos)
+    }
+
+    /// Take a step forward.
+    pub fn advance(&mut self) {
+        self.pos = self.ahead();
+    }
+
+    /// Leave a mark where the robot stands, returning the one it replaced.
+    pub fn mark(&mut self, mark: T) -> Option<T> {
+        self.visited.insert(self.pos, mark)
+    }
+
+    /// The mark where the robot stands, if it's left one.
+    pub fn here(&self) -> Option<&T> {
+        self.visited.get(&self.pos)
+    }
+
+    /// Every position the robot has left a mark on.
+    pub fn visited(&self) -> &HashMap<Pos, T> {
+        &self.visited
+    }
+
+    pub fn into_visited(self) -> HashMap<Pos, T> {
+        self.visited
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turns_round() {
+        for &direction in &Direction::ALL {
+            assert_eq!(direction.turn(Turn::Left).turn(Turn::Right), direction);
+            let mut round = direction;
+            for _ in 0..4 {
+                round = round.turn(Turn::Right);
+            }
+            assert_eq!(round, direction);
+        }
+        assert_eq!(Direction::Up.turn(Turn::Right), Direction::Right);
+        assert_eq!(Direction::Up.turn(Turn::Left), Direction::Left);
+        assert_eq!(Direction::Up.next((3, 3)), (3, 2));
+        assert_eq!(Direction::Left.next((3, 3)), (2, 3));
+    }
+
+    #[test]
+    fn marks_its_way() {
+        let mut robot = Robot::new((0, 0), Direction::Up);
+        robot.mark('a');
+        robot.advance();
+        robot.turn(Turn::Right);
+        assert_eq!(robot.ahead(), (1, -1));
+        assert_eq!(robot.here(), None);
+        robot.mark('b');
+        assert_eq!(robot.mark('c'), Some('b'));
+        robot.advance();
+        assert_eq!(robot.pos, (1, -1));
+        assert_eq!(robot.facing, Direction::Right);
+        let visited = robot.into_visited();
+        assert_eq!(visited.len(), 2);
+        assert_eq!(visited[&(0, -1)], 'c');
+    }
+}
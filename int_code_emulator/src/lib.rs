@@ -1,35 +1,75 @@
 use std::{
     cell::RefCell,
     collections::VecDeque,
+    convert::TryFrom,
+    fmt::{Debug, Display},
     fs::File,
     io::{self, BufRead, Read, Write},
     sync::mpsc::{Receiver, Sender},
 };
+use tracing::{debug, debug_span, trace};
+
+/// A number memory can hold. Programs run with i128 cells, or with i64
+/// ones while every value they use fits.
+trait Cell: Copy + Debug + Display + Ord + From<bool> + Into<i128> + TryFrom<i128> {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    fn wide(self) -> i128 {
+        self.into()
+    }
+}
+
+impl Cell for i64 {
+    const ZERO: Self = 0;
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        i64::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        i64::checked_mul(self, other)
+    }
+}
+
+impl Cell for i128 {
+    const ZERO: Self = 0;
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        i128::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        i128::checked_mul(self, other)
+    }
+}
 
 #[derive(Copy, Clone, Debug)]
-enum Mode {
+enum Mode<C> {
     Position(usize),
-    Immediate(i128),
+    Immediate(C),
     Relative(isize),
 }
 
-impl Mode {
-    fn from(op_code: usize, mem: &[i128], idx: usize, off: usize) -> Self {
+impl<C: Cell> Mode<C> {
+    fn from(op_code: usize, mem: &[C], idx: usize, off: usize) -> Self {
         let mode = op_code / 10usize.pow(off as u32) % 10;
         let val = mem[idx + off];
         match mode {
-            0 => Mode::Position(val as usize),
+            0 => Mode::Position(val.wide() as usize),
             1 => Mode::Immediate(val),
-            2 => Mode::Relative(val as isize),
+            2 => Mode::Relative(val.wide() as isize),
             _ => panic!("Unexpected mode param {}", mode),
         }
     }
 
-    fn val(&self, mem: &[i128], relative: usize) -> i128 {
+    fn val(&self, mem: &[C], relative: usize) -> C {
         match *self {
             Mode::Position(idx) => {
                 if idx >= mem.len() {
-                    0
+                    C::ZERO
                 } else {
                     mem[idx]
                 }
@@ -38,7 +78,7 @@ impl Mode {
             Mode::Relative(offset) => {
                 let idx = (relative as isize + offset) as usize;
                 if idx >= mem.len() {
-                    0
+                    C::ZERO
                 } else {
                     mem[idx]
                 }
@@ -106,27 +146,38 @@ enum Incr {
     Offset(usize),
     Jump(usize),
     Exit,
+    // A value too large for the cells, which still needs writing to `adr`
+    Overflow { adr: usize, value: i128 },
+}
+
+// Hand a value that doesn't fit back to be written once the cells are
+// larger. Values too large even for i128 cells can't be run at all.
+fn overflow(adr: usize, value: Option<i128>) -> Incr {
+    Incr::Overflow {
+        adr,
+        value: value.expect("Value overflowed 128 bits"),
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
 // Models the possible commands available to this "machine"
-enum OpCode {
-    Add { r1: Mode, r2: Mode, o: Mode },
-    Mul { r1: Mode, r2: Mode, o: Mode },
-    Ipt { adr: Mode },
-    Opt { o: Mode },
-    Jtr { r1: Mode, jmp: Mode },
-    Jfl { r1: Mode, jmp: Mode },
-    Les { r1: Mode, r2: Mode, o: Mode },
-    Eql { r1: Mode, r2: Mode, o: Mode },
-    Crl { off: Mode },
+enum OpCode<C> {
+    Add { r1: Mode<C>, r2: Mode<C>, o: Mode<C> },
+    Mul { r1: Mode<C>, r2: Mode<C>, o: Mode<C> },
+    Ipt { adr: Mode<C> },
+    Opt { o: Mode<C> },
+    Jtr { r1: Mode<C>, jmp: Mode<C> },
+    Jfl { r1: Mode<C>, jmp: Mode<C> },
+    Les { r1: Mode<C>, r2: Mode<C>, o: Mode<C> },
+    Eql { r1: Mode<C>, r2: Mode<C>, o: Mode<C> },
+    Crl { off: Mode<C> },
     Ext,
 }
 
-impl OpCode {
+impl<C: Cell> OpCode<C> {
     /// Generate an OpCode from a specific region of memory
-    fn from(mem: &[i128], idx: usize) -> Self {
-        let instruction = mem[idx];
+    fn from(mem: &[C], idx: usize) -> Self {
+        let instruction = mem[idx].wide();
         let op_code = instruction % 100;
         let mode_spec = instruction as usize / 100;
         let pidx = idx + 1;
@@ -169,12 +220,12 @@ impl OpCode {
                 off: Mode::from(mode_spec, mem, pidx, 0),
             },
             99 => OpCode::Ext,
-            _ => panic!(format!("Unexpected opcode: {}", op_code)),
+            _ => panic!("Unexpected opcode: {}", op_code),
         }
     }
 
     // Execute the OpCode against the passed in memory
-    fn exec(self, mem: &mut Vec<i128>, io: &impl Io, rel: &mut usize) -> Incr {
+    fn exec(self, mem: &mut Vec<C>, io: &impl Io, rel: &mut usize) -> Incr {
         // potentially increase memory size before ops
         match self {
             OpCode::Add { o: adr, .. }
@@ -184,7 +235,7 @@ impl OpCode {
             | OpCode::Ipt { adr } => {
                 let adr = adr.adr(*rel);
                 if adr >= mem.len() {
-                    mem.resize(adr + 1, 0);
+                    mem.resize(adr + 1, C::ZERO);
                 }
             }
             _ => {}
@@ -193,45 +244,54 @@ impl OpCode {
             OpCode::Ext => return Incr::Exit,
             OpCode::Add { r1, r2, o } => {
                 let adr = o.adr(*rel);
-                mem[adr] = r1.val(mem, *rel) + r2.val(mem, *rel)
-            },
+                let (a, b) = (r1.val(mem, *rel), r2.val(mem, *rel));
+                match a.checked_add(b) {
+                    Some(sum) => mem[adr] = sum,
+                    None => return overflow(adr, a.wide().checked_add(b.wide())),
+                }
+            }
             OpCode::Mul { r1, r2, o } => {
                 let adr = o.adr(*rel);
-                mem[adr] = r1.val(mem, *rel) * r2.val(mem, *rel)
-            },
+                let (a, b) = (r1.val(mem, *rel), r2.val(mem, *rel));
+                match a.checked_mul(b) {
+                    Some(product) => mem[adr] = product,
+                    None => return overflow(adr, a.wide().checked_mul(b.wide())),
+                }
+            }
             OpCode::Ipt { adr } => {
                 let adr = adr.adr(*rel);
-                mem[adr] = io.read().trim().parse().expect("Failed to parse input")
+                let value: i128 = io.read().trim().parse().expect("Failed to parse input");
+                debug!(value, "Input");
+                match C::try_from(value) {
+                    Ok(value) => mem[adr] = value,
+                    Err(_) => return overflow(adr, Some(value)),
+                }
+            }
+            OpCode::Opt { o } => {
+                let value = o.val(mem, *rel);
+                debug!(%value, "Output");
+                io.write(&format!("{}", value))
             }
-            OpCode::Opt { o } => io.write(&format!("{}", o.val(mem, *rel))),
             OpCode::Jtr { r1, jmp } => {
-                if r1.val(mem, *rel) != 0 {
-                    return Incr::Jump(jmp.val(mem, *rel) as usize);
+                if r1.val(mem, *rel) != C::ZERO {
+                    return Incr::Jump(jmp.val(mem, *rel).wide() as usize);
                 }
             }
             OpCode::Jfl { r1, jmp } => {
-                if r1.val(mem, *rel) == 0 {
-                    return Incr::Jump(jmp.val(mem, *rel) as usize);
+                if r1.val(mem, *rel) == C::ZERO {
+                    return Incr::Jump(jmp.val(mem, *rel).wide() as usize);
                 }
             }
             OpCode::Les { r1, r2, o } => {
                 let adr = o.adr(*rel);
-                mem[adr] = if r1.val(mem, *rel) < r2.val(mem, *rel) {
-                    1
-                } else {
-                    0
-                }
+                mem[adr] = C::from(r1.val(mem, *rel) < r2.val(mem, *rel))
             }
             OpCode::Eql { r1, r2, o } => {
                 let adr = o.adr(*rel);
-                mem[adr] = if r1.val(mem, *rel) == r2.val(mem, *rel) {
-                    1
-                } else {
-                    0
-                }
+                mem[adr] = C::from(r1.val(mem, *rel) == r2.val(mem, *rel))
             }
             OpCode::Crl { off } => *rel = {
-                let res = *rel as isize + off.val(mem, *rel) as isize;
+                let res = *rel as isize + off.val(mem, *rel).wide() as isize;
                 if res < 0 {
                     panic!("Invalid relative address!")
                 }
@@ -261,6 +321,7 @@ where
     ctr: usize,
     rel: usize,
     io: T,
+    halted: bool,
 }
 
 impl<'a, T> Program<'a, T>
@@ -273,37 +334,163 @@ where
             ctr: 0,
             rel: 0,
             io,
+            halted: false,
         }
     }
 
     // Process the op codes in memory until an exit opcode is reached
     pub fn run(mut self) {
-        loop {
-            let op_code = OpCode::from(self.mem, self.ctr);
+        let _span = debug_span!("run", cells = "i128").entered();
+        while self.step() {}
+    }
+
+    /// Process the op codes like `run`, but with i64 cells while every value
+    /// fits in one, which is faster. The i128 memory is released while the
+    /// i64 copy is in use, so it takes half the memory once started. The
+    /// first time a value doesn't fit, memory moves over to i128 cells and
+    /// the program carries on from there.
+    pub fn run_fast(mut self) {
+        let narrow = self
+            .mem
+            .iter()
+            .map(|&cell| i64::try_from(cell))
+            .collect::<Result<Vec<_>, _>>();
+        let mut mem = match narrow {
+            Ok(mem) => mem,
+            Err(_) => return self.run(),
+        };
+        *self.mem = Vec::new();
+        let span = debug_span!("run", cells = "i64").entered();
+        while !self.halted {
+            let op_code = OpCode::from(&mem, self.ctr);
             let mut relative = self.rel;
+            trace!(ctr = self.ctr, rel = self.rel, op = ?op_code);
+            self.ctr = match op_code.exec(&mut mem, &self.io, &mut relative) {
+                Incr::Offset(offset) => self.ctr + offset,
+                Incr::Jump(address) => address,
+                Incr::Exit => {
+                    self.halted = true;
+                    self.ctr
+                }
+                Incr::Overflow { adr, value } => {
+                    debug!(ctr = self.ctr, value, "Moving to i128 cells");
+                    *self.mem = mem.iter().map(|&cell| i128::from(cell)).collect();
+                    self.mem[adr] = value;
+                    self.ctr += op_code.len();
+                    self.rel = relative;
+                    drop(span);
+                    return self.run();
+                }
+            };
+            self.rel = relative;
+        }
+        *self.mem = mem.into_iter().map(i128::from).collect();
+    }
 
-            #[cfg(debug_assertions)]
-            {
-                let printable_mem = self.mem.iter().enumerate().map(|(i, m)| {
-                    if i == self.ctr {
-                        format!("*{}:{}*", i, m)
-                    } else {
-                        format!("{}:{}", i, m)
-                    }
-                })
-                .collect::<Vec<_>>();
-                println!(
-                    "ctr: {:?}\nrel: {:?}\nmem: [{}]\nop: {:?}\n",
-                    self.ctr, self.rel, printable_mem.join(", "), op_code
-                );
+    /// Whether the next instruction reads input. Programs stepped one
+    /// instruction at a time can use this to stop and wait for it.
+    pub fn wants_input(&self) -> bool {
+        !self.halted && self.mem.get(self.ctr).is_some_and(|op| op % 100 == 3)
+    }
+
+    /// Whether the next instruction writes output.
+    pub fn gives_output(&self) -> bool {
+        !self.halted && self.mem.get(self.ctr).is_some_and(|op| op % 100 == 4)
+    }
+
+    pub fn has_exited(&self) -> bool {
+        self.halted
+    }
+
+    /// Execute instructions until the program has written one output,
+    /// returning false if it exits first. Programs that answer each input
+    /// with a few outputs can be run a response at a time with this.
+    pub fn run_until_output(&mut self) -> bool {
+        loop {
+            let output = self.gives_output();
+            if !self.step() {
+                return false;
+            }
+            if output {
+                return true;
             }
+        }
+    }
+
+    /// Execute a single instruction, returning false once the program has
+    /// exited.
+    pub fn step(&mut self) -> bool {
+        if self.halted {
+            return false;
+        }
+
+        {
+            let op_code = OpCode::from(self.mem, self.ctr);
+            let mut relative = self.rel;
+            trace!(ctr = self.ctr, rel = self.rel, op = ?op_code);
             self.ctr = match op_code.exec(self.mem, &self.io, &mut relative) {
                 Incr::Offset(offset) => self.ctr + offset,
                 Incr::Jump(address) => address,
-                Incr::Exit => break,
+                Incr::Exit => {
+                    self.halted = true;
+                    return false;
+                }
+                Incr::Overflow { .. } => unreachable!("Nothing is larger than i128 cells"),
             };
             self.rel = relative;
         }
+        true
+    }
+}
+
+/// Runs several programs on a single thread, taking turns a few
+/// instructions at a time. Programs that need to wait on each other should
+/// use Io that doesn't block.
+pub struct Scheduler<'a, T>
+where
+    T: Io + 'a,
+{
+    programs: Vec<Program<'a, T>>,
+}
+
+impl<'a, T> Default for Scheduler<'a, T>
+where
+    T: Io + 'a,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> Scheduler<'a, T>
+where
+    T: Io + 'a,
+{
+    pub fn new() -> Self {
+        Self { programs: vec![] }
+    }
+
+    pub fn spawn(&mut self, program: Program<'a, T>) {
+        self.programs.push(program);
+    }
+
+    /// Give every program a turn of up to `steps` instructions, returning
+    /// false once they have all exited.
+    pub fn tick(&mut self, steps: usize) -> bool {
+        let mut running = false;
+        for program in &mut self.programs {
+            for _ in 0..steps {
+                if !program.step() {
+                    break;
+                }
+            }
+            running |= !program.halted;
+        }
+        running
+    }
+
+    pub fn run(mut self, steps: usize) {
+        while self.tick(steps) {}
     }
 }
 
@@ -312,6 +499,12 @@ pub struct MockIo {
     output: RefCell<Vec<String>>,
 }
 
+impl Default for MockIo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MockIo {
     pub fn new() -> Self {
         Self {
@@ -326,11 +519,15 @@ impl MockIo {
             output: RefCell::new(vec![]),
         }
     }
+
+    /// Everything the program has written so far.
+    pub fn into_output(self) -> Vec<String> {
+        self.output.into_inner()
+    }
 }
 
 impl Io for MockIo {
     fn read(&self) -> String {
-        println!("Reading...");
         self.input
             .borrow_mut()
             .pop_front()
@@ -342,6 +539,140 @@ impl Io for MockIo {
     }
 }
 
+/// Io for programs which talk in ASCII. Input text is fed to the program
+/// one character code at a time, and its output is collected back into
+/// text.
+pub struct AsciiIo {
+    input: RefCell<VecDeque<i128>>,
+    output: RefCell<Vec<i128>>,
+}
+
+impl Default for AsciiIo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsciiIo {
+    pub fn new() -> Self {
+        Self::with_input("")
+    }
+
+    pub fn with_input(text: &str) -> Self {
+        let io = Self {
+            input: RefCell::new(VecDeque::new()),
+            output: RefCell::new(vec![]),
+        };
+        io.push_input(text);
+        io
+    }
+
+    pub fn push_input(&self, text: &str) {
+        self.input.borrow_mut().extend(text.bytes().map(i128::from));
+    }
+
+    /// Everything the program has printed so far.
+    pub fn text(&self) -> String {
+        self.output
+            .borrow()
+            .iter()
+            .filter(|&&c| (0..128).contains(&c))
+            .map(|&c| c as u8 as char)
+            .collect()
+    }
+
+    /// Whether there's input left for the program to read.
+    pub fn has_input(&self) -> bool {
+        !self.input.borrow().is_empty()
+    }
+
+    /// Take everything printed since the last call, for programs that
+    /// converse over several prompts.
+    pub fn take_text(&self) -> String {
+        let text = self.text();
+        self.output.borrow_mut().clear();
+        text
+    }
+
+    /// Output too large to be a character, which usually holds an answer.
+    pub fn values(&self) -> Vec<i128> {
+        self.output
+            .borrow()
+            .iter()
+            .copied()
+            .filter(|c| !(0..128).contains(c))
+            .collect()
+    }
+}
+
+impl Io for AsciiIo {
+    fn read(&self) -> String {
+        self.input
+            .borrow_mut()
+            .pop_front()
+            .expect("Ran out of input")
+            .to_string()
+    }
+
+    fn write(&self, output: &str) {
+        self.output
+            .borrow_mut()
+            .push(output.trim().parse().expect("Output should be a number"));
+    }
+}
+
+/// Io for programs that talk in plain numbers, fed to them and collected
+/// back as values rather than text.
+pub struct ValueIo {
+    input: RefCell<VecDeque<i128>>,
+    output: RefCell<Vec<i128>>,
+}
+
+impl Default for ValueIo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValueIo {
+    pub fn new() -> Self {
+        Self {
+            input: RefCell::new(VecDeque::new()),
+            output: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn push_input(&self, value: i128) {
+        self.input.borrow_mut().push_back(value);
+    }
+
+    /// Whether there's input left for the program to read.
+    pub fn has_input(&self) -> bool {
+        !self.input.borrow().is_empty()
+    }
+
+    /// Take every value written since the last call.
+    pub fn take_output(&self) -> Vec<i128> {
+        self.output.borrow_mut().drain(..).collect()
+    }
+}
+
+impl Io for ValueIo {
+    fn read(&self) -> String {
+        self.input
+            .borrow_mut()
+            .pop_front()
+            .expect("Ran out of input")
+            .to_string()
+    }
+
+    fn write(&self, output: &str) {
+        self.output
+            .borrow_mut()
+            .push(output.trim().parse().expect("Output should be a number"));
+    }
+}
+
 pub struct PipedIo {
     rx: (String, Receiver<String>),
     tx: (String, Sender<String>),
@@ -382,17 +713,16 @@ pub fn parse(file_name: &str) -> Result<Vec<i128>, String> {
     let mut contents = String::new();
     file.read_to_string(&mut contents)
         .map_err(|e| format!("Failed to read file: {}", e))?;
-    Ok(contents
-        .split(",")
-        .filter_map(|s| {
-            let s = s.trim();
-            if s.len() == 0 {
-                None
-            } else {
-                s.parse::<i128>().ok()
-            }
-        })
-        .collect::<Vec<_>>())
+    Ok(parse_str(&contents))
+}
+
+/// Parse a comma separated int code program that has already been
+/// read into memory.
+pub fn parse_str(contents: &str) -> Vec<i128> {
+    contents
+        .split(',')
+        .filter_map(|s| s.trim().parse::<i128>().ok())
+        .collect::<Vec<_>>()
 }
 
 #[cfg(test)]
@@ -403,9 +733,13 @@ mod test {
         ($mem:expr, $expected:expr) => {
             let io = MockIo::new();
             let mem = $mem;
+            // The fast path has to end up in the same place
+            let mut fast = mem.clone();
+            Program::new(&mut fast, MockIo::new()).run_fast();
             let program = Program::new(mem, &io);
             let expected = $expected;
             program.run();
+            assert_eq!(&fast, mem, "fast path left different memory");
             assert_eq!(
                 mem.len(),
                 expected.len(),
@@ -457,8 +791,14 @@ mod test {
             let io = MockIo::with_input($input);
             let mem = $mem;
             let expected = $expected;
+            // The fast path has to end up in the same place
+            let mut fast = mem.clone();
+            let fast_io = MockIo::with_input($input);
+            Program::new(&mut fast, &fast_io).run_fast();
             let program = Program::new(mem, &io);
             program.run();
+            assert_eq!(&fast, mem, "fast path left different memory");
+            assert_eq!(fast_io.output, io.output, "fast path output differently");
             assert_eq!(
                 mem.len(),
                 expected.len(),
@@ -789,110 +1129,95 @@ mod test {
         );
     }
 
-    // #[test]
-    // fn amplifiers_case_1_simple() {
-    //     let mem = vec![
-    //         3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
-    //     ];
-    //     let amplifiers = Amplifiers::new(&mem, &[4, 3, 2, 1, 0], Configuration::Simple);
-    //     let res = amplifiers.run();
-    //     assert_eq!(res, 43_210);
-    // }
-
-    // #[test]
-    // fn amplifiers_case_2_simple() {
-    //     let mem = vec![
-    //         3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23,
-    //         99, 0, 0,
-    //     ];
-    //     let amplifiers = Amplifiers::new(&mem, &[0, 1, 2, 3, 4], Configuration::Simple);
-    //     let res = amplifiers.run();
-    //     assert_eq!(res, 54_321);
-    // }
-
-    // #[test]
-    // fn amplifiers_case_3_simple() {
-    //     let mem = vec![
-    //         3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1,
-    //         33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
-    //     ];
-    //     let amplifiers = Amplifiers::new(&mem, &[1, 0, 4, 3, 2], Configuration::Simple);
-    //     let res = amplifiers.run();
-    //     assert_eq!(res, 65_210);
-    // }
-
-    // #[test]
-    // fn amplifiers_case_1_looped() {
-    //     let mem = vec![
-    //         3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
-    //         28, 1005, 28, 6, 99, 0, 0, 5,
-    //     ];
-    //     let amplifiers = Amplifiers::new(&mem, &[9, 8, 7, 6, 5], Configuration::Looped);
-    //     let res = amplifiers.run();
-    //     assert_eq!(res, 139_629_729);
-    // }
-
-    // #[test]
-    // fn amplifiers_case_2_looped() {
-    //     let mem = vec![
-    //         3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001, 54,
-    //         -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53, 55, 53, 4,
-    //         53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10,
-    //     ];
-    //     let amplifiers = Amplifiers::new(&mem, &[9, 7, 8, 5, 6], Configuration::Looped);
-    //     let res = amplifiers.run();
-    //     assert_eq!(res, 18_216);
-    // }
-
-    // #[test]
-    // fn max_thrust_case_1() {
-    //     let mem = vec![
-    //         3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
-    //     ];
-    //     let res = maximize_amplifiers(&mem, Configuration::Simple);
-    //     assert_eq!(res, 43210);
-    // }
-
-    // #[test]
-    // fn max_thrust_case_2() {
-    //     let mem = vec![
-    //         3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23,
-    //         99, 0, 0,
-    //     ];
-    //     let res = maximize_amplifiers(&mem, Configuration::Simple);
-    //     assert_eq!(res, 54_321);
-    // }
-
-    // #[test]
-    // fn max_thrust_case_3() {
-    //     let mem = vec![
-    //         3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1,
-    //         33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
-    //     ];
-    //     let res = maximize_amplifiers(&mem, Configuration::Simple);
-    //     assert_eq!(res, 65_210);
-    // }
-
-    // #[test]
-    // fn max_thrust_case_1_looped() {
-    //     let mem = vec![
-    //         3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
-    //         28, 1005, 28, 6, 99, 0, 0, 5,
-    //     ];
-    //     let res = maximize_amplifiers(&mem, Configuration::Looped);
-    //     assert_eq!(res, 139_629_729);
-    // }
-
-    // #[test]
-    // fn max_thrust_case_2_looped() {
-    //     let mem = vec![
-    //         3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001, 54,
-    //         -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53, 55, 53, 4,
-    //         53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10,
-    //     ];
-    //     let res = maximize_amplifiers(&mem, Configuration::Looped);
-    //     assert_eq!(res, 18_216);
-    // }
+    #[test]
+    fn fast_path_promotes_on_overflow() {
+        // Square 2^32 into address 11, overflowing i64 cells, then double
+        // it into address 12 and output it. Memory has to move over to i128
+        // cells partway through.
+        let mut fast = vec![1102, 4294967296, 4294967296, 11, 1002, 11, 2, 12, 4, 12, 99];
+        let output = "36893488147419103232";
+        let io = MockIo::new();
+        Program::new(&mut fast, &io).run_fast();
+        assert_eq!(fast[11], 1 << 64);
+        assert_eq!(io.into_output(), vec![output]);
+
+        // Input too large for i64 cells promotes them too
+        let io = MockIo::with_input(&[output]);
+        let mut fast = vec![3, 5, 4, 5, 99];
+        Program::new(&mut fast, &io).run_fast();
+        assert_eq!(io.into_output(), vec![output]);
+
+        // Programs that start out too large never use i64 cells
+        let io = MockIo::new();
+        let mut fast = vec![104, 1 << 64, 99];
+        Program::new(&mut fast, &io).run_fast();
+        assert_eq!(io.into_output(), vec!["18446744073709551616"]);
+    }
+
+    #[test]
+    fn ascii_io_speaks_text() {
+        // Echo two characters back, then print 1000
+        let io = AsciiIo::with_input("hi");
+        let mut mem = vec![3, 0, 4, 0, 3, 0, 4, 0, 104, 1000, 104, 10, 99];
+        Program::new(&mut mem, &io).run();
+        assert_eq!(io.text(), "hi\n");
+        assert_eq!(io.values(), vec![1000]);
+    }
+
+    #[test]
+    fn steps_until_input_is_wanted() {
+        // Print "?", then echo a character back
+        let io = AsciiIo::new();
+        let mut mem = vec![104, 63, 3, 20, 4, 20, 99];
+        let mut program = Program::new(&mut mem, &io);
+        while !program.wants_input() {
+            program.step();
+        }
+        assert_eq!(io.take_text(), "?");
+        assert!(!io.has_input());
+        io.push_input("!");
+        while program.step() {}
+        assert!(program.has_exited());
+        assert!(!program.wants_input());
+        assert_eq!(io.take_text(), "!");
+    }
+
+    #[test]
+    fn pauses_on_output() {
+        // Answer each input with it and its double, until given a 0
+        let io = ValueIo::new();
+        let mut mem = vec![
+            3, 20, 1006, 20, 16, 4, 20, 1002, 20, 2, 21, 4, 21, 1105, 1, 0, 99, 0, 0, 0, 0, 0,
+        ];
+        let mut program = Program::new(&mut mem, &io);
+        io.push_input(3);
+        assert!(program.run_until_output());
+        assert_eq!(io.take_output(), vec![3]);
+        assert!(program.run_until_output());
+        assert_eq!(io.take_output(), vec![6]);
+        io.push_input(5);
+        assert!(program.run_until_output());
+        assert!(program.run_until_output());
+        assert_eq!(io.take_output(), vec![5, 10]);
+        io.push_input(0);
+        assert!(!program.run_until_output());
+        assert!(program.has_exited());
+        assert!(io.take_output().is_empty());
+    }
+
+    #[test]
+    fn scheduler_takes_turns() {
+        let io = AsciiIo::new();
+        let mut first = vec![104, 1001, 104, 1001, 99];
+        let mut second = vec![104, 1002, 104, 1002, 104, 1002, 99];
+        let mut scheduler = Scheduler::new();
+        scheduler.spawn(Program::new(&mut first, &io));
+        scheduler.spawn(Program::new(&mut second, &io));
+        assert!(scheduler.tick(1));
+        assert_eq!(io.values(), vec![1001, 1002]);
+        scheduler.run(1);
+        assert_eq!(io.values(), vec![1001, 1002, 1001, 1002, 1002]);
+    }
 
     #[test]
     fn test_op_extra_case_1() {